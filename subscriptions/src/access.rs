@@ -0,0 +1,68 @@
+elrond_wasm::imports!();
+
+use crate::merkle::Hash;
+use crate::structs::Plan;
+
+#[elrond_wasm::module]
+pub trait AccessModule:
+    crate::storage::StorageModule +
+    crate::events::EventsModule +
+    crate::merkle::MerkleModule +
+    crate::denylist::DenylistModule +
+    crate::pricing::PricingModule +
+    crate::activation::ActivationModule +
+    crate::plans::PlansModule +
+    crate::holders::HoldersModule +
+    crate::bundles::BundlesModule +
+    crate::private_plans::PrivatePlansModule
+{
+    /**
+     * It checks that a user can start or renew a subscription to a plan and returns the plan
+     *
+     * The payments, the stakes and the loyalty points redemptions all go through these checks
+     *
+     * Validation
+     * [x] It should check that the contract is enabled
+     * [x] It should check that the migration mode is not started
+     * [x] It should check that the subscription plan exists
+     * [x] It should check that the subscription plan is enabled, or deprecated and the user is a subscriber
+     * [x] It should check that the user is not denied
+     * [x] It should check that the user has an active subscription for the plan prerequisites
+     * [x] It should check that the user is allowed to purchase the plan, when the plan is private
+     * [x] It should check that the user is a holder, when the plan is gated
+     * [x] It should check that the user does not keep the plan active with a locked stake
+     */
+    fn require_can_subscribe(
+        &self,
+        plan_id: &ManagedBuffer,
+        user_address: &ManagedAddress,
+        proof: &ManagedVec<Self::Api, Hash<Self::Api>>,
+        is_holder: bool
+    ) -> Plan<Self::Api> {
+        require!(self.enabled().get(), "Contract is not enabled");
+        require!(!self.migration_mode().get(), "Payments are disabled during the migration");
+        require!(self.plan_ids().contains(plan_id), "This plan does not exist");
+
+        let plan = self.plans(plan_id).get();
+
+        self.require_plan_available(&plan, user_address);
+        self.require_not_denied(user_address);
+        self.require_prerequisites(user_address, plan_id);
+        self.require_plan_access(plan_id, user_address, proof);
+        self.require_holder_access(plan_id, is_holder);
+        self.require_not_staked(user_address, plan_id);
+
+        plan
+    }
+
+    /**
+     * It checks that a user does not keep a plan active with a locked stake
+     */
+    #[inline]
+    fn require_not_staked(&self, user_address: &ManagedAddress, plan_id: &ManagedBuffer) {
+        require!(
+            self.user_stakes(user_address, plan_id).is_empty() || self.user_stakes(user_address, plan_id).get().unbonding_ends_at != 0,
+            "The plan is active through a stake"
+        );
+    }
+}
@@ -0,0 +1,136 @@
+elrond_wasm::imports!();
+
+use crate::structs::PaymentSettlement;
+
+#[elrond_wasm::module]
+pub trait AccumulationModule:
+    crate::storage::StorageModule +
+    crate::pricing::PricingModule
+{
+    /**
+     * It allows the smart contract owner to keep the payments in the contract until they are swept to the payment address
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     *
+     * Actions
+     * [x] It should set the status to `accumulation_enabled` storage
+     */
+    #[only_owner]
+    #[endpoint(setAccumulationMode)]
+    fn set_accumulation_mode(&self, enabled: bool) {
+        self.accumulation_enabled().set(enabled);
+    }
+
+    /**
+     * It allows the smart contract owner to set the accumulated balance of a token that triggers a sweep
+     *
+     * A zero threshold disables the automatic sweep for the token
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the token is valid
+     *
+     * Actions
+     * [x] It should set the threshold to `sweep_threshold` storage, or clear it when zero
+     */
+    #[only_owner]
+    #[endpoint(setSweepThreshold)]
+    fn set_sweep_threshold(&self, token_identifier: EgldOrEsdtTokenIdentifier<Self::Api>, threshold: BigUint<Self::Api>) {
+        require!(token_identifier.is_valid(), "Invalid token");
+
+        if threshold == 0 {
+            self.sweep_threshold(&token_identifier).clear();
+        } else {
+            self.sweep_threshold(&token_identifier).set(threshold);
+        }
+    }
+
+    /**
+     * It allows the payment address to receive the accumulated payments
+     *
+     * Validation
+     * [x] It should check that the caller is the payment address
+     * [x] It should check that there are accumulated payments
+     *
+     * Actions
+     * [x] It should call `sweep_accumulated_balances` function
+     */
+    #[endpoint(sweep)]
+    fn sweep(&self) {
+        let caller = self.blockchain().get_caller();
+
+        require!(!self.payment_address().is_empty(), "Payment address is not configured");
+        require!(caller == self.payment_address().get(), "Only the payment address can sweep the payments");
+        require!(!self.accumulated_token_ids().is_empty(), "Nothing to sweep");
+
+        self.sweep_accumulated_balances();
+    }
+
+    /**
+     * It checks if a payment should be accumulated in the contract
+     *
+     * The SFT, NFT or meta-ESDT payments, the payments that are burned and the payments with a plan token payment address are never accumulated
+     */
+    #[inline]
+    fn is_accumulated(&self, plan_id: &ManagedBuffer, payment: &EgldOrEsdtTokenPayment<Self::Api>) -> bool {
+        self.accumulation_enabled().get() &&
+            payment.token_nonce == 0 &&
+            self.plan_token_payment_address(plan_id, &payment.token_identifier).is_empty() &&
+            self.plan_token_settlement(plan_id, &payment.token_identifier).get() == PaymentSettlement::Forward
+    }
+
+    /**
+     * It accumulates a payment in the contract
+     *
+     * Actions
+     * [x] It should add the payment to the `accumulated_balance` and `accumulated_token_ids` storage
+     * [x] It should sweep the accumulated payments when the token threshold is reached
+     */
+    fn accumulate_payment(&self, payment: &EgldOrEsdtTokenPayment<Self::Api>) {
+        self.accumulated_token_ids().insert(payment.token_identifier.clone());
+
+        let balance = self.accumulated_balance(&payment.token_identifier).update(|balance| {
+            *balance += &payment.amount;
+            balance.clone()
+        });
+        let threshold = self.sweep_threshold(&payment.token_identifier).get();
+
+        if threshold > 0 && balance >= threshold {
+            self.sweep_accumulated_balances();
+        }
+    }
+
+    /**
+     * It sends the accumulated balances to their token payment addresses
+     *
+     * The ESDT balances sent to `payment_address` are sent in one multi-transfer
+     *
+     * Actions
+     * [x] It should clear the `accumulated_balance` and `accumulated_token_ids` storage
+     * [x] It should send the accumulated EGLD and ESDT balances to the token payment addresses
+     */
+    fn sweep_accumulated_balances(&self) {
+        let payment_address = self.payment_address().get();
+        let mut esdt_payments = ManagedVec::new();
+
+        for token_identifier in self.accumulated_token_ids().iter() {
+            let amount = self.accumulated_balance(&token_identifier).get();
+            let address = self.get_token_payment_address(&token_identifier);
+
+            self.accumulated_balance(&token_identifier).clear();
+
+            if token_identifier.is_egld() || address != payment_address {
+                self.send().direct(&address, &token_identifier, 0, &amount);
+            } else {
+                esdt_payments.push(EsdtTokenPayment::new(token_identifier.unwrap_esdt(), 0, amount));
+            }
+        }
+
+        self.accumulated_token_ids().clear();
+
+        if !esdt_payments.is_empty() {
+            self.send().direct_multi(&payment_address, &esdt_payments);
+        }
+    }
+}
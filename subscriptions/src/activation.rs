@@ -0,0 +1,325 @@
+elrond_wasm::imports!();
+
+use crate::structs::{Plan, PlanStatus, UserPlan, ActivationSource, ExpiryEntry, RenewalMode};
+
+pub const EXPIRY_BUCKET_DURATION: u64 = 86_400;
+pub const LIFETIME_EXPIRY: u64 = u64::MAX;
+
+#[elrond_wasm::module]
+pub trait ActivationModule:
+    crate::storage::StorageModule
+{
+    /**
+     * It activates the user subscription
+     *
+     * Validation
+     * [x] It should check that the subscription plan is not archived
+     * [x] It should check that the user does not already have a lifetime subscription, for lifetime plan payments
+     * [x] It should check that the plan maximum horizon is not exceeded, for payments and loyalty redemptions
+     *
+     * Actions
+     * [x] It should add the user address to the `users` storage
+     * [x] It should add the user address to the `plan_subscribers` storage, when it is not there or was pruned
+     * [x] It should extend or reset the user's subscription expiration date, based on the plan renewal mode for payments
+     * [x] It should extend the user's subscription expiration date for the other sources, so it is never lowered
+     * [x] It should end the user's subscription pause, when the expiration date is reset
+     * [x] It should update the user's subscription `last_activated` date
+     * [x] It should move the user subscription to its new expiry bucket
+     * [x] It should increase the plan activations count for the activation source
+     *
+     * It returns the timestamp when the activated period starts
+     */
+    #[inline]
+    fn activate_user_plan(
+        &self,
+        user_address: &ManagedAddress,
+        plan: &Plan<Self::Api>,
+        validity: u64,
+        source: ActivationSource
+    ) -> u64 {
+        require!(plan.status != PlanStatus::Archived, "Subscription plan is archived");
+
+        let current_timestamp = self.blockchain().get_block_timestamp();
+        let renewal_mode = if source == ActivationSource::Payment {
+            self.plan_renewal_mode(&plan.id).get()
+        } else {
+            RenewalMode::Extend
+        };
+
+        if !self.users().contains(user_address) {
+            self.users().insert(user_address.clone());
+        }
+
+        let mut user_plan: UserPlan<Self::Api>;
+        let period_start: u64;
+
+        if !self.user_plan_ids(user_address).contains(&plan.id) {
+            self.user_plan_ids(user_address).insert(plan.id.clone());
+            self.add_plan_subscriber(user_address, &plan.id);
+
+            period_start = current_timestamp;
+            user_plan = UserPlan {
+                plan_id: plan.id.clone(),
+                expires_at: self.get_activation_expiry(renewal_mode, current_timestamp, validity),
+                first_subscribed: current_timestamp,
+                last_subscribed: current_timestamp,
+                paused_until: 0,
+            };
+        } else {
+            user_plan = self.user_plans(user_address, &plan.id).get();
+            let is_active = user_plan.expires_at > current_timestamp;
+
+            require!(
+                !is_active || renewal_mode != RenewalMode::Lifetime,
+                "The user already has a lifetime subscription"
+            );
+
+            self.add_plan_subscriber(user_address, &plan.id);
+            self.unindex_user_plan_expiry(user_address, &plan.id, user_plan.expires_at);
+
+            if is_active && renewal_mode == RenewalMode::Extend {
+                period_start = user_plan.expires_at;
+                user_plan.expires_at = user_plan.expires_at.saturating_add(validity);
+            } else {
+                period_start = current_timestamp;
+                user_plan.expires_at = self.get_activation_expiry(renewal_mode, current_timestamp, validity);
+
+                if user_plan.paused_until > current_timestamp {
+                    self.unindex_user_plan_pause(user_address, &plan.id, user_plan.paused_until);
+                    user_plan.paused_until = current_timestamp;
+                }
+            }
+
+            user_plan.last_subscribed = current_timestamp;
+        }
+
+        let is_prepaid = source == ActivationSource::Payment || source == ActivationSource::Loyalty;
+
+        if is_prepaid && renewal_mode != RenewalMode::Lifetime {
+            let max_horizon = self.plan_max_horizon(&plan.id).get();
+
+            require!(
+                max_horizon == 0 || user_plan.expires_at - current_timestamp <= max_horizon,
+                "The plan maximum prepaid period is exceeded"
+            );
+        }
+
+        self.index_user_plan_expiry(user_address, &plan.id, user_plan.expires_at);
+        self.user_plans(user_address, &plan.id).set(user_plan);
+        self.plan_activations(&plan.id, &source).update(|activations| *activations += 1);
+
+        period_start
+    }
+
+    /**
+     * It returns the expiration date of a period activated at a timestamp
+     */
+    #[inline]
+    fn get_activation_expiry(&self, renewal_mode: RenewalMode, timestamp: u64, validity: u64) -> u64 {
+        if renewal_mode == RenewalMode::Lifetime {
+            return LIFETIME_EXPIRY
+        }
+
+        timestamp.saturating_add(validity)
+    }
+
+    /**
+     * It checks if a user subscription gives access at a timestamp [not expired and not paused]
+     */
+    #[inline]
+    fn is_user_plan_active(&self, user_plan: &UserPlan<Self::Api>, timestamp: u64) -> bool {
+        user_plan.expires_at > timestamp && user_plan.paused_until <= timestamp
+    }
+
+    /**
+     * It returns the subscription time left to a user at a timestamp, without the unused pause time
+     */
+    #[inline]
+    fn get_remaining_time(&self, user_plan: &UserPlan<Self::Api>, timestamp: u64) -> u64 {
+        let unused_pause_time = user_plan.paused_until.saturating_sub(timestamp);
+
+        user_plan.expires_at.saturating_sub(timestamp).saturating_sub(unused_pause_time)
+    }
+
+    /**
+     * It removes the user subscription entry for a plan
+     *
+     * The quota usage is kept, so the quota periods are not restarted when the user subscribes again
+     *
+     * Actions
+     * [x] It should call `unindex_user_plan` function
+     * [x] It should remove the plan from the `user_plan_ids` storage
+     * [x] It should remove the user subscription from the `user_plans` storage
+     * [x] It should remove the user delegates for the plan
+     */
+    fn remove_user_plan(&self, user_address: &ManagedAddress, plan_id: &ManagedBuffer) {
+        self.unindex_user_plan(user_address, plan_id);
+
+        self.user_plan_ids(user_address).swap_remove(plan_id);
+        self.user_plans(user_address, plan_id).clear();
+
+        for delegate_address in self.user_delegates(user_address, plan_id).iter() {
+            self.delegate_owner(&delegate_address, plan_id).clear();
+        }
+
+        self.user_delegates(user_address, plan_id).clear();
+        self.user_delegate_removed_at(user_address, plan_id).clear();
+    }
+
+    /**
+     * It removes a user subscription from the subscriber indexes, the user subscription entry is kept
+     *
+     * Actions
+     * [x] It should remove the user subscription from its pause and expiry buckets
+     * [x] It should remove the user address from the `plan_subscribers` storage
+     * [x] It should decrease the `plan_active_subscriber_count` storage, when the subscription was counted
+     */
+    fn unindex_user_plan(&self, user_address: &ManagedAddress, plan_id: &ManagedBuffer) {
+        if !self.user_plans(user_address, plan_id).is_empty() {
+            let user_plan = self.user_plans(user_address, plan_id).get();
+
+            // the pause is ended first, so a paused subscription is not removed twice from the active count
+            self.unindex_user_plan_pause(user_address, plan_id, user_plan.paused_until);
+            self.unindex_user_plan_expiry(user_address, plan_id, user_plan.expires_at);
+        }
+
+        if self.plan_subscribers(plan_id).swap_remove(user_address) {
+            self.plan_active_subscriber_count(plan_id).update(|count| *count = count.saturating_sub(1));
+        }
+    }
+
+    /**
+     * It adds a user address to the plan subscribers, the subscription is counted as active
+     */
+    fn add_plan_subscriber(&self, user_address: &ManagedAddress, plan_id: &ManagedBuffer) {
+        if self.plan_subscribers(plan_id).insert(user_address.clone()) {
+            self.plan_active_subscriber_count(plan_id).update(|count| *count += 1);
+        }
+    }
+
+    /**
+     * It adds a user subscription pause to the bucket of its end day, the subscription is no longer counted as active
+     */
+    fn index_user_plan_pause(&self, user_address: &ManagedAddress, plan_id: &ManagedBuffer, paused_until: u64) {
+        let entry = ExpiryEntry {
+            address: user_address.clone(),
+            plan_id: plan_id.clone(),
+            expires_at: paused_until,
+        };
+
+        if self.pause_buckets(paused_until / EXPIRY_BUCKET_DURATION).insert(entry) {
+            self.plan_active_subscriber_count(plan_id).update(|count| *count = count.saturating_sub(1));
+        }
+    }
+
+    /**
+     * It removes a user subscription pause from the bucket of its end day, the subscription is counted as active again
+     */
+    fn unindex_user_plan_pause(&self, user_address: &ManagedAddress, plan_id: &ManagedBuffer, paused_until: u64) {
+        let entry = ExpiryEntry {
+            address: user_address.clone(),
+            plan_id: plan_id.clone(),
+            expires_at: paused_until,
+        };
+
+        if self.pause_buckets(paused_until / EXPIRY_BUCKET_DURATION).remove(&entry) {
+            self.plan_active_subscriber_count(plan_id).update(|count| *count += 1);
+        }
+    }
+
+    /**
+     * It changes the expiration date of a user subscription
+     *
+     * Actions
+     * [x] It should move the user subscription to its new expiry bucket
+     * [x] It should update the user subscription expiration date
+     */
+    fn set_user_plan_expiry(&self, user_address: &ManagedAddress, plan_id: &ManagedBuffer, expires_at: u64) {
+        let mut user_plan = self.user_plans(user_address, plan_id).get();
+
+        self.unindex_user_plan_expiry(user_address, plan_id, user_plan.expires_at);
+        self.index_user_plan_expiry(user_address, plan_id, expires_at);
+
+        user_plan.expires_at = expires_at;
+        self.user_plans(user_address, plan_id).set(user_plan);
+    }
+
+    /**
+     * It adds a user subscription to the bucket of its expiration day, if it is not already there
+     */
+    fn index_user_plan_expiry(&self, user_address: &ManagedAddress, plan_id: &ManagedBuffer, expires_at: u64) {
+        let day = expires_at / EXPIRY_BUCKET_DURATION;
+        let entry = ExpiryEntry {
+            address: user_address.clone(),
+            plan_id: plan_id.clone(),
+            expires_at,
+        };
+
+        if !self.expiry_buckets(day).insert(entry) {
+            return;
+        }
+
+        self.plan_expiry_counts(plan_id, day).update(|count| *count += 1);
+    }
+
+    /**
+     * It removes a user subscription from the bucket of its expiration day
+     */
+    fn unindex_user_plan_expiry(&self, user_address: &ManagedAddress, plan_id: &ManagedBuffer, expires_at: u64) {
+        let day = expires_at / EXPIRY_BUCKET_DURATION;
+        let entry = ExpiryEntry {
+            address: user_address.clone(),
+            plan_id: plan_id.clone(),
+            expires_at,
+        };
+
+        if !self.expiry_buckets(day).remove(&entry) {
+            return;
+        }
+
+        let count = self.plan_expiry_counts(plan_id, day).update(|count| {
+            *count -= 1;
+            *count
+        });
+
+        if count == 0 {
+            self.plan_expiry_counts(plan_id, day).clear();
+        }
+    }
+
+    /**
+     * It computes and saves historical data
+     *
+     * Actions
+     * [x] It should add the payment token to the `payment_token_ids` storage
+     * [x] It should save the payment amount to the `payment_token_amounts` storage
+     * [x] It should increase the payments count in the `payment_token_counts` storage
+     * [x] It should add the payment token to the `user_payment_tokens` storage
+     * [x] It should save the payment amount to the `user_payment_amounts` storage
+     */
+    #[inline]
+    fn register_historic_data(
+        &self,
+        caller: &ManagedAddress,
+        payment: &EgldOrEsdtTokenPayment<Self::Api>
+    ) {
+        let mut existing_user_payment = BigUint::zero();
+        let mut existing_token_payment = BigUint::zero();
+
+        if self.payment_token_ids().contains(&payment.token_identifier) {
+            existing_token_payment = self.payment_token_amounts(&payment.token_identifier).get();
+        } else {
+            self.payment_token_ids().insert(payment.token_identifier.clone());
+        }
+
+        if self.user_payment_tokens(caller).contains(&payment.token_identifier) {
+            existing_user_payment = self.user_payment_amounts(caller, &payment.token_identifier).get();
+        } else {
+            self.user_payment_tokens(caller).insert(payment.token_identifier.clone());
+        }
+
+        self.payment_token_amounts(&payment.token_identifier).set(existing_token_payment + &payment.amount);
+        self.payment_token_counts(&payment.token_identifier).update(|count| *count += 1);
+        self.user_payment_amounts(caller, &payment.token_identifier).set(existing_user_payment + &payment.amount);
+    }
+}
@@ -0,0 +1,174 @@
+elrond_wasm::imports!();
+
+use crate::structs::{ActivationSource, PlanStatus, RenewalMode};
+
+#[elrond_wasm::module]
+pub trait BundlesModule:
+    crate::storage::StorageModule +
+    crate::pricing::PricingModule +
+    crate::activation::ActivationModule
+{
+    /**
+     * It allows the smart contract owner to require an active subscription to another plan before paying for a plan
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the subscription plan is not a merchant plan
+     * [x] It should check that both plans exist and are different
+     * [x] It should check that the prerequisite is not already set
+     *
+     * Actions
+     * [x] It should add the prerequisite to `plan_prerequisites` storage
+     */
+    #[only_owner]
+    #[endpoint(addSubscriptionPlanPrerequisite)]
+    fn add_subscription_plan_prerequisite(&self, plan_id: ManagedBuffer<Self::Api>, prerequisite_id: ManagedBuffer<Self::Api>) {
+        self.require_owner_plan(&plan_id);
+        require!(self.plan_ids().contains(&prerequisite_id), "The prerequisite plan does not exist");
+        require!(plan_id != prerequisite_id, "Invalid prerequisite plan");
+        require!(!self.plan_prerequisites(&plan_id).contains(&prerequisite_id), "This prerequisite is already set");
+
+        self.plan_prerequisites(&plan_id).insert(prerequisite_id);
+    }
+
+    /**
+     * It allows the smart contract owner to remove a prerequisite of a plan
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the subscription plan is not a merchant plan
+     * [x] It should check that the prerequisite is set
+     *
+     * Actions
+     * [x] It should remove the prerequisite from `plan_prerequisites` storage
+     */
+    #[only_owner]
+    #[endpoint(removeSubscriptionPlanPrerequisite)]
+    fn remove_subscription_plan_prerequisite(&self, plan_id: ManagedBuffer<Self::Api>, prerequisite_id: ManagedBuffer<Self::Api>) {
+        self.require_owner_plan(&plan_id);
+        require!(self.plan_prerequisites(&plan_id).contains(&prerequisite_id), "This prerequisite is not set");
+
+        self.plan_prerequisites(&plan_id).remove(&prerequisite_id);
+    }
+
+    /**
+     * It allows the smart contract owner to add a component plan to a bundle plan, or to update its validity
+     *
+     * The bundles cannot be nested, a bundle plan cannot be a component of another bundle
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the subscription plan is not a merchant plan
+     * [x] It should check that both plans exist and are different
+     * [x] It should check that the bundle plan is not a component and the component plan is not a bundle
+     * [x] It should check that the component plan is not archived and is not a lifetime plan
+     * [x] It should check that the validity is not zero
+     *
+     * Actions
+     * [x] It should add the component to `plan_bundle_components` storage if it does not exist
+     * [x] It should set the validity to `plan_bundle_validity` storage
+     * [x] It should mark the component plan in the `bundle_component_plans` storage
+     */
+    #[only_owner]
+    #[endpoint(setSubscriptionPlanBundleComponent)]
+    fn set_subscription_plan_bundle_component(
+        &self,
+        plan_id: ManagedBuffer<Self::Api>,
+        component_id: ManagedBuffer<Self::Api>,
+        validity: u64
+    ) {
+        self.require_owner_plan(&plan_id);
+        require!(self.plan_ids().contains(&component_id), "The component plan does not exist");
+        require!(plan_id != component_id, "Invalid component plan");
+        require!(self.bundle_component_count(&plan_id).get() == 0, "A bundle component cannot be a bundle");
+        require!(self.plan_bundle_components(&component_id).is_empty(), "A bundle cannot be a bundle component");
+        require!(self.plans(&component_id).get().status != PlanStatus::Archived, "An archived plan cannot be a bundle component");
+        require!(
+            self.plan_renewal_mode(&component_id).get() != RenewalMode::Lifetime,
+            "A lifetime plan cannot be a bundle component"
+        );
+        require!(validity > 0, "Invalid validity");
+
+        if self.plan_bundle_components(&plan_id).insert(component_id.clone()) {
+            self.bundle_component_count(&component_id).update(|count| *count += 1);
+        }
+
+        self.plan_bundle_validity(&plan_id, &component_id).set(validity);
+    }
+
+    /**
+     * It allows the smart contract owner to remove a component plan from a bundle plan
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the subscription plan is not a merchant plan
+     * [x] It should check that the plan is a component of the bundle
+     *
+     * Actions
+     * [x] It should remove the component from `plan_bundle_components` storage
+     * [x] It should clear the `plan_bundle_validity` storage
+     * [x] It should update the `bundle_component_count` storage
+     */
+    #[only_owner]
+    #[endpoint(removeSubscriptionPlanBundleComponent)]
+    fn remove_subscription_plan_bundle_component(&self, plan_id: ManagedBuffer<Self::Api>, component_id: ManagedBuffer<Self::Api>) {
+        self.require_owner_plan(&plan_id);
+        require!(self.plan_bundle_components(&plan_id).contains(&component_id), "This plan is not a bundle component");
+
+        self.plan_bundle_components(&plan_id).remove(&component_id);
+        self.plan_bundle_validity(&plan_id, &component_id).clear();
+        self.bundle_component_count(&component_id).update(|count| *count = count.saturating_sub(1));
+    }
+
+    /**
+     * It checks that a user has an active subscription for all the plan prerequisites
+     *
+     * The prerequisites that were removed from the plans are ignored
+     */
+    fn require_prerequisites(&self, user_address: &ManagedAddress, plan_id: &ManagedBuffer) {
+        let current_timestamp = self.blockchain().get_block_timestamp();
+
+        for prerequisite_id in self.plan_prerequisites(plan_id).iter() {
+            if !self.plan_ids().contains(&prerequisite_id) {
+                continue;
+            }
+
+            require!(
+                self.user_plan_ids(user_address).contains(&prerequisite_id) &&
+                    self.is_user_plan_active(&self.user_plans(user_address, &prerequisite_id).get(), current_timestamp),
+                "A prerequisite plan is not active"
+            );
+        }
+    }
+
+    /**
+     * It activates the component plans of a bundle plan, each one with its own validity
+     *
+     * The components that were removed from the plans, archived since, or already active for life are ignored
+     */
+    fn activate_bundle_components(&self, user_address: &ManagedAddress, plan_id: &ManagedBuffer) {
+        let current_timestamp = self.blockchain().get_block_timestamp();
+
+        for component_id in self.plan_bundle_components(plan_id).iter() {
+            if !self.plan_ids().contains(&component_id) {
+                continue;
+            }
+
+            let component = self.plans(&component_id).get();
+
+            if component.status == PlanStatus::Archived {
+                continue;
+            }
+
+            if self.plan_renewal_mode(&component_id).get() == RenewalMode::Lifetime &&
+                self.user_plan_ids(user_address).contains(&component_id) &&
+                self.user_plans(user_address, &component_id).get().expires_at > current_timestamp {
+                continue;
+            }
+
+            let validity = self.plan_bundle_validity(plan_id, &component_id).get();
+
+            self.activate_user_plan(user_address, &component, validity, ActivationSource::Bundle);
+        }
+    }
+}
@@ -0,0 +1,167 @@
+elrond_wasm::imports!();
+
+use crate::structs::DelegateSettings;
+
+#[elrond_wasm::module]
+pub trait DelegatesModule:
+    crate::storage::StorageModule +
+    crate::pricing::PricingModule +
+    crate::activation::ActivationModule +
+    crate::events::EventsModule +
+    crate::denylist::DenylistModule
+{
+    /**
+     * It allows the smart contract owner to let the subscribers of a plan share their access with delegates
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the subscription plan exists
+     * [x] It should check that the subscription plan is not a merchant plan
+     * [x] It should check that the maximum number of delegates is not zero
+     *
+     * Actions
+     * [x] It should set the settings to `plan_delegate_settings` storage
+     */
+    #[only_owner]
+    #[endpoint(setSubscriptionPlanDelegateSettings)]
+    fn set_subscription_plan_delegate_settings(&self, plan_id: ManagedBuffer<Self::Api>, max_delegates: u32, cooldown: u64) {
+        self.require_owner_plan(&plan_id);
+        require!(max_delegates > 0, "Invalid maximum number of delegates");
+
+        self.plan_delegate_settings(&plan_id).set(DelegateSettings {
+            max_delegates,
+            cooldown,
+        });
+    }
+
+    /**
+     * It allows the smart contract owner to stop the subscribers of a plan from adding delegates
+     *
+     * The existing delegates keep their access until they are removed
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the subscription plan is not a merchant plan
+     * [x] It should check that the plan has delegate settings
+     *
+     * Actions
+     * [x] It should clear the `plan_delegate_settings` storage
+     */
+    #[only_owner]
+    #[endpoint(removeSubscriptionPlanDelegateSettings)]
+    fn remove_subscription_plan_delegate_settings(&self, plan_id: ManagedBuffer<Self::Api>) {
+        self.require_owner_plan(&plan_id);
+        require!(!self.plan_delegate_settings(&plan_id).is_empty(), "This plan does not allow delegates");
+
+        self.plan_delegate_settings(&plan_id).clear();
+    }
+
+    /**
+     * It allows a subscriber to share its plan access with a delegate address
+     *
+     * Validation
+     * [x] It should check that the plan allows delegates
+     * [x] It should check that the caller is subscribed to the plan
+     * [x] It should check that the delegate is not the caller and is not already a delegate for this plan
+     * [x] It should check that the caller and the delegate are not denied
+     * [x] It should check that the maximum number of delegates is not reached
+     * [x] It should check that the swap cooldown since the last delegate removal has passed
+     *
+     * Actions
+     * [x] It should add the delegate to the `user_delegates` storage
+     * [x] It should set the caller as the delegate owner in the `delegate_owner` storage
+     * [x] It should emit the `addDelegate` event
+     */
+    #[endpoint(addDelegate)]
+    fn add_delegate(&self, plan_id: ManagedBuffer<Self::Api>, delegate_address: ManagedAddress<Self::Api>) {
+        let caller = self.blockchain().get_caller();
+
+        require!(!self.plan_delegate_settings(&plan_id).is_empty(), "This plan does not allow delegates");
+        require!(self.user_plan_ids(&caller).contains(&plan_id), "The user is not subscribed to this plan");
+        require!(delegate_address != caller, "Invalid delegate");
+        require!(self.delegate_owner(&delegate_address, &plan_id).is_empty(), "This address is already a delegate for this plan");
+        self.require_not_denied(&caller);
+        self.require_not_denied(&delegate_address);
+
+        let settings = self.plan_delegate_settings(&plan_id).get();
+
+        require!(
+            self.user_delegates(&caller, &plan_id).len() < settings.max_delegates as usize,
+            "The maximum number of delegates is reached"
+        );
+        require!(
+            self.user_delegate_removed_at(&caller, &plan_id).is_empty() ||
+                self.blockchain().get_block_timestamp() >= self.user_delegate_removed_at(&caller, &plan_id).get() + settings.cooldown,
+            "A delegate was removed too recently"
+        );
+
+        self.user_delegates(&caller, &plan_id).insert(delegate_address.clone());
+        self.delegate_owner(&delegate_address, &plan_id).set(&caller);
+
+        self.add_delegate_event(&caller, &plan_id, &delegate_address);
+    }
+
+    /**
+     * It allows a subscriber to stop sharing its plan access with a delegate
+     *
+     * Validation
+     * [x] It should check that the address is a delegate of the caller for this plan
+     *
+     * Actions
+     * [x] It should remove the delegate from the `user_delegates` storage
+     * [x] It should clear the `delegate_owner` storage
+     * [x] It should set the current timestamp to the `user_delegate_removed_at` storage
+     * [x] It should emit the `removeDelegate` event
+     */
+    #[endpoint(removeDelegate)]
+    fn remove_delegate(&self, plan_id: ManagedBuffer<Self::Api>, delegate_address: ManagedAddress<Self::Api>) {
+        let caller = self.blockchain().get_caller();
+
+        require!(self.user_delegates(&caller, &plan_id).contains(&delegate_address), "This address is not a delegate");
+
+        self.user_delegates(&caller, &plan_id).remove(&delegate_address);
+        self.delegate_owner(&delegate_address, &plan_id).clear();
+        self.user_delegate_removed_at(&caller, &plan_id).set(self.blockchain().get_block_timestamp());
+
+        self.remove_delegate_event(&caller, &plan_id, &delegate_address);
+    }
+
+    /**
+     * It checks if an address has access to a plan, through its own subscription or as a delegate
+     *
+     * The denied addresses have no access, and neither do the delegates of a denied subscriber
+     */
+    fn has_plan_access(&self, address: &ManagedAddress, plan_id: &ManagedBuffer) -> bool {
+        self.get_plan_access_owner(address, plan_id).is_some()
+    }
+
+    /**
+     * It returns the subscriber giving an address access to a plan [the address itself or the user that added it as a delegate]
+     */
+    fn get_plan_access_owner(&self, address: &ManagedAddress, plan_id: &ManagedBuffer) -> Option<ManagedAddress<Self::Api>> {
+        let current_timestamp = self.blockchain().get_block_timestamp();
+
+        if self.denied_addresses().contains(address) {
+            return None
+        }
+
+        if self.user_plan_ids(address).contains(plan_id) &&
+            self.is_user_plan_active(&self.user_plans(address, plan_id).get(), current_timestamp) {
+            return Some(address.clone())
+        }
+
+        if self.delegate_owner(address, plan_id).is_empty() {
+            return None
+        }
+
+        let owner_address = self.delegate_owner(address, plan_id).get();
+
+        if !self.denied_addresses().contains(&owner_address) &&
+            self.user_plan_ids(&owner_address).contains(plan_id) &&
+            self.is_user_plan_active(&self.user_plans(&owner_address, plan_id).get(), current_timestamp) {
+            return Some(owner_address)
+        }
+
+        None
+    }
+}
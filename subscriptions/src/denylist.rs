@@ -0,0 +1,55 @@
+elrond_wasm::imports!();
+
+#[elrond_wasm::module]
+pub trait DenylistModule:
+    crate::storage::StorageModule +
+    crate::events::EventsModule
+{
+    /**
+     * It allows the smart contract owner to add addresses to the denylist
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     *
+     * Actions
+     * [x] It should add the addresses to `denied_addresses` storage
+     * [x] It should emit the `denyAddress` event for each newly denied address
+     */
+    #[only_owner]
+    #[endpoint(addDeniedAddresses)]
+    fn add_denied_addresses(&self, addresses: MultiValueEncoded<ManagedAddress<Self::Api>>) {
+        for address in addresses.into_iter() {
+            if self.denied_addresses().insert(address.clone()) {
+                self.deny_address_event(&address);
+            }
+        }
+    }
+
+    /**
+     * It allows the smart contract owner to remove addresses from the denylist
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     *
+     * Actions
+     * [x] It should remove the addresses from `denied_addresses` storage
+     * [x] It should emit the `allowAddress` event for each removed address
+     */
+    #[only_owner]
+    #[endpoint(removeDeniedAddresses)]
+    fn remove_denied_addresses(&self, addresses: MultiValueEncoded<ManagedAddress<Self::Api>>) {
+        for address in addresses.into_iter() {
+            if self.denied_addresses().remove(&address) {
+                self.allow_address_event(&address);
+            }
+        }
+    }
+
+    /**
+     * It checks that an address is not on the denylist
+     */
+    #[inline]
+    fn require_not_denied(&self, address: &ManagedAddress) {
+        require!(!self.denied_addresses().contains(address), "This address is denied");
+    }
+}
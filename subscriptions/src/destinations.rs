@@ -0,0 +1,97 @@
+elrond_wasm::imports!();
+
+#[elrond_wasm::module]
+pub trait DestinationsModule:
+    crate::storage::StorageModule +
+    crate::pricing::PricingModule
+{
+    /**
+     * It allows the smart contract owner to send the payments made with a token to another address than `payment_address`
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the token is valid
+     *
+     * Actions
+     * [x] It should set the address to `token_payment_address` storage
+     */
+    #[only_owner]
+    #[endpoint(setTokenPaymentAddress)]
+    fn set_token_payment_address(&self, token_identifier: EgldOrEsdtTokenIdentifier<Self::Api>, address: ManagedAddress<Self::Api>) {
+        require!(token_identifier.is_valid(), "Invalid token");
+
+        self.token_payment_address(&token_identifier).set(address);
+    }
+
+    /**
+     * It allows the smart contract owner to send the payments made with a token to `payment_address` again
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the token has a payment address
+     *
+     * Actions
+     * [x] It should clear the `token_payment_address` storage
+     */
+    #[only_owner]
+    #[endpoint(removeTokenPaymentAddress)]
+    fn remove_token_payment_address(&self, token_identifier: EgldOrEsdtTokenIdentifier<Self::Api>) {
+        require!(!self.token_payment_address(&token_identifier).is_empty(), "This token has no payment address");
+
+        self.token_payment_address(&token_identifier).clear();
+    }
+
+    /**
+     * It allows the smart contract owner to send the payments made with a plan token to another address than the token payment address
+     *
+     * These payments are forwarded on each payment, they are never held in escrow or accumulated
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the subscription plan is not a merchant plan
+     * [x] It should check that the token is enabled for this subscription plan
+     *
+     * Actions
+     * [x] It should set the address to `plan_token_payment_address` storage
+     */
+    #[only_owner]
+    #[endpoint(setSubscriptionPlanTokenPaymentAddress)]
+    fn set_subscription_plan_token_payment_address(
+        &self,
+        plan_id: ManagedBuffer<Self::Api>,
+        token_identifier: EgldOrEsdtTokenIdentifier<Self::Api>,
+        address: ManagedAddress<Self::Api>
+    ) {
+        self.require_owner_plan(&plan_id);
+        require!(self.is_plan_token(&plan_id, &token_identifier), "No price configured for this plan/token combination");
+
+        self.plan_token_payment_address(&plan_id, &token_identifier).set(address);
+    }
+
+    /**
+     * It allows the smart contract owner to send the payments made with a plan token to the token payment address again
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the subscription plan is not a merchant plan
+     * [x] It should check that the plan token has a payment address
+     *
+     * Actions
+     * [x] It should clear the `plan_token_payment_address` storage
+     */
+    #[only_owner]
+    #[endpoint(removeSubscriptionPlanTokenPaymentAddress)]
+    fn remove_subscription_plan_token_payment_address(
+        &self,
+        plan_id: ManagedBuffer<Self::Api>,
+        token_identifier: EgldOrEsdtTokenIdentifier<Self::Api>
+    ) {
+        self.require_owner_plan(&plan_id);
+        require!(
+            !self.plan_token_payment_address(&plan_id, &token_identifier).is_empty(),
+            "This plan/token combination has no payment address"
+        );
+
+        self.plan_token_payment_address(&plan_id, &token_identifier).clear();
+    }
+}
@@ -0,0 +1,219 @@
+elrond_wasm::imports!();
+
+use crate::structs::{Entitlement, QuotaUsage};
+
+#[elrond_wasm::module]
+pub trait EntitlementsModule:
+    crate::storage::StorageModule +
+    crate::pricing::PricingModule +
+    crate::activation::ActivationModule +
+    crate::events::EventsModule +
+    crate::denylist::DenylistModule +
+    crate::delegates::DelegatesModule
+{
+    /**
+     * It allows the smart contract owner to set the quota granted by a plan for an entitlement key
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the subscription plan exists
+     * [x] It should check that the subscription plan is not a merchant plan
+     * [x] It should check that the quota is not zero
+     *
+     * Actions
+     * [x] It should add the key to `plan_entitlement_keys` storage if it does not exist
+     * [x] It should add the plan to `entitlement_plan_ids` storage if it does not exist
+     * [x] It should set the entitlement to `plan_entitlements` storage
+     */
+    #[only_owner]
+    #[endpoint(setSubscriptionPlanEntitlement)]
+    fn set_subscription_plan_entitlement(
+        &self,
+        plan_id: ManagedBuffer<Self::Api>,
+        key: ManagedBuffer<Self::Api>,
+        quota: u64,
+        reset_period: u64
+    ) {
+        self.require_owner_plan(&plan_id);
+        require!(quota > 0, "Invalid quota");
+
+        self.plan_entitlement_keys(&plan_id).insert(key.clone());
+        self.entitlement_plan_ids(&key).insert(plan_id.clone());
+        self.plan_entitlements(&plan_id, &key).set(Entitlement {
+            quota,
+            reset_period,
+        });
+    }
+
+    /**
+     * It allows the smart contract owner to remove an entitlement from a plan
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the subscription plan is not a merchant plan
+     * [x] It should check that the plan has the entitlement
+     *
+     * Actions
+     * [x] It should remove the key from `plan_entitlement_keys` storage
+     * [x] It should remove the plan from `entitlement_plan_ids` storage
+     * [x] It should clear the `plan_entitlements` storage
+     */
+    #[only_owner]
+    #[endpoint(removeSubscriptionPlanEntitlement)]
+    fn remove_subscription_plan_entitlement(&self, plan_id: ManagedBuffer<Self::Api>, key: ManagedBuffer<Self::Api>) {
+        self.require_owner_plan(&plan_id);
+        require!(self.plan_entitlement_keys(&plan_id).contains(&key), "This plan has no such entitlement");
+
+        self.plan_entitlement_keys(&plan_id).remove(&key);
+        self.entitlement_plan_ids(&key).remove(&plan_id);
+        self.plan_entitlements(&plan_id, &key).clear();
+    }
+
+    /**
+     * It allows the smart contract owner to add a contract that can consume the user quotas
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the address is a smart contract
+     * [x] It should check that the address is not already a consumer
+     *
+     * Actions
+     * [x] It should add the address to `quota_consumers` storage
+     */
+    #[only_owner]
+    #[endpoint(addQuotaConsumer)]
+    fn add_quota_consumer(&self, address: ManagedAddress<Self::Api>) {
+        require!(self.blockchain().is_smart_contract(&address), "The consumer should be a smart contract");
+        require!(!self.quota_consumers().contains(&address), "This address is already a consumer");
+
+        self.quota_consumers().insert(address);
+    }
+
+    /**
+     * It allows the smart contract owner to remove a quota consumer
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the address is a consumer
+     *
+     * Actions
+     * [x] It should remove the address from `quota_consumers` storage
+     */
+    #[only_owner]
+    #[endpoint(removeQuotaConsumer)]
+    fn remove_quota_consumer(&self, address: &ManagedAddress<Self::Api>) {
+        require!(self.quota_consumers().contains(address), "This address is not a consumer");
+
+        self.quota_consumers().remove(address);
+    }
+
+    /**
+     * It allows a consumer contract to use a part of a user quota
+     *
+     * The amount is taken from the plans granting the entitlement that the user can access, in order, until it is covered
+     * A delegate uses the quota of the subscriber that added it
+     *
+     * Validation
+     * [x] It should check that the caller is a quota consumer
+     * [x] It should check that the amount is not zero
+     * [x] It should check that the accessible plans have enough quota left for the current period
+     *
+     * Actions
+     * [x] It should increase the subscriber usage in `user_quota_usage` storage, starting a new period when the previous one ended
+     * [x] It should emit the `consumeQuota` event
+     */
+    #[endpoint(consumeQuota)]
+    fn consume_quota(&self, address: ManagedAddress<Self::Api>, key: ManagedBuffer<Self::Api>, amount: u64) {
+        let caller = self.blockchain().get_caller();
+        let current_timestamp = self.blockchain().get_block_timestamp();
+
+        require!(self.quota_consumers().contains(&caller), "Only a quota consumer can consume quotas");
+        require!(amount > 0, "Invalid amount");
+
+        let mut left_amount = amount;
+
+        for plan_id in self.entitlement_plan_ids(&key).iter() {
+            if left_amount == 0 {
+                break;
+            }
+
+            let subscriber = match self.get_plan_access_owner(&address, &plan_id) {
+                Some(subscriber) => subscriber,
+                None => continue,
+            };
+
+            let entitlement = self.plan_entitlements(&plan_id, &key).get();
+            let mut usage = self.get_quota_usage(&subscriber, &plan_id, &key, &entitlement, current_timestamp);
+            let consumed = left_amount.min(entitlement.quota.saturating_sub(usage.used));
+
+            if consumed == 0 {
+                continue;
+            }
+
+            usage.used += consumed;
+            self.user_quota_usage(&subscriber, &plan_id, &key).set(usage);
+            left_amount -= consumed;
+        }
+
+        require!(left_amount == 0, "Quota exceeded");
+
+        self.consume_quota_event(&address, &key, &caller, amount);
+    }
+
+    /**
+     * It returns the quota left to a user for an entitlement key, in the current period of the plans it can access
+     */
+    fn get_remaining_quota(&self, address: &ManagedAddress, key: &ManagedBuffer) -> u64 {
+        let current_timestamp = self.blockchain().get_block_timestamp();
+        let mut remaining_quota = 0;
+
+        for plan_id in self.entitlement_plan_ids(key).iter() {
+            let subscriber = match self.get_plan_access_owner(address, &plan_id) {
+                Some(subscriber) => subscriber,
+                None => continue,
+            };
+
+            let entitlement = self.plan_entitlements(&plan_id, key).get();
+            let usage = self.get_quota_usage(&subscriber, &plan_id, key, &entitlement, current_timestamp);
+
+            remaining_quota += entitlement.quota.saturating_sub(usage.used);
+        }
+
+        remaining_quota
+    }
+
+    /**
+     * It returns the quota usage of a subscriber in the period including a timestamp
+     *
+     * The first period starts with the first usage and the next ones follow each other every reset period,
+     * the usage of the previous periods is ignored
+     */
+    fn get_quota_usage(
+        &self,
+        subscriber: &ManagedAddress,
+        plan_id: &ManagedBuffer,
+        key: &ManagedBuffer,
+        entitlement: &Entitlement,
+        timestamp: u64
+    ) -> QuotaUsage {
+        if self.user_quota_usage(subscriber, plan_id, key).is_empty() {
+            return QuotaUsage {
+                period_start: timestamp,
+                used: 0,
+            }
+        }
+
+        let usage = self.user_quota_usage(subscriber, plan_id, key).get();
+
+        if entitlement.reset_period == 0 || timestamp < usage.period_start + entitlement.reset_period {
+            return usage
+        }
+
+        let elapsed_periods = (timestamp - usage.period_start) / entitlement.reset_period;
+
+        QuotaUsage {
+            period_start: usage.period_start + elapsed_periods * entitlement.reset_period,
+            used: 0,
+        }
+    }
+}
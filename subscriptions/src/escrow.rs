@@ -0,0 +1,200 @@
+elrond_wasm::imports!();
+
+use crate::structs::{EscrowCheckpoint, EscrowDayChange, PaymentSettlement, RenewalMode};
+
+pub const ESCROW_RELEASE_DURATION: u64 = 86_400;
+
+#[elrond_wasm::module]
+pub trait EscrowModule:
+    crate::storage::StorageModule +
+    crate::pricing::PricingModule
+{
+    /**
+     * It allows the smart contract owner to enable or disable the revenue escrow
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     *
+     * Actions
+     * [x] It should set the status to `escrow_enabled` storage
+     */
+    #[only_owner]
+    #[endpoint(setEscrowMode)]
+    fn set_escrow_mode(&self, enabled: bool) {
+        self.escrow_enabled().set(enabled);
+    }
+
+    /**
+     * It allows the payment address to claim the revenue earned so far
+     *
+     * Validation
+     * [x] It should check that the caller is the payment address
+     * [x] It should check that there is revenue to claim
+     *
+     * Actions
+     * [x] It should update the `escrow_checkpoint` storage until the current day
+     * [x] It should add the claimed revenue to the `escrow_claimed` storage
+     * [x] It should send the earned revenue to the token payment addresses
+     */
+    #[endpoint(claimRevenue)]
+    fn claim_revenue(&self) {
+        let caller = self.blockchain().get_caller();
+
+        require!(!self.payment_address().is_empty(), "Payment address is not configured");
+        require!(caller == self.payment_address().get(), "Only the payment address can claim the revenue");
+
+        let mut has_claimed = false;
+
+        for token_identifier in self.escrow_token_ids().iter() {
+            let checkpoint = self.update_escrow_checkpoint(&token_identifier);
+            let amount = checkpoint.earned - self.escrow_claimed(&token_identifier).get();
+
+            if amount > 0 {
+                self.escrow_claimed(&token_identifier).update(|claimed| *claimed += &amount);
+                self.send().direct(&self.get_token_payment_address(&token_identifier), &token_identifier, 0, &amount);
+                has_claimed = true;
+            }
+        }
+
+        require!(has_claimed, "Nothing to claim");
+    }
+
+    /**
+     * It checks if a payment should be held in escrow
+     *
+     * The SFT, NFT or meta-ESDT payments, the payments that are burned, the payments with a plan token payment address
+     * and the lifetime plan payments are never held in escrow
+     */
+    #[inline]
+    fn is_escrowed(&self, plan_id: &ManagedBuffer, payment: &EgldOrEsdtTokenPayment<Self::Api>) -> bool {
+        self.escrow_enabled().get() &&
+            payment.token_nonce == 0 &&
+            self.plan_renewal_mode(plan_id).get() != RenewalMode::Lifetime &&
+            self.plan_token_payment_address(plan_id, &payment.token_identifier).is_empty() &&
+            self.plan_token_settlement(plan_id, &payment.token_identifier).get() == PaymentSettlement::Forward
+    }
+
+    /**
+     * It holds a payment in escrow, to be released daily over the purchased period
+     *
+     * The payment is split into equal daily amounts [the division remainder is released on the last day]
+     * and only the release rate changes are stored, so the claims do not depend on the number of payments
+     *
+     * Actions
+     * [x] It should add the payment token to the `escrow_token_ids` storage
+     * [x] It should add the payment to the `escrow_total` storage
+     * [x] It should add the daily amount to the release rate from the first day of the period
+     * [x] It should remove the daily amount from the release rate on the last day of the period
+     */
+    fn escrow_payment(&self, payment: &EgldOrEsdtTokenPayment<Self::Api>, starts_at: u64, ends_at: u64) {
+        if !self.escrow_token_ids().contains(&payment.token_identifier) {
+            self.escrow_token_ids().insert(payment.token_identifier.clone());
+        }
+
+        let mut checkpoint = self.update_escrow_checkpoint(&payment.token_identifier);
+
+        let start_day = starts_at / ESCROW_RELEASE_DURATION;
+        let end_day = core::cmp::max((ends_at + ESCROW_RELEASE_DURATION - 1) / ESCROW_RELEASE_DURATION, start_day + 1);
+        let daily_amount = &payment.amount / (end_day - start_day);
+        let remainder = &payment.amount - &(&daily_amount * (end_day - start_day));
+
+        if start_day <= checkpoint.day {
+            checkpoint.rate += &daily_amount;
+            self.escrow_checkpoint(&payment.token_identifier).set(&checkpoint);
+        } else {
+            let mut start_change = self.get_escrow_day_change(&payment.token_identifier, start_day);
+            start_change.starting_rate += &daily_amount;
+            self.escrow_day_changes(&payment.token_identifier, start_day).set(&start_change);
+        }
+
+        let mut end_change = self.get_escrow_day_change(&payment.token_identifier, end_day);
+        end_change.ending_rate += &daily_amount;
+        end_change.remainder += &remainder;
+        self.escrow_day_changes(&payment.token_identifier, end_day).set(&end_change);
+
+        self.escrow_total(&payment.token_identifier).update(|total| *total += &payment.amount);
+    }
+
+    /**
+     * It processes the release rate changes until the current day and returns the updated checkpoint
+     *
+     * Actions
+     * [x] It should clear the processed days from the `escrow_day_changes` storage
+     * [x] It should set the checkpoint to the `escrow_checkpoint` storage
+     */
+    fn update_escrow_checkpoint(&self, token_identifier: &EgldOrEsdtTokenIdentifier) -> EscrowCheckpoint<Self::Api> {
+        let current_day = self.blockchain().get_block_timestamp() / ESCROW_RELEASE_DURATION;
+        let checkpoint = self.advance_escrow_checkpoint(token_identifier, current_day, true);
+
+        self.escrow_checkpoint(token_identifier).set(&checkpoint);
+
+        checkpoint
+    }
+
+    /**
+     * It returns the checkpoint of a token advanced until a day
+     *
+     * The cost grows with the days elapsed since the last update of the token, not with the number of payments
+     */
+    fn advance_escrow_checkpoint(
+        &self,
+        token_identifier: &EgldOrEsdtTokenIdentifier,
+        day: u64,
+        clear_changes: bool
+    ) -> EscrowCheckpoint<Self::Api> {
+        if self.escrow_checkpoint(token_identifier).is_empty() {
+            return EscrowCheckpoint {
+                day,
+                rate: BigUint::zero(),
+                earned: BigUint::zero(),
+            }
+        }
+
+        let mut checkpoint = self.escrow_checkpoint(token_identifier).get();
+
+        while checkpoint.day < day {
+            checkpoint.earned += &checkpoint.rate;
+            checkpoint.day += 1;
+
+            let change = self.get_escrow_day_change(token_identifier, checkpoint.day);
+
+            checkpoint.rate += change.starting_rate;
+            checkpoint.rate -= change.ending_rate;
+            checkpoint.earned += change.remainder;
+
+            if clear_changes {
+                self.escrow_day_changes(token_identifier, checkpoint.day).clear();
+            }
+        }
+
+        checkpoint
+    }
+
+    /**
+     * It returns the release rate changes of a token in a day
+     */
+    fn get_escrow_day_change(&self, token_identifier: &EgldOrEsdtTokenIdentifier, day: u64) -> EscrowDayChange<Self::Api> {
+        if self.escrow_day_changes(token_identifier, day).is_empty() {
+            return EscrowDayChange {
+                starting_rate: BigUint::zero(),
+                ending_rate: BigUint::zero(),
+                remainder: BigUint::zero(),
+            }
+        }
+
+        self.escrow_day_changes(token_identifier, day).get()
+    }
+
+    /**
+     * It returns the earned [not claimed] and deferred revenue for a token
+     */
+    fn get_escrow_revenue(&self, token_identifier: &EgldOrEsdtTokenIdentifier) -> (BigUint<Self::Api>, BigUint<Self::Api>) {
+        let current_day = self.blockchain().get_block_timestamp() / ESCROW_RELEASE_DURATION;
+        let checkpoint = self.advance_escrow_checkpoint(token_identifier, current_day, false);
+
+        let deferred = self.escrow_total(token_identifier).get() - &checkpoint.earned;
+        let earned = checkpoint.earned - self.escrow_claimed(token_identifier).get();
+
+        (earned, deferred)
+    }
+}
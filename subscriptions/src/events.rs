@@ -0,0 +1,178 @@
+elrond_wasm::imports!();
+
+#[elrond_wasm::module]
+pub trait EventsModule {
+    /**
+     * Emitted when a subscription is granted without a payment
+     */
+    #[event("grantSubscription")]
+    fn grant_subscription_event(
+        &self,
+        #[indexed] user_address: &ManagedAddress,
+        #[indexed] plan_id: &ManagedBuffer,
+        #[indexed] granted_by: &ManagedAddress,
+        duration: u64
+    );
+
+    /**
+     * Emitted when a subscription is revoked
+     */
+    #[event("revokeSubscription")]
+    fn revoke_subscription_event(
+        &self,
+        #[indexed] user_address: &ManagedAddress,
+        #[indexed] plan_id: &ManagedBuffer,
+        revoked_by: &ManagedAddress
+    );
+
+    /**
+     * Emitted when an address is added to the denylist
+     */
+    #[event("denyAddress")]
+    fn deny_address_event(&self, #[indexed] address: &ManagedAddress);
+
+    /**
+     * Emitted when an address is removed from the denylist
+     */
+    #[event("allowAddress")]
+    fn allow_address_event(&self, #[indexed] address: &ManagedAddress);
+
+    /**
+     * Emitted when a voucher is redeemed
+     */
+    #[event("redeemVoucher")]
+    fn redeem_voucher_event(
+        &self,
+        #[indexed] user_address: &ManagedAddress,
+        #[indexed] plan_id: &ManagedBuffer,
+        #[indexed] nonce: u64,
+        duration: u64
+    );
+
+    /**
+     * Emitted when a user pauses a subscription
+     */
+    #[event("pauseSubscription")]
+    fn pause_subscription_event(
+        &self,
+        #[indexed] user_address: &ManagedAddress,
+        #[indexed] plan_id: &ManagedBuffer,
+        paused_until: u64
+    );
+
+    /**
+     * Emitted when a user resumes a paused subscription
+     */
+    #[event("resumeSubscription")]
+    fn resume_subscription_event(
+        &self,
+        #[indexed] user_address: &ManagedAddress,
+        #[indexed] plan_id: &ManagedBuffer,
+        expires_at: u64
+    );
+
+    /**
+     * Emitted when a consumer contract uses a part of a user quota
+     */
+    #[event("consumeQuota")]
+    fn consume_quota_event(
+        &self,
+        #[indexed] user_address: &ManagedAddress,
+        #[indexed] key: &ManagedBuffer,
+        #[indexed] consumer: &ManagedAddress,
+        amount: u64
+    );
+
+    /**
+     * Emitted when a reporter submits the add-on usage of a user
+     */
+    #[event("reportUsage")]
+    fn report_usage_event(
+        &self,
+        #[indexed] user_address: &ManagedAddress,
+        #[indexed] plan_id: &ManagedBuffer,
+        #[indexed] addon_id: &ManagedBuffer,
+        #[indexed] period: u64,
+        units: u64
+    );
+
+    /**
+     * Emitted when the add-on usage of a user is settled from its deposit
+     */
+    #[event("settleUsage")]
+    fn settle_usage_event(
+        &self,
+        #[indexed] user_address: &ManagedAddress,
+        #[indexed] plan_id: &ManagedBuffer,
+        #[indexed] addon_id: &ManagedBuffer,
+        #[indexed] period: u64,
+        charge: &BigUint
+    );
+
+    /**
+     * Emitted when a user shares a plan access with a delegate
+     */
+    #[event("addDelegate")]
+    fn add_delegate_event(
+        &self,
+        #[indexed] user_address: &ManagedAddress,
+        #[indexed] plan_id: &ManagedBuffer,
+        #[indexed] delegate_address: &ManagedAddress
+    );
+
+    /**
+     * Emitted when a user stops sharing a plan access with a delegate
+     */
+    #[event("removeDelegate")]
+    fn remove_delegate_event(
+        &self,
+        #[indexed] user_address: &ManagedAddress,
+        #[indexed] plan_id: &ManagedBuffer,
+        #[indexed] delegate_address: &ManagedAddress
+    );
+
+    /**
+     * Emitted when a user earns loyalty points with a payment
+     */
+    #[event("earnPoints")]
+    fn earn_points_event(
+        &self,
+        #[indexed] user_address: &ManagedAddress,
+        #[indexed] plan_id: &ManagedBuffer,
+        points: &BigUint
+    );
+
+    /**
+     * Emitted when a user redeems loyalty points for subscription time
+     */
+    #[event("redeemPoints")]
+    fn redeem_points_event(
+        &self,
+        #[indexed] user_address: &ManagedAddress,
+        #[indexed] plan_id: &ManagedBuffer,
+        #[indexed] points: &BigUint,
+        duration: u64
+    );
+
+    /**
+     * Emitted when a user locks tokens to keep a plan active
+     */
+    #[event("stake")]
+    fn stake_event(
+        &self,
+        #[indexed] user_address: &ManagedAddress,
+        #[indexed] plan_id: &ManagedBuffer,
+        amount: &BigUint
+    );
+
+    /**
+     * Emitted when a user starts the unbonding of a stake
+     */
+    #[event("unstake")]
+    fn unstake_event(
+        &self,
+        #[indexed] user_address: &ManagedAddress,
+        #[indexed] plan_id: &ManagedBuffer,
+        unbonding_ends_at: u64
+    );
+}
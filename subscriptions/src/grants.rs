@@ -0,0 +1,154 @@
+elrond_wasm::imports!();
+
+use crate::structs::ActivationSource;
+
+#[elrond_wasm::module]
+pub trait GrantsModule:
+    crate::storage::StorageModule +
+    crate::events::EventsModule +
+    crate::pricing::PricingModule +
+    crate::activation::ActivationModule +
+    crate::denylist::DenylistModule +
+    crate::merkle::MerkleModule
+{
+    /**
+     * It allows the smart contract owner to add an address that can grant and revoke subscriptions
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the address is not already a grant manager
+     *
+     * Actions
+     * [x] It should add the address to `grant_managers` storage
+     */
+    #[only_owner]
+    #[endpoint(addGrantManager)]
+    fn add_grant_manager(&self, address: ManagedAddress<Self::Api>) {
+        require!(!self.grant_managers().contains(&address), "This address is already a grant manager");
+
+        self.grant_managers().insert(address);
+    }
+
+    /**
+     * It allows the smart contract owner to remove a grant manager
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the address is a grant manager
+     *
+     * Actions
+     * [x] It should remove the address from `grant_managers` storage
+     */
+    #[only_owner]
+    #[endpoint(removeGrantManager)]
+    fn remove_grant_manager(&self, address: &ManagedAddress<Self::Api>) {
+        require!(self.grant_managers().contains(address), "This address is not a grant manager");
+
+        self.grant_managers().remove(address);
+    }
+
+    /**
+     * It allows the SC owner or a grant manager to give a complimentary subscription to an address
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner or a grant manager
+     * [x] It should check that the plan exists
+     * [x] It should check that the subscription plan is not a merchant plan
+     * [x] It should check that the duration is not zero
+     * [x] It should check that the address is not denied
+     *
+     * Actions
+     * [x] It should call `activate_user_plan` function with the `Grant` source
+     * [x] It should add the duration to the `user_granted_time` storage
+     * [x] It should emit the `grantSubscription` event
+     */
+    #[endpoint(grantSubscription)]
+    fn grant_subscription(
+        &self,
+        address: ManagedAddress<Self::Api>,
+        plan_id: ManagedBuffer<Self::Api>,
+        duration: u64
+    ) {
+        self.require_grant_manager();
+        self.grant_user_plan(&address, &plan_id, duration);
+    }
+
+    /**
+     * It allows the SC owner or a grant manager to give complimentary subscriptions in batch
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner or a grant manager
+     * [x] It should run the `grantSubscription` validations for every entry
+     *
+     * Actions
+     * [x] It should run the `grantSubscription` actions for every entry
+     */
+    #[endpoint(grantSubscriptions)]
+    fn grant_subscriptions(
+        &self,
+        grants: MultiValueEncoded<MultiValue3<ManagedAddress<Self::Api>, ManagedBuffer<Self::Api>, u64>>
+    ) {
+        self.require_grant_manager();
+
+        for grant in grants.into_iter() {
+            let (address, plan_id, duration) = grant.into_tuple();
+            self.grant_user_plan(&address, &plan_id, duration);
+        }
+    }
+
+    /**
+     * It allows the SC owner or a grant manager to revoke a user subscription
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner or a grant manager
+     * [x] It should check that the user has an active subscription for this plan
+     * [x] It should check that the subscription is not kept active with a locked stake
+     *
+     * Actions
+     * [x] It should expire the user subscription at the current timestamp
+     * [x] It should emit the `revokeSubscription` event
+     */
+    #[endpoint(revokeSubscription)]
+    fn revoke_subscription(&self, address: ManagedAddress<Self::Api>, plan_id: ManagedBuffer<Self::Api>) {
+        self.require_grant_manager();
+
+        let current_timestamp = self.blockchain().get_block_timestamp();
+
+        require!(self.user_plan_ids(&address).contains(&plan_id), "The user is not subscribed to this plan");
+
+        let user_plan = self.user_plans(&address, &plan_id).get();
+        require!(user_plan.expires_at > current_timestamp, "The user subscription is not active");
+        require!(
+            self.user_stakes(&address, &plan_id).is_empty() || self.user_stakes(&address, &plan_id).get().unbonding_ends_at != 0,
+            "The plan is active through a stake"
+        );
+
+        self.set_user_plan_expiry(&address, &plan_id, current_timestamp);
+
+        self.revoke_subscription_event(&address, &plan_id, &self.blockchain().get_caller());
+    }
+
+    #[inline]
+    fn grant_user_plan(&self, address: &ManagedAddress, plan_id: &ManagedBuffer, duration: u64) {
+        self.require_owner_plan(plan_id);
+        require!(duration > 0, "Invalid duration");
+        self.require_not_denied(address);
+
+        let plan = self.plans(plan_id).get();
+
+        self.activate_user_plan(address, &plan, duration, ActivationSource::Grant);
+        self.user_granted_time(address, plan_id).update(|granted_time| *granted_time = granted_time.saturating_add(duration));
+
+        self.grant_subscription_event(address, plan_id, &self.blockchain().get_caller(), duration);
+    }
+
+    #[inline]
+    fn require_grant_manager(&self) {
+        let caller = self.blockchain().get_caller();
+
+        require!(
+            caller == self.blockchain().get_owner_address() || self.grant_managers().contains(&caller),
+            "Only the owner or a grant manager can manage grants"
+        );
+    }
+}
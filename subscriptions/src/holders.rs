@@ -0,0 +1,158 @@
+elrond_wasm::imports!();
+
+use crate::MAX_BASIS_POINTS;
+use crate::structs::HolderBenefits;
+
+#[elrond_wasm::module]
+pub trait HoldersModule:
+    crate::storage::StorageModule +
+    crate::pricing::PricingModule
+{
+    /**
+     * It allows the smart contract owner to configure the benefits of a collection holders for a plan
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the subscription plan exists
+     * [x] It should check that the subscription plan is not a merchant plan
+     * [x] It should check that the collection is a valid token identifier
+     * [x] It should check that the discount is not greater than 100%
+     * [x] It should check that the plan is either gated or has a discount
+     *
+     * Actions
+     * [x] It should set the benefits to `plan_holder_benefits` storage
+     */
+    #[only_owner]
+    #[endpoint(setSubscriptionPlanHolderBenefits)]
+    fn set_subscription_plan_holder_benefits(
+        &self,
+        plan_id: ManagedBuffer<Self::Api>,
+        collection: TokenIdentifier<Self::Api>,
+        gated: bool,
+        discount_bps: u64
+    ) {
+        self.require_owner_plan(&plan_id);
+        require!(collection.is_valid_esdt_identifier(), "Invalid collection");
+        require!(discount_bps <= MAX_BASIS_POINTS, "Invalid discount");
+        require!(gated || discount_bps > 0, "The plan should be gated or have a holder discount");
+
+        self.plan_holder_benefits(&plan_id).set(HolderBenefits {
+            collection,
+            gated,
+            discount_bps,
+        });
+    }
+
+    /**
+     * It allows the smart contract owner to remove the holder benefits of a plan
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the subscription plan is not a merchant plan
+     * [x] It should check that the plan has holder benefits
+     *
+     * Actions
+     * [x] It should clear the `plan_holder_benefits` storage
+     */
+    #[only_owner]
+    #[endpoint(removeSubscriptionPlanHolderBenefits)]
+    fn remove_subscription_plan_holder_benefits(&self, plan_id: ManagedBuffer<Self::Api>) {
+        self.require_owner_plan(&plan_id);
+        require!(!self.plan_holder_benefits(&plan_id).is_empty(), "This plan has no holder benefits");
+
+        self.plan_holder_benefits(&plan_id).clear();
+    }
+
+    /**
+     * It applies the holder benefits of a plan to a price
+     *
+     * Validation
+     * [x] It should check that the payer is a holder for gated plans
+     *
+     * Actions
+     * [x] It should apply the holder discount when the payer is a holder
+     */
+    fn apply_holder_benefits(
+        &self,
+        plan_id: &ManagedBuffer,
+        price: BigUint<Self::Api>,
+        is_holder: bool
+    ) -> BigUint<Self::Api> {
+        if self.plan_holder_benefits(plan_id).is_empty() {
+            return price
+        }
+
+        self.require_holder_access(plan_id, is_holder);
+
+        let benefits = self.plan_holder_benefits(plan_id).get();
+
+        if !is_holder || benefits.discount_bps == 0 {
+            return price
+        }
+
+        price * (MAX_BASIS_POINTS - benefits.discount_bps) / MAX_BASIS_POINTS
+    }
+
+    /**
+     * It checks that the payer is a holder when the plan is gated
+     */
+    #[inline]
+    fn require_holder_access(&self, plan_id: &ManagedBuffer, is_holder: bool) {
+        require!(
+            is_holder || self.plan_holder_benefits(plan_id).is_empty() || !self.plan_holder_benefits(plan_id).get().gated,
+            "This plan is only available to collection holders"
+        );
+    }
+
+    /**
+     * It checks the holder proof and returns the payment
+     *
+     * The caller can prove the ownership in two ways:
+     * - by sending a token of the collection after the payment, in a multi-transfer; the token is returned to the caller
+     * - by providing the nonce of a token of the collection that it holds; this works only for same-shard callers
+     *
+     * Validation
+     * [x] It should check that the plan has holder benefits
+     * [x] It should check that the caller holds a token of the collection
+     *
+     * Actions
+     * [x] It should return the token of the collection sent as a proof
+     */
+    fn verify_holder_payment(
+        &self,
+        plan_id: &ManagedBuffer,
+        holder_nonce: OptionalValue<u64>
+    ) -> EgldOrEsdtTokenPayment<Self::Api> {
+        require!(!self.plan_holder_benefits(plan_id).is_empty(), "This plan has no holder benefits");
+
+        let collection = self.plan_holder_benefits(plan_id).get().collection;
+        let caller = self.blockchain().get_caller();
+
+        match holder_nonce {
+            OptionalValue::Some(nonce) => {
+                require!(
+                    self.blockchain().get_esdt_balance(&caller, &collection, nonce) > 0,
+                    "The caller does not hold a token of the collection"
+                );
+
+                self.call_value().egld_or_single_esdt()
+            },
+            OptionalValue::None => {
+                let transfers = self.call_value().all_esdt_transfers();
+                require!(transfers.len() == 2, "Expected a payment and a token of the collection");
+
+                let holder_token = transfers.get(1);
+                require!(holder_token.token_identifier == collection, "Invalid collection token");
+
+                self.send().direct_esdt(
+                    &caller,
+                    &holder_token.token_identifier,
+                    holder_token.token_nonce,
+                    &holder_token.amount
+                );
+
+                EgldOrEsdtTokenPayment::from(transfers.get(0))
+            },
+        }
+    }
+}
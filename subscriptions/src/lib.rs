@@ -5,19 +5,83 @@ elrond_wasm::derive_imports!();
 
 pub mod views;
 pub mod storage;
+pub mod events;
+
+pub mod merkle;
+
+pub mod activation;
+pub mod plans;
+pub mod merchants;
+pub mod grants;
+pub mod denylist;
+pub mod access;
+pub mod pricing;
+pub mod holders;
+pub mod overpayment;
+pub mod renewals;
+pub mod pauses;
+pub mod migrations;
+pub mod entitlements;
+pub mod metering;
+pub mod delegates;
+pub mod bundles;
+pub mod promotions;
+pub mod private_plans;
+pub mod loyalty;
+pub mod staking;
+pub mod swaps;
+pub mod escrow;
+pub mod accumulation;
+pub mod destinations;
+pub mod vouchers;
 
 pub mod structs;
 
-use crate::structs::{Plan, UserPlan, PlanStatus};
+use crate::structs::{PlanStatus, ActivationSource};
+use crate::merkle::Hash;
+use crate::activation::EXPIRY_BUCKET_DURATION;
+
+pub const MAX_BASIS_POINTS: u64 = 10_000;
 
 #[elrond_wasm::contract]
 pub trait Subscriptions: 
     views::ViewsModule +
-    storage::StorageModule
+    storage::StorageModule +
+    events::EventsModule +
+    activation::ActivationModule +
+    plans::PlansModule +
+    merchants::MerchantsModule +
+    merkle::MerkleModule +
+    grants::GrantsModule +
+    denylist::DenylistModule +
+    access::AccessModule +
+    pricing::PricingModule +
+    holders::HoldersModule +
+    overpayment::OverpaymentModule +
+    renewals::RenewalsModule +
+    pauses::PausesModule +
+    migrations::MigrationsModule +
+    entitlements::EntitlementsModule +
+    metering::MeteringModule +
+    delegates::DelegatesModule +
+    bundles::BundlesModule +
+    promotions::PromotionsModule +
+    private_plans::PrivatePlansModule +
+    loyalty::LoyaltyModule +
+    staking::StakingModule +
+    swaps::SwapsModule +
+    escrow::EscrowModule +
+    accumulation::AccumulationModule +
+    destinations::DestinationsModule +
+    vouchers::VouchersModule
 {
     #[init]
     fn init(&self) {
         self.enabled().set(false);
+
+        if self.expiry_prune_day().is_empty() {
+            self.expiry_prune_day().set(self.blockchain().get_block_timestamp() / EXPIRY_BUCKET_DURATION);
+        }
     }
 
     /**
@@ -32,11 +96,11 @@ pub trait Subscriptions:
     #[only_owner]
     #[endpoint(setStatus)]
     fn set_status(&self, status: bool) {
-        if status == true {
+        if status {
             require!(!self.payment_address().is_empty(), "Payment address is not configured");
         }
 
-        self.enabled().set(false);
+        self.enabled().set(status);
     }
 
     /**
@@ -59,6 +123,7 @@ pub trait Subscriptions:
      * 
      * Validation
      * [x] It should check that the caller is the SC owner
+     * [x] It should check that the plan id does not contain the merchant plan id separator [/]
      * [x] It should check that the subscription plan does not exist
      * 
      * Actions
@@ -67,20 +132,8 @@ pub trait Subscriptions:
     #[only_owner]
     #[endpoint(addSubscriptionPlan)]
     fn add_plan(&self, plan_id: ManagedBuffer<Self::Api>, validity: u64) {
-        require!(!self.plan_ids().contains(&plan_id), "This plan already exists");
-        self.plan_ids().insert(plan_id.clone());
-
-        let plan: Plan<Self::Api> = Plan {
-            id: plan_id.clone(),
-            validity,
-            status: PlanStatus::Enabled,
-            
-            /* not used at this time */
-            refund_period: 0,
-            allows_refund: false,
-        };
-
-        self.plans(&plan_id).set(plan);
+        self.require_valid_plan_id(&plan_id);
+        self.create_plan(&plan_id, validity);
     }
 
     /**
@@ -89,25 +142,34 @@ pub trait Subscriptions:
      * Validation
      * [x] It should check that the caller is the SC owner
      * [x] It should check that the subscription plan exists
+     * [x] It should check that the subscription plan is not a merchant plan
+     * [x] It should check that the subscription plan has no subscribers left
+     * [x] It should check that the subscription plan is not a bundle component
      * 
      * Actions
      * [x] It should remove the subscription plan ID from storage
      * [x] It should remove the subscription plan from storage
      * [x] It should remove the subscription plan token prices from storage
+     * [x] It should remove the subscription plan token nonce prices, settlements, promotions and payment addresses from storage
+     * [x] It should remove the subscription plan holder benefits from storage
+     * [x] It should remove the subscription plan overpayment policy from storage
+     * [x] It should remove the subscription plan renewal mode and maximum horizon from storage
+     * [x] It should remove the subscription plan pause settings from storage
+     * [x] It should remove the subscription plan delegate settings from storage
+     * [x] It should remove the subscription plan prerequisites and bundle components from storage
+     * [x] It should remove the subscription plan allowlist from storage
+     * [x] It should remove the subscription plan loyalty points price from storage
+     * [x] It should remove the subscription plan stake requirement from storage
+     * [x] It should remove the subscription plan entitlements from storage
+     * [x] It should remove the subscription plan metered add-ons from storage
+     * [x] It should remove the subscription plan from its merchant plans
      */
     #[only_owner]
     #[endpoint(removeSubscriptionPlan)]
     fn remove_plan(&self, plan_id: ManagedBuffer<Self::Api>) {
-        require!(self.plan_ids().contains(&plan_id), "This plan does not exist");
+        self.require_owner_plan(&plan_id);
 
-        self.plan_ids().remove(&plan_id);
-        self.plans(&plan_id).clear();
-
-        for token_identifier in self.plan_tokens(&plan_id).iter() {
-            self.plan_prices(&plan_id, &token_identifier).clear();
-        }
-
-        self.plan_tokens(&plan_id).clear();
+        self.delete_plan(&plan_id);
     }
 
     /**
@@ -116,6 +178,7 @@ pub trait Subscriptions:
      * Validation
      * [x] It should check that the caller is the SC owner
      * [x] It should check that the subscription plan exists
+     * [x] It should check that the subscription plan is not a merchant plan
      * 
      * Actions
      * [x] It should update the plan status to Enabled
@@ -123,12 +186,9 @@ pub trait Subscriptions:
     #[only_owner]
     #[endpoint(enableSubscriptionPlan)]
     fn enable_subscription_plan(&self, plan_id: ManagedBuffer<Self::Api>) {
-        require!(self.plan_ids().contains(&plan_id), "This plan does not exist");
-
-        let mut plan = self.plans(&plan_id).get();
-        plan.status = PlanStatus::Enabled;
+        self.require_owner_plan(&plan_id);
 
-        self.plans(&plan_id).set(plan);
+        self.set_plan_status(&plan_id, PlanStatus::Enabled);
     }
 
     /**
@@ -137,6 +197,7 @@ pub trait Subscriptions:
      * Validation
      * [x] It should check that the caller is the SC owner
      * [x] It should check that the subscription plan exists
+     * [x] It should check that the subscription plan is not a merchant plan
      * 
      * Actions
      * [x] It should update the plan status to Disabled
@@ -144,12 +205,112 @@ pub trait Subscriptions:
     #[only_owner]
     #[endpoint(disableSubscriptionPlan)]
     fn disable_subscription_plan(&self, plan_id: ManagedBuffer<Self::Api>) {
-        require!(self.plan_ids().contains(&plan_id), "This plan does not exist");
+        self.require_owner_plan(&plan_id);
 
-        let mut plan = self.plans(&plan_id).get();
-        plan.status = PlanStatus::Disabled;
+        self.set_plan_status(&plan_id, PlanStatus::Disabled);
+    }
+
+    /**
+     * It allows the smart contract owner to deprecate a subscription plan, only its subscribers can renew it
+     * 
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the subscription plan exists
+     * [x] It should check that the subscription plan is not a merchant plan
+     * 
+     * Actions
+     * [x] It should update the plan status to Deprecated
+     */
+    #[only_owner]
+    #[endpoint(deprecateSubscriptionPlan)]
+    fn deprecate_subscription_plan(&self, plan_id: ManagedBuffer<Self::Api>) {
+        self.require_owner_plan(&plan_id);
 
-        self.plans(&plan_id).set(plan);
+        self.set_plan_status(&plan_id, PlanStatus::Deprecated);
+    }
+
+    /**
+     * It allows the smart contract owner to archive a subscription plan, it can no longer be activated
+     * 
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the subscription plan exists
+     * [x] It should check that the subscription plan is not a merchant plan
+     * 
+     * Actions
+     * [x] It should update the plan status to Archived
+     */
+    #[only_owner]
+    #[endpoint(archiveSubscriptionPlan)]
+    fn archive_subscription_plan(&self, plan_id: ManagedBuffer<Self::Api>) {
+        self.require_owner_plan(&plan_id);
+
+        self.set_plan_status(&plan_id, PlanStatus::Archived);
+    }
+
+    /**
+     * It allows the smart contract owner to release the subscribers of an archived plan, before removing it
+     * 
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the subscription plan exists and is archived
+     * [x] It should check that the subscription plan and the compensation plan are not merchant plans
+     * [x] It should check that no active subscribers exist when there is no compensation plan
+     * 
+     * Actions
+     * [x] It should compensate the active subscribers with their remaining time on the compensation plan
+     * [x] It should remove the released subscribers entries
+     * 
+     * It returns the number of subscribers left to release
+     */
+    #[only_owner]
+    #[endpoint(releaseSubscriptionPlanSubscribers)]
+    fn release_subscription_plan_subscribers(
+        &self, 
+        plan_id: ManagedBuffer<Self::Api>, 
+        max_count: usize, 
+        compensation_plan_id: OptionalValue<ManagedBuffer<Self::Api>>
+    ) -> usize {
+        self.require_owner_plan(&plan_id);
+
+        let compensation_plan_id = compensation_plan_id.into_option();
+
+        if let Some(compensation_plan_id) = &compensation_plan_id {
+            self.require_owner_plan(compensation_plan_id);
+        }
+
+        self.release_plan_subscribers(&plan_id, compensation_plan_id, max_count)
+    }
+
+    /**
+     * It allows anyone to remove the user subscriptions that expired before the current day
+     * 
+     * The pruned users are no longer listed as plan subscribers, their subscription entries are kept for the renewals
+     * 
+     * Actions
+     * [x] It should call `prune_expired_user_plans` function with at most `max_steps` days, pauses and user subscriptions
+     * 
+     * It returns the number of user subscriptions pruned
+     */
+    #[endpoint(pruneExpiredSubscriptions)]
+    fn prune_expired_subscriptions(&self, max_steps: usize) -> usize {
+        self.prune_expired_user_plans(max_steps)
+    }
+
+    /**
+     * It allows anyone to remove the user entries of the subscription plans that no longer exist
+     * 
+     * Actions
+     * [x] It should remove the user entries of the removed plans
+     * [x] It should keep the user entries that expired before the last pruned day out of the subscriber indexes
+     * [x] It should add the user addresses to the `plan_subscribers` storage when missing
+     * [x] It should add the user subscriptions to their expiry buckets when missing
+     */
+    #[endpoint(cleanupUserPlans)]
+    fn cleanup_user_plans(&self, user_addresses: MultiValueEncoded<ManagedAddress<Self::Api>>) {
+        for user_address in user_addresses {
+            self.remove_orphaned_user_plans(&user_address);
+        }
     }
 
     /**
@@ -158,6 +319,7 @@ pub trait Subscriptions:
      * Validation
      * [x] It should check that the caller is the SC owner
      * [x] It should check that the subscription plan exists
+     * [x] It should check that the subscription plan is not a merchant plan
      * 
      * Actions
      * [x] It should add the token to `plan_tokens` storage if it does not exist
@@ -171,13 +333,9 @@ pub trait Subscriptions:
         token_identifier: &EgldOrEsdtTokenIdentifier<Self::Api>, 
         price: BigUint<Self::Api>
     ) {
-        require!(self.plan_ids().contains(&plan_id), "This plan does not exist");
-        
-        if !self.plan_tokens(&plan_id).contains(&token_identifier) {
-            self.plan_tokens(&plan_id).insert(token_identifier.clone());
-        }
+        self.require_owner_plan(&plan_id);
 
-        self.plan_prices(&plan_id, token_identifier).set(price);
+        self.set_plan_price(&plan_id, token_identifier, price);
     }
 
     /**
@@ -186,11 +344,13 @@ pub trait Subscriptions:
      * Validation
      * [x] It should check that the caller is the SC owner
      * [x] It should check that the subscription plan exists
+     * [x] It should check that the subscription plan is not a merchant plan
      * [x] It should check that the there token is enabled for this subscription plan
      * 
      * Actions
-     * [x] It should remove the token from `plan_tokens` storage
+     * [x] It should remove the token from `plan_tokens` and `plan_nonce_tokens` storage
      * [x] It should remove the token prices from `plan_prices` storage
+     * [x] It should remove the token nonces, nonce prices, settlement, promotions and payment address from storage
      */
     #[only_owner]
     #[endpoint(removeSubscriptionPlanToken)]
@@ -199,143 +359,189 @@ pub trait Subscriptions:
         plan_id: ManagedBuffer<Self::Api>, 
         token_identifier: &EgldOrEsdtTokenIdentifier<Self::Api>
     ) {
-        require!(self.plan_ids().contains(&plan_id), "This plan does not exist");
-        require!(self.plan_tokens(&plan_id).contains(token_identifier), "No price configured for this plan/token combination");
+        self.require_owner_plan(&plan_id);
 
-        self.plan_tokens(&plan_id).remove(token_identifier);
-        self.plan_prices(&plan_id, token_identifier).clear();
+        self.delete_plan_token(&plan_id, token_identifier);
     }
 
     /**
      * It allows anyone to pay with EGLD
+     * 
+     * The allowlist proof is only needed for the private plans with an allowlist Merkle root
      */
     #[payable("EGLD")]
     #[endpoint(payWithEgld)]
-    fn pay_with_egld(&self, plan_id: ManagedBuffer<Self::Api>) {
-        self.register_payment(plan_id);
+    fn pay_with_egld(&self, plan_id: ManagedBuffer<Self::Api>, proof: MultiValueEncoded<Hash<Self::Api>>) {
+        let payment = self.call_value().egld_or_single_esdt();
+        self.register_payment(plan_id, payment, false, proof.to_vec());
     }
 
     /**
      * It allows anyone to pay with ESDT
+     * 
+     * The allowlist proof is only needed for the private plans with an allowlist Merkle root
      */
     #[payable("*")]
     #[endpoint(payWithEsdt)]
-    fn pay_with_esdt(&self, plan_id: ManagedBuffer<Self::Api>) {
-        self.register_payment(plan_id);
+    fn pay_with_esdt(&self, plan_id: ManagedBuffer<Self::Api>, proof: MultiValueEncoded<Hash<Self::Api>>) {
+        let payment = self.call_value().egld_or_single_esdt();
+        self.register_payment(plan_id, payment, false, proof.to_vec());
     }
 
     /**
-     * It checks and registers the subscription payments
+     * It allows the holders of the plan collection to pay with EGLD or ESDT
+     * 
+     * The ownership is proven by sending a token of the collection along with the payment [returned in the same call]
+     * or by providing the nonce of a token held by the caller [same-shard callers only]
+     * 
+     * The private plans can only be paid this way by the addresses in the plan allowlist
+     */
+    #[payable("*")]
+    #[endpoint(payAsHolder)]
+    fn pay_as_holder(&self, plan_id: ManagedBuffer<Self::Api>, holder_nonce: OptionalValue<u64>) {
+        let payment = self.verify_holder_payment(&plan_id, holder_nonce);
+        self.register_payment(plan_id, payment, true, ManagedVec::new());
+    }
+
+    /**
+     * It allows anyone to pay with any ESDT that can be swapped into a plan token through the configured DEX pair
      * 
      * Validation
-     * [x] It should check that the plan exists
-     * [x] It should check that the contract is enabled
-     * [x] It should check that the subscription plan is enabled
-     * [x] It should check that the price is correct for this token
-     * [x] It should check that the payment token is enabled for this plan
+     * [x] It should check that the swap output token is enabled for this plan
+     * [x] It should check that the swap output covers the plan price
      * 
      * Actions
-     * [x] It should call `activate_user_plan` function
-     * [x] It should call `register_historic_data` function
-     * [x] It should send the payment to `payment_address`
+     * [x] It should swap the payment through the configured DEX pair
+     * [x] It should send the swap output surplus back to the caller
+     * [x] It should call `register_payment` function with the plan price
      */
-    #[inline]
-    fn register_payment(&self, plan_id: ManagedBuffer<Self::Api>) {
-        let payment = self.call_value().egld_or_single_esdt();
+    #[payable("*")]
+    #[endpoint(payWithSwap)]
+    fn pay_with_swap(
+        &self, 
+        plan_id: ManagedBuffer<Self::Api>, 
+        min_out: BigUint<Self::Api>, 
+        proof: MultiValueEncoded<Hash<Self::Api>>
+    ) {
+        let payment = self.call_value().single_esdt();
+        let caller = self.blockchain().get_caller();
 
-        require!(!self.enabled().is_empty(), "Contract is not enabled");
-        require!(self.plan_ids().contains(&plan_id), "This plan does not exist");
-        require!(self.plan_tokens(&plan_id).contains(&payment.token_identifier), "This token is not enabled");
-        require!(&self.plan_prices(&plan_id, &payment.token_identifier).get() == &payment.amount, "Invalid payment amount");
+        require!(!self.swap_routes(&payment.token_identifier).is_empty(), "No swap route configured for this token");
 
-        let plan = self.plans(&plan_id).get();
-        let caller = self.blockchain().get_caller();
+        let token_out = EgldOrEsdtTokenIdentifier::esdt(self.swap_routes(&payment.token_identifier).get().token_out);
+        require!(self.plan_tokens(&plan_id).contains(&token_out), "The swap output token is not enabled for this plan");
 
-        require!(plan.status == PlanStatus::Enabled, "Subscription plan is disabled");
+        let mut output = EgldOrEsdtTokenPayment::from(self.swap_payment(payment, min_out));
+        let price = self.get_effective_price(&plan_id, &output, false);
 
-        self.activate_user_plan(&caller, plan);
-        self.register_historic_data(&caller, &payment);
+        require!(output.amount >= price, "The swap output is lower than the plan price");
+
+        if output.amount > price {
+            self.send().direct(&caller, &output.token_identifier, output.token_nonce, &(&output.amount - &price));
+        }
 
-        self.send().direct(
-            &self.payment_address().get(), 
-            &payment.token_identifier, 
-            payment.token_nonce, 
-            &payment.amount
-        );
+        output.amount = price;
+        self.register_payment(plan_id, output, false, proof.to_vec());
     }
 
     /**
-     * It activates the user subscription
+     * It allows anyone to pay using the credit accumulated from previous overpayments
+     * 
+     * Validation
+     * [x] It should check that the plan has a price for this token
+     * [x] It should check that the price to pay is not zero
+     * [x] It should check that the credit covers the plan price
      * 
      * Actions
-     * [x] It should add the user address to the `users` storage
-     * [x] It should increase the user's subscription expiration date
-     * [x] It should update the user's subscription `last_activated` date
+     * [x] It should deduct the plan price from the `user_credits` storage
+     * [x] It should call `register_payment` function with the plan price
      */
-    #[inline]
-    fn activate_user_plan(&self, user_address: &ManagedAddress, plan: Plan<Self::Api>) {
-        let current_timestamp = self.blockchain().get_block_timestamp();
+    #[endpoint(payWithCredit)]
+    fn pay_with_credit(
+        &self, 
+        plan_id: ManagedBuffer<Self::Api>, 
+        token_identifier: EgldOrEsdtTokenIdentifier<Self::Api>, 
+        proof: MultiValueEncoded<Hash<Self::Api>>
+    ) {
+        let caller = self.blockchain().get_caller();
 
-        if !self.users().contains(&user_address) {
-            self.users().insert(user_address.clone());
-        }
+        require!(!self.plan_prices(&plan_id, &token_identifier).is_empty(), "No price configured for this plan/token combination");
 
-        let mut user_plan: UserPlan<Self::Api>;
+        let mut payment = EgldOrEsdtTokenPayment::new(token_identifier, 0, BigUint::zero());
 
-        if !self.user_plan_ids(&user_address).contains(&plan.id) {
-            self.user_plan_ids(&user_address).insert(plan.id.clone());
+        payment.amount = self.get_effective_price(&plan_id, &payment, false);
+        require!(payment.amount > 0, "The plan cannot be paid with credit at a zero price");
 
-            user_plan = UserPlan {
-                plan_id: plan.id.clone(),
-                expires_at: current_timestamp + plan.validity,
-                first_subscribed: current_timestamp,
-                last_subscribed: current_timestamp,
-            };
-        } else {
-            user_plan = self.user_plans(&user_address, &plan.id).get();
-            if user_plan.expires_at > current_timestamp {
-                user_plan.expires_at += plan.validity;
-            } else {
-                user_plan.expires_at = current_timestamp + plan.validity;
-            }
-
-            user_plan.last_subscribed = current_timestamp;
-        }
+        let credit = self.user_credits(&caller, &payment.token_identifier).get();
+        require!(credit >= payment.amount, "Insufficient credit");
 
-        self.user_plans(&user_address, &plan.id).set(user_plan);
+        self.user_credits(&caller, &payment.token_identifier).set(credit - &payment.amount);
+        self.register_payment(plan_id, payment, false, proof.to_vec());
     }
 
     /**
-     * It computes and saves historical data
+     * It checks and registers the subscription payments
+     * 
+     * Validation
+     * [x] It should call `require_can_subscribe` function for the caller
+     * [x] It should check that the price is correct for this token and nonce, applying the running promotion
+     * [x] It should apply the holder discount
+     * [x] It should handle the payment surplus based on the plan overpayment policy
+     * [x] It should check that the payment token and nonce are enabled for this plan
      * 
      * Actions
-     * [x] It should add the payment token to the `payment_token_ids` storage
-     * [x] It should save the payment amount to the `payment_token_amounts` storage
-     * [x] It should add the payment token to the `user_payment_tokens` storage
-     * [x] It should save the payment amount to the `user_payment_amounts` storage
+     * [x] It should add the loyalty points earned with the payment to the caller balance
+     * [x] It should call `activate_user_plan` function
+     * [x] It should activate the bundle component plans
+     * [x] It should call `register_historic_data` function
+     * [x] It should send the merchant plan payments to the merchant, minus the platform fee
+     * [x] It should hold the payment in escrow over the purchased period, when the escrow is enabled
+     * [x] It should accumulate the payment in the contract, when the accumulation is enabled
+     * [x] It should send the payment to its payment destination or burn it, based on the plan token settlement
      */
     #[inline]
-    fn register_historic_data(
+    fn register_payment(
         &self, 
-        caller: &ManagedAddress, 
-        payment: &EgldOrEsdtTokenPayment<Self::Api>
+        plan_id: ManagedBuffer<Self::Api>, 
+        payment: EgldOrEsdtTokenPayment<Self::Api>, 
+        is_holder: bool, 
+        proof: ManagedVec<Self::Api, Hash<Self::Api>>
     ) {
-        let mut existing_user_payment = BigUint::zero();
-        let mut existing_token_payment = BigUint::zero();
+        let caller = self.blockchain().get_caller();
+        let plan = self.require_can_subscribe(&plan_id, &caller, &proof, is_holder);
 
-        if !self.payment_token_ids().contains(&payment.token_identifier) {
-            existing_token_payment = self.payment_token_amounts(&payment.token_identifier).get();
-        } else {
-            self.payment_token_ids().insert(payment.token_identifier.clone());
-        }
+        let price = self.get_effective_price(&plan_id, &payment, is_holder);
+        let payment = self.take_plan_price(&plan_id, &caller, payment, price);
+
+        self.accrue_loyalty_points(&caller, &plan, &payment);
+
+        let period_start = self.activate_user_plan(&caller, &plan, plan.validity, ActivationSource::Payment);
+        self.activate_bundle_components(&caller, &plan_id);
+        self.register_historic_data(&caller, &payment);
 
-        if self.user_payment_tokens(&caller).contains(&payment.token_identifier) {
-            existing_user_payment = self.user_payment_amounts(&caller, &payment.token_identifier).get();
+        if !self.plan_merchant(&plan_id).is_empty() {
+            self.settle_merchant_payment(self.plan_merchant(&plan_id).get(), &payment);
+        } else if self.is_escrowed(&plan_id, &payment) {
+            self.escrow_payment(&payment, period_start, period_start + plan.validity);
+        } else if self.is_accumulated(&plan_id, &payment) {
+            self.accumulate_payment(&payment);
         } else {
-            self.user_payment_tokens(&caller).insert(payment.token_identifier.clone());
+            self.settle_payment(&plan_id, &payment);
         }
+    }
+
+    /**
+     * It returns the price that the payer has to pay for a plan with the payment token and nonce
+     */
+    #[inline]
+    fn get_effective_price(
+        &self, 
+        plan_id: &ManagedBuffer<Self::Api>, 
+        payment: &EgldOrEsdtTokenPayment<Self::Api>, 
+        is_holder: bool
+    ) -> BigUint<Self::Api> {
+        let price = self.get_plan_payment_price(plan_id, payment);
 
-        self.payment_token_amounts(&payment.token_identifier).set(existing_token_payment + &payment.amount);
-        self.user_payment_amounts(&caller, &payment.token_identifier).set(existing_user_payment + &payment.amount);
+        self.apply_holder_benefits(plan_id, price, is_holder)
     }
 }
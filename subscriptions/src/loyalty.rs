@@ -0,0 +1,173 @@
+elrond_wasm::imports!();
+
+use crate::MAX_BASIS_POINTS;
+use crate::activation::EXPIRY_BUCKET_DURATION;
+use crate::merkle::Hash;
+use crate::structs::{ActivationSource, Plan, RenewalBonus, RenewalMode};
+
+#[elrond_wasm::module]
+pub trait LoyaltyModule:
+    crate::storage::StorageModule +
+    crate::events::EventsModule +
+    crate::merkle::MerkleModule +
+    crate::denylist::DenylistModule +
+    crate::pricing::PricingModule +
+    crate::activation::ActivationModule +
+    crate::plans::PlansModule +
+    crate::holders::HoldersModule +
+    crate::bundles::BundlesModule +
+    crate::private_plans::PrivatePlansModule +
+    crate::access::AccessModule
+{
+    /**
+     * It allows the smart contract owner to set the token amount that earns one loyalty point [0 = no points]
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     *
+     * Actions
+     * [x] It should set the rate to `loyalty_point_rates` storage
+     */
+    #[only_owner]
+    #[endpoint(setLoyaltyPointRate)]
+    fn set_loyalty_point_rate(&self, token_identifier: &EgldOrEsdtTokenIdentifier<Self::Api>, amount_per_point: BigUint<Self::Api>) {
+        self.loyalty_point_rates(token_identifier).set(amount_per_point);
+    }
+
+    /**
+     * It allows the smart contract owner to set the loyalty points bonus for the renewing users
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the bonus per period is not greater than the maximum bonus
+     *
+     * Actions
+     * [x] It should set the bonus to `loyalty_renewal_bonus` storage
+     */
+    #[only_owner]
+    #[endpoint(setLoyaltyRenewalBonus)]
+    fn set_loyalty_renewal_bonus(&self, bps_per_period: u64, max_bps: u64) {
+        require!(bps_per_period <= max_bps, "Invalid bonus");
+
+        self.loyalty_renewal_bonus().set(RenewalBonus {
+            bps_per_period,
+            max_bps,
+        });
+    }
+
+    /**
+     * It allows the smart contract owner to set the loyalty points needed to redeem one day of a plan [0 = not redeemable]
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the subscription plan exists
+     * [x] It should check that the subscription plan is not a merchant plan
+     * [x] It should check that the subscription plan is not a lifetime plan
+     *
+     * Actions
+     * [x] It should set the price to `plan_points_per_day` storage
+     */
+    #[only_owner]
+    #[endpoint(setSubscriptionPlanPointsPerDay)]
+    fn set_subscription_plan_points_per_day(&self, plan_id: ManagedBuffer<Self::Api>, points: BigUint<Self::Api>) {
+        self.require_owner_plan(&plan_id);
+        require!(self.plan_renewal_mode(&plan_id).get() != RenewalMode::Lifetime, "Lifetime plans cannot be redeemed with points");
+
+        self.plan_points_per_day(&plan_id).set(points);
+    }
+
+    /**
+     * It allows anyone to redeem loyalty points for days of a plan
+     *
+     * Only the points for whole days are used, the rest stays in the balance
+     * The redeemed days always extend the subscription, whatever the plan renewal mode
+     *
+     * The allowlist proof is only needed for the private plans with an allowlist Merkle root,
+     * the plans gated to collection holders cannot be redeemed with points
+     *
+     * Validation
+     * [x] It should call `require_can_subscribe` function for the caller
+     * [x] It should check that the plan can be redeemed with points and is not a lifetime plan
+     * [x] It should check that the points cover at least one day
+     * [x] It should check that the caller has enough points
+     *
+     * Actions
+     * [x] It should remove the used points from the `loyalty_points` storage
+     * [x] It should call `activate_user_plan` function with the `Loyalty` source, within the plan maximum horizon
+     * [x] It should emit the `redeemPoints` event
+     */
+    #[endpoint(redeemPoints)]
+    fn redeem_points(&self, plan_id: ManagedBuffer<Self::Api>, points: BigUint<Self::Api>, proof: MultiValueEncoded<Hash<Self::Api>>) {
+        let caller = self.blockchain().get_caller();
+        let plan = self.require_can_subscribe(&plan_id, &caller, &proof.to_vec(), false);
+
+        let points_per_day = self.plan_points_per_day(&plan_id).get();
+        require!(points_per_day > 0, "This plan cannot be redeemed with points");
+        require!(self.plan_renewal_mode(&plan_id).get() != RenewalMode::Lifetime, "Lifetime plans cannot be redeemed with points");
+
+        let days = (&points / &points_per_day).to_u64().unwrap_or_default();
+        require!(days > 0, "Invalid points");
+
+        let used_points = points_per_day * days;
+        let balance = self.loyalty_points(&caller).get();
+        require!(balance >= used_points, "Insufficient points");
+
+        self.loyalty_points(&caller).set(balance - &used_points);
+
+        let duration = days * EXPIRY_BUCKET_DURATION;
+        self.activate_user_plan(&caller, &plan, duration, ActivationSource::Loyalty);
+
+        self.redeem_points_event(&caller, &plan_id, &used_points, duration);
+    }
+
+    /**
+     * It adds the loyalty points earned with a payment to the user balance
+     *
+     * The renewing users with an active subscription earn a bonus for each plan period since they first subscribed
+     *
+     * Actions
+     * [x] It should add the points to the `loyalty_points` storage
+     * [x] It should emit the `earnPoints` event
+     */
+    fn accrue_loyalty_points(&self, user_address: &ManagedAddress, plan: &Plan<Self::Api>, payment: &EgldOrEsdtTokenPayment<Self::Api>) {
+        let amount_per_point = self.loyalty_point_rates(&payment.token_identifier).get();
+        if amount_per_point == 0 {
+            return;
+        }
+
+        let mut points = &payment.amount / &amount_per_point;
+        let bonus_bps = self.get_renewal_bonus_bps(user_address, plan);
+
+        if bonus_bps > 0 {
+            points = &points * (MAX_BASIS_POINTS + bonus_bps) / MAX_BASIS_POINTS;
+        }
+
+        if points == 0 {
+            return;
+        }
+
+        self.loyalty_points(user_address).update(|balance| *balance += &points);
+        self.earn_points_event(user_address, &plan.id, &points);
+    }
+
+    /**
+     * It returns the loyalty points bonus of a user renewing a plan
+     */
+    fn get_renewal_bonus_bps(&self, user_address: &ManagedAddress, plan: &Plan<Self::Api>) -> u64 {
+        let current_timestamp = self.blockchain().get_block_timestamp();
+
+        if self.loyalty_renewal_bonus().is_empty() || plan.validity == 0 || !self.user_plan_ids(user_address).contains(&plan.id) {
+            return 0
+        }
+
+        let user_plan = self.user_plans(user_address, &plan.id).get();
+        if !self.is_user_plan_active(&user_plan, current_timestamp) {
+            return 0
+        }
+
+        let bonus = self.loyalty_renewal_bonus().get();
+        let periods = (current_timestamp - user_plan.first_subscribed) / plan.validity;
+
+        (periods.saturating_mul(bonus.bps_per_period)).min(bonus.max_bps)
+    }
+}
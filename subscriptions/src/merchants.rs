@@ -0,0 +1,356 @@
+elrond_wasm::imports!();
+
+use crate::MAX_BASIS_POINTS;
+use crate::structs::{Merchant, PlanStatus};
+
+pub const MERCHANT_PLAN_ID_SEPARATOR: &[u8] = b"/";
+
+#[elrond_wasm::module]
+pub trait MerchantsModule:
+    crate::storage::StorageModule +
+    crate::pricing::PricingModule +
+    crate::activation::ActivationModule +
+    crate::plans::PlansModule
+{
+    /**
+     * It allows the smart contract owner to set the fee taken from the merchant payments
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the fee is not greater than 100%
+     *
+     * Actions
+     * [x] It should set the fee to `platform_fee_bps` storage
+     */
+    #[only_owner]
+    #[endpoint(setPlatformFee)]
+    fn set_platform_fee(&self, fee_bps: u64) {
+        require!(fee_bps <= MAX_BASIS_POINTS, "Invalid fee");
+
+        self.platform_fee_bps().set(fee_bps);
+    }
+
+    /**
+     * It allows the smart contract owner to enable or disable a merchant
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the merchant exists
+     *
+     * Actions
+     * [x] It should update the merchant status
+     */
+    #[only_owner]
+    #[endpoint(setMerchantStatus)]
+    fn set_merchant_status(&self, merchant_id: u64, enabled: bool) {
+        require!(!self.merchants(merchant_id).is_empty(), "This merchant does not exist");
+
+        self.merchants(merchant_id).update(|merchant| merchant.enabled = enabled);
+    }
+
+    /**
+     * It allows anyone to register as a merchant
+     *
+     * Validation
+     * [x] It should check that the caller is not already a merchant
+     *
+     * Actions
+     * [x] It should assign the next merchant id to the caller
+     * [x] It should add the merchant to `merchants` storage
+     */
+    #[endpoint(registerMerchant)]
+    fn register_merchant(&self, payment_address: ManagedAddress<Self::Api>) -> u64 {
+        let caller = self.blockchain().get_caller();
+
+        require!(self.merchant_ids(&caller).is_empty(), "This address is already a merchant");
+
+        let merchant_id = self.last_merchant_id().update(|last_id| {
+            *last_id += 1;
+            *last_id
+        });
+
+        self.merchant_ids(&caller).set(merchant_id);
+        self.merchants(merchant_id).set(Merchant {
+            id: merchant_id,
+            address: caller,
+            payment_address,
+            enabled: true,
+        });
+
+        merchant_id
+    }
+
+    /**
+     * It allows a merchant to change the address where its payments are sent to
+     *
+     * Validation
+     * [x] It should check that the caller is a merchant
+     *
+     * Actions
+     * [x] It should update the merchant payment address
+     */
+    #[endpoint(setMerchantPaymentAddress)]
+    fn set_merchant_payment_address(&self, payment_address: ManagedAddress<Self::Api>) {
+        let merchant_id = self.require_caller_merchant();
+
+        self.merchants(merchant_id).update(|merchant| merchant.payment_address = payment_address);
+    }
+
+    /**
+     * It allows a merchant to add a subscription plan, namespaced under its merchant id [merchant_id/plan_id]
+     *
+     * Validation
+     * [x] It should check that the caller is a merchant
+     * [x] It should check that the plan id does not contain the merchant plan id separator [/]
+     * [x] It should check that the subscription plan does not exist
+     *
+     * Actions
+     * [x] It should add the subscription plan to storage
+     * [x] It should add the subscription plan to the merchant plans
+     */
+    #[endpoint(addMerchantSubscriptionPlan)]
+    fn add_merchant_plan(&self, plan_id: ManagedBuffer<Self::Api>, validity: u64) -> ManagedBuffer<Self::Api> {
+        let merchant_id = self.require_caller_merchant();
+        self.require_valid_plan_id(&plan_id);
+
+        let merchant_plan_id = self.get_merchant_plan_id(merchant_id, &plan_id);
+
+        self.create_plan(&merchant_plan_id, validity);
+        self.merchant_plan_ids(merchant_id).insert(merchant_plan_id.clone());
+        self.plan_merchant(&merchant_plan_id).set(merchant_id);
+
+        merchant_plan_id
+    }
+
+    /**
+     * It allows a merchant to remove one of its subscription plans
+     *
+     * Validation
+     * [x] It should check that the caller is a merchant
+     * [x] It should check that the subscription plan exists
+     * [x] It should check that the subscription plan has no subscribers left
+     *
+     * Actions
+     * [x] It should remove the subscription plan and all its settings
+     */
+    #[endpoint(removeMerchantSubscriptionPlan)]
+    fn remove_merchant_plan(&self, plan_id: ManagedBuffer<Self::Api>) {
+        let merchant_plan_id = self.require_caller_merchant_plan(&plan_id);
+
+        self.delete_plan(&merchant_plan_id);
+    }
+
+    /**
+     * It allows a merchant to enable one of its subscription plans
+     *
+     * Validation
+     * [x] It should check that the caller is a merchant
+     * [x] It should check that the subscription plan exists
+     *
+     * Actions
+     * [x] It should update the plan status to Enabled
+     */
+    #[endpoint(enableMerchantSubscriptionPlan)]
+    fn enable_merchant_plan(&self, plan_id: ManagedBuffer<Self::Api>) {
+        let merchant_plan_id = self.require_caller_merchant_plan(&plan_id);
+
+        self.set_plan_status(&merchant_plan_id, PlanStatus::Enabled);
+    }
+
+    /**
+     * It allows a merchant to disable one of its subscription plans
+     *
+     * Validation
+     * [x] It should check that the caller is a merchant
+     * [x] It should check that the subscription plan exists
+     *
+     * Actions
+     * [x] It should update the plan status to Disabled
+     */
+    #[endpoint(disableMerchantSubscriptionPlan)]
+    fn disable_merchant_plan(&self, plan_id: ManagedBuffer<Self::Api>) {
+        let merchant_plan_id = self.require_caller_merchant_plan(&plan_id);
+
+        self.set_plan_status(&merchant_plan_id, PlanStatus::Disabled);
+    }
+
+    /**
+     * It allows a merchant to deprecate one of its subscription plans, only its subscribers can renew it
+     *
+     * Validation
+     * [x] It should check that the caller is a merchant
+     * [x] It should check that the subscription plan exists
+     *
+     * Actions
+     * [x] It should update the plan status to Deprecated
+     */
+    #[endpoint(deprecateMerchantSubscriptionPlan)]
+    fn deprecate_merchant_plan(&self, plan_id: ManagedBuffer<Self::Api>) {
+        let merchant_plan_id = self.require_caller_merchant_plan(&plan_id);
+
+        self.set_plan_status(&merchant_plan_id, PlanStatus::Deprecated);
+    }
+
+    /**
+     * It allows a merchant to archive one of its subscription plans, it can no longer be activated
+     *
+     * Validation
+     * [x] It should check that the caller is a merchant
+     * [x] It should check that the subscription plan exists
+     *
+     * Actions
+     * [x] It should update the plan status to Archived
+     */
+    #[endpoint(archiveMerchantSubscriptionPlan)]
+    fn archive_merchant_plan(&self, plan_id: ManagedBuffer<Self::Api>) {
+        let merchant_plan_id = self.require_caller_merchant_plan(&plan_id);
+
+        self.set_plan_status(&merchant_plan_id, PlanStatus::Archived);
+    }
+
+    /**
+     * It allows a merchant to release the subscribers of one of its archived plans, before removing it
+     *
+     * Validation
+     * [x] It should check that the caller is a merchant
+     * [x] It should check that the subscription plan and the compensation plan belong to the merchant
+     * [x] It should check that the subscription plan is archived
+     * [x] It should check that no active subscribers exist when there is no compensation plan
+     *
+     * Actions
+     * [x] It should compensate the active subscribers with their remaining time on the compensation plan
+     * [x] It should remove the released subscribers entries
+     *
+     * It returns the number of subscribers left to release
+     */
+    #[endpoint(releaseMerchantSubscriptionPlanSubscribers)]
+    fn release_merchant_plan_subscribers(
+        &self,
+        plan_id: ManagedBuffer<Self::Api>,
+        max_count: usize,
+        compensation_plan_id: OptionalValue<ManagedBuffer<Self::Api>>
+    ) -> usize {
+        let merchant_plan_id = self.require_caller_merchant_plan(&plan_id);
+        let compensation_plan_id = compensation_plan_id
+            .into_option()
+            .map(|compensation_plan_id| self.require_caller_merchant_plan(&compensation_plan_id));
+
+        self.release_plan_subscribers(&merchant_plan_id, compensation_plan_id, max_count)
+    }
+
+    /**
+     * It allows a merchant to set the subscription payment amount for a token
+     *
+     * Validation
+     * [x] It should check that the caller is a merchant
+     * [x] It should check that the subscription plan exists
+     *
+     * Actions
+     * [x] It should add the token to `plan_tokens` storage if it does not exist
+     * [x] It should set the price on `plan_prices` storage
+     */
+    #[endpoint(setMerchantSubscriptionPlanPrice)]
+    fn set_merchant_plan_price(
+        &self,
+        plan_id: ManagedBuffer<Self::Api>,
+        token_identifier: &EgldOrEsdtTokenIdentifier<Self::Api>,
+        price: BigUint<Self::Api>
+    ) {
+        let merchant_plan_id = self.require_caller_merchant_plan(&plan_id);
+
+        self.set_plan_price(&merchant_plan_id, token_identifier, price);
+    }
+
+    /**
+     * It allows a merchant to remove a token from its plan payments
+     *
+     * Validation
+     * [x] It should check that the caller is a merchant
+     * [x] It should check that the subscription plan exists
+     * [x] It should check that the there token is enabled for this subscription plan
+     *
+     * Actions
+     * [x] It should remove the token from `plan_tokens` and `plan_nonce_tokens` storage
+     * [x] It should remove the token prices from `plan_prices` storage
+     */
+    #[endpoint(removeMerchantSubscriptionPlanToken)]
+    fn remove_merchant_plan_token(
+        &self,
+        plan_id: ManagedBuffer<Self::Api>,
+        token_identifier: &EgldOrEsdtTokenIdentifier<Self::Api>
+    ) {
+        let merchant_plan_id = self.require_caller_merchant_plan(&plan_id);
+
+        self.delete_plan_token(&merchant_plan_id, token_identifier);
+    }
+
+    /**
+     * It sends a merchant plan payment to the merchant, minus the platform fee sent to the token payment address
+     *
+     * Validation
+     * [x] It should check that the merchant is enabled
+     */
+    fn settle_merchant_payment(&self, merchant_id: u64, payment: &EgldOrEsdtTokenPayment<Self::Api>) {
+        let merchant = self.merchants(merchant_id).get();
+        require!(merchant.enabled, "This merchant is disabled");
+
+        let fee = &payment.amount * self.platform_fee_bps().get() / MAX_BASIS_POINTS;
+        let merchant_amount = &payment.amount - &fee;
+
+        if fee > 0 {
+            self.send().direct(&self.get_token_payment_address(&payment.token_identifier), &payment.token_identifier, payment.token_nonce, &fee);
+            self.platform_fees(&payment.token_identifier).update(|fees| *fees += &fee);
+        }
+
+        if merchant_amount > 0 {
+            self.send().direct(&merchant.payment_address, &payment.token_identifier, payment.token_nonce, &merchant_amount);
+        }
+    }
+
+    /**
+     * It returns the global id of a merchant plan [merchant_id/plan_id]
+     */
+    fn get_merchant_plan_id(&self, merchant_id: u64, plan_id: &ManagedBuffer) -> ManagedBuffer<Self::Api> {
+        let mut digits = [0u8; 20];
+        let mut position = digits.len();
+        let mut value = merchant_id;
+
+        loop {
+            position -= 1;
+            digits[position] = b'0' + (value % 10) as u8;
+            value /= 10;
+
+            if value == 0 {
+                break;
+            }
+        }
+
+        let mut merchant_plan_id = ManagedBuffer::new_from_bytes(&digits[position..]);
+        merchant_plan_id.append_bytes(MERCHANT_PLAN_ID_SEPARATOR);
+        merchant_plan_id.append(plan_id);
+
+        merchant_plan_id
+    }
+
+    /**
+     * It checks that the caller is a merchant and returns its merchant id
+     */
+    fn require_caller_merchant(&self) -> u64 {
+        let caller = self.blockchain().get_caller();
+        require!(!self.merchant_ids(&caller).is_empty(), "The caller is not a merchant");
+
+        self.merchant_ids(&caller).get()
+    }
+
+    /**
+     * It checks that the caller is a merchant and returns the global id of its plan
+     */
+    fn require_caller_merchant_plan(&self, plan_id: &ManagedBuffer) -> ManagedBuffer<Self::Api> {
+        let merchant_id = self.require_caller_merchant();
+        let merchant_plan_id = self.get_merchant_plan_id(merchant_id, plan_id);
+
+        require!(self.merchant_plan_ids(merchant_id).contains(&merchant_plan_id), "This plan does not exist");
+
+        merchant_plan_id
+    }
+}
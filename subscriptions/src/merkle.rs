@@ -0,0 +1,57 @@
+elrond_wasm::imports!();
+
+use crate::structs::HashAlgorithm;
+
+pub const HASH_LENGTH: usize = 32;
+
+pub type Hash<M> = ManagedByteArray<M, HASH_LENGTH>;
+
+#[elrond_wasm::module]
+pub trait MerkleModule {
+    /**
+     * It returns the Merkle tree leaf for an address, hashed with the given algorithm
+     */
+    #[inline]
+    fn address_leaf_with(&self, address: &ManagedAddress, algorithm: HashAlgorithm) -> Hash<Self::Api> {
+        self.hash_with(address.as_managed_buffer(), algorithm)
+    }
+
+    /**
+     * It checks that a leaf belongs to the tree with the given root, hashed with the given algorithm
+     *
+     * The pairs are hashed in sorted order, so the proof does not need to specify the node positions
+     */
+    fn verify_merkle_proof_with(
+        &self,
+        leaf: Hash<Self::Api>,
+        proof: &ManagedVec<Self::Api, Hash<Self::Api>>,
+        root: &Hash<Self::Api>,
+        algorithm: HashAlgorithm
+    ) -> bool {
+        let mut computed_hash = leaf;
+
+        for node in proof.iter() {
+            let mut pair = ManagedBuffer::new();
+
+            if computed_hash.to_byte_array() <= node.to_byte_array() {
+                pair.append(computed_hash.as_managed_buffer());
+                pair.append(node.as_managed_buffer());
+            } else {
+                pair.append(node.as_managed_buffer());
+                pair.append(computed_hash.as_managed_buffer());
+            }
+
+            computed_hash = self.hash_with(&pair, algorithm);
+        }
+
+        &computed_hash == root
+    }
+
+    #[inline]
+    fn hash_with(&self, data: &ManagedBuffer, algorithm: HashAlgorithm) -> Hash<Self::Api> {
+        match algorithm {
+            HashAlgorithm::Keccak256 => self.crypto().keccak256(data),
+            HashAlgorithm::Sha256 => self.crypto().sha256(data),
+        }
+    }
+}
@@ -0,0 +1,348 @@
+elrond_wasm::imports!();
+
+use crate::structs::{MeteredAddon, PriceTier, UsageKey, UsageRecord};
+
+#[elrond_wasm::module]
+pub trait MeteringModule:
+    crate::storage::StorageModule +
+    crate::events::EventsModule +
+    crate::pricing::PricingModule +
+    crate::activation::ActivationModule +
+    crate::plans::PlansModule +
+    crate::merchants::MerchantsModule
+{
+    /**
+     * It allows the smart contract owner to add or update a metered add-on of a plan
+     *
+     * The tiers are given as [up to units, unit price] pairs, the last tier has no upper limit [up to = 0]
+     * Each tier price applies to the units used within the tier
+     * The usage already reported keeps its charge until more units are reported for the same billing period
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the subscription plan exists
+     * [x] It should check that the subscription plan is not a merchant plan
+     * [x] It should check that the billing period is not zero
+     * [x] It should check that the tier limits are increasing and only the last tier is unlimited
+     *
+     * Actions
+     * [x] It should add the add-on id to `plan_addon_ids` storage if it does not exist
+     * [x] It should set the add-on to `plan_addons` storage
+     */
+    #[only_owner]
+    #[endpoint(setSubscriptionPlanAddon)]
+    fn set_subscription_plan_addon(
+        &self,
+        plan_id: ManagedBuffer<Self::Api>,
+        addon_id: ManagedBuffer<Self::Api>,
+        token_identifier: EgldOrEsdtTokenIdentifier<Self::Api>,
+        period_duration: u64,
+        tiers: MultiValueEncoded<MultiValue2<u64, BigUint<Self::Api>>>
+    ) {
+        self.require_owner_plan(&plan_id);
+        require!(period_duration > 0, "Invalid billing period");
+        require!(!tiers.is_empty(), "Invalid price tiers");
+
+        let tiers_count = tiers.len();
+        let mut addon_tiers = ManagedVec::new();
+        let mut last_up_to = 0;
+
+        for (index, tier) in tiers.into_iter().enumerate() {
+            let (up_to, unit_price) = tier.into_tuple();
+
+            if index + 1 == tiers_count {
+                require!(up_to == 0, "The last price tier should be unlimited");
+            } else {
+                require!(up_to > last_up_to, "Invalid price tiers");
+                last_up_to = up_to;
+            }
+
+            addon_tiers.push(PriceTier {
+                up_to,
+                unit_price,
+            });
+        }
+
+        self.plan_addon_ids(&plan_id).insert(addon_id.clone());
+        self.plan_addons(&plan_id, &addon_id).set(MeteredAddon {
+            token_identifier,
+            period_duration,
+            tiers: addon_tiers,
+        });
+    }
+
+    /**
+     * It allows the smart contract owner to remove a metered add-on from a plan
+     *
+     * The usage reported for a removed add-on is dropped when settled
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the subscription plan is not a merchant plan
+     * [x] It should check that the plan has the add-on
+     *
+     * Actions
+     * [x] It should remove the add-on id from `plan_addon_ids` storage
+     * [x] It should clear the `plan_addons` storage
+     */
+    #[only_owner]
+    #[endpoint(removeSubscriptionPlanAddon)]
+    fn remove_subscription_plan_addon(&self, plan_id: ManagedBuffer<Self::Api>, addon_id: ManagedBuffer<Self::Api>) {
+        self.require_owner_plan(&plan_id);
+        require!(self.plan_addon_ids(&plan_id).contains(&addon_id), "This plan has no such add-on");
+
+        self.plan_addon_ids(&plan_id).remove(&addon_id);
+        self.plan_addons(&plan_id, &addon_id).clear();
+    }
+
+    /**
+     * It allows the smart contract owner to add an address that can report the add-ons usage
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the address is not already a reporter
+     *
+     * Actions
+     * [x] It should add the address to `usage_reporters` storage
+     */
+    #[only_owner]
+    #[endpoint(addUsageReporter)]
+    fn add_usage_reporter(&self, address: ManagedAddress<Self::Api>) {
+        require!(!self.usage_reporters().contains(&address), "This address is already a reporter");
+
+        self.usage_reporters().insert(address);
+    }
+
+    /**
+     * It allows the smart contract owner to remove a usage reporter
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the address is a reporter
+     *
+     * Actions
+     * [x] It should remove the address from `usage_reporters` storage
+     */
+    #[only_owner]
+    #[endpoint(removeUsageReporter)]
+    fn remove_usage_reporter(&self, address: &ManagedAddress<Self::Api>) {
+        require!(self.usage_reporters().contains(address), "This address is not a reporter");
+
+        self.usage_reporters().remove(address);
+    }
+
+    /**
+     * It allows a usage reporter to submit the units used by a user for an add-on in a billing period
+     *
+     * The billing period is the index of the period [timestamp / add-on billing period]
+     *
+     * Validation
+     * [x] It should check that the caller is a usage reporter
+     * [x] It should check that the plan has the add-on
+     * [x] It should check that the user has a subscription to the plan
+     * [x] It should check that the billing period is not in the future
+     * [x] It should check that the user subscription covered a part of the billing period
+     * [x] It should check that the units are not zero
+     *
+     * Actions
+     * [x] It should add the record to `user_open_usage` storage if it does not exist
+     * [x] It should add the units to `user_usage` storage and update the record charge based on the add-on price tiers
+     * [x] It should update the `user_outstanding_usage_charge` storage with the charge difference
+     * [x] It should emit the `reportUsage` event
+     */
+    #[endpoint(reportUsage)]
+    fn report_usage(
+        &self,
+        address: ManagedAddress<Self::Api>,
+        plan_id: ManagedBuffer<Self::Api>,
+        addon_id: ManagedBuffer<Self::Api>,
+        period: u64,
+        units: u64
+    ) {
+        require!(self.usage_reporters().contains(&self.blockchain().get_caller()), "Only a usage reporter can report usage");
+        require!(self.plan_addon_ids(&plan_id).contains(&addon_id), "This plan has no such add-on");
+        require!(!self.user_plans(&address, &plan_id).is_empty(), "The user is not subscribed to this plan");
+        require!(units > 0, "Invalid units");
+
+        let addon = self.plan_addons(&plan_id, &addon_id).get();
+        require!(period <= self.get_current_usage_period(&addon), "Invalid billing period");
+
+        let user_plan = self.user_plans(&address, &plan_id).get();
+        require!(
+            period * addon.period_duration < user_plan.expires_at &&
+                (period + 1) * addon.period_duration > user_plan.first_subscribed,
+            "The user was not subscribed to this plan in this billing period"
+        );
+
+        let usage_key = UsageKey {
+            plan_id: plan_id.clone(),
+            addon_id: addon_id.clone(),
+            period,
+        };
+
+        let mut record = if self.user_open_usage(&address).insert(usage_key.clone()) {
+            UsageRecord {
+                units: 0,
+                token_identifier: addon.token_identifier.clone(),
+                charge: BigUint::zero(),
+            }
+        } else {
+            self.user_usage(&address, &usage_key).get()
+        };
+
+        self.user_outstanding_usage_charge(&address, &record.token_identifier).update(|charge| *charge -= &record.charge);
+
+        record.units += units;
+        record.token_identifier = addon.token_identifier.clone();
+        record.charge = self.get_usage_charge(&addon, record.units);
+
+        self.user_outstanding_usage_charge(&address, &record.token_identifier).update(|charge| *charge += &record.charge);
+        self.user_usage(&address, &usage_key).set(record);
+
+        self.report_usage_event(&address, &plan_id, &addon_id, period, units);
+    }
+
+    /**
+     * It allows anyone to add funds to the deposit used to settle their add-ons usage
+     *
+     * Actions
+     * [x] It should add the payment to `usage_deposits` storage
+     */
+    #[payable("*")]
+    #[endpoint(depositUsageFunds)]
+    fn deposit_usage_funds(&self) {
+        let (token_identifier, amount) = self.call_value().egld_or_single_fungible_esdt();
+        let caller = self.blockchain().get_caller();
+
+        require!(amount > 0, "Invalid amount");
+
+        self.usage_deposits(&caller, &token_identifier).update(|deposit| *deposit += amount);
+    }
+
+    /**
+     * It allows a user to withdraw funds from its usage deposit
+     *
+     * Validation
+     * [x] It should check that the deposit left covers the outstanding usage charges
+     *
+     * Actions
+     * [x] It should remove the amount from `usage_deposits` storage
+     * [x] It should send the amount to the caller
+     */
+    #[endpoint(withdrawUsageFunds)]
+    fn withdraw_usage_funds(&self, token_identifier: EgldOrEsdtTokenIdentifier<Self::Api>, amount: BigUint<Self::Api>) {
+        let caller = self.blockchain().get_caller();
+        let deposit = self.usage_deposits(&caller, &token_identifier).get();
+
+        require!(amount > 0 && amount <= deposit, "Invalid amount");
+
+        let deposit_left = deposit - &amount;
+        require!(
+            deposit_left >= self.user_outstanding_usage_charge(&caller, &token_identifier).get(),
+            "The deposit should cover the outstanding usage charges"
+        );
+
+        self.usage_deposits(&caller, &token_identifier).set(deposit_left);
+        self.send().direct(&caller, &token_identifier, 0, &amount);
+    }
+
+    /**
+     * It allows anyone to settle the usage of a user for the closed billing periods, from the user deposit
+     *
+     * The records that the deposit cannot cover stay open until the next settlement
+     * At most `max_count` records are checked in a call
+     *
+     * Actions
+     * [x] It should drop the records of removed add-ons
+     * [x] It should remove the charges from `usage_deposits` storage
+     * [x] It should send the charges to the merchant or to `payment_address`
+     * [x] It should remove the settled records from `user_open_usage` and `user_usage` storage
+     * [x] It should remove the settled and dropped charges from `user_outstanding_usage_charge` storage
+     * [x] It should emit the `settleUsage` event for each settled record
+     *
+     * It returns the number of records left open
+     */
+    #[endpoint(settleUsage)]
+    fn settle_usage(&self, address: ManagedAddress<Self::Api>, max_count: usize) -> usize {
+        let mut usage_keys: ManagedVec<Self::Api, UsageKey<Self::Api>> = ManagedVec::new();
+
+        for usage_key in self.user_open_usage(&address).iter().take(max_count) {
+            usage_keys.push(usage_key);
+        }
+
+        for usage_key in usage_keys.iter() {
+            let record = self.user_usage(&address, &usage_key).get();
+
+            if self.plan_addons(&usage_key.plan_id, &usage_key.addon_id).is_empty() {
+                self.close_usage_record(&address, &usage_key, &record);
+                continue;
+            }
+
+            let addon = self.plan_addons(&usage_key.plan_id, &usage_key.addon_id).get();
+            if usage_key.period >= self.get_current_usage_period(&addon) {
+                continue;
+            }
+
+            let deposit = self.usage_deposits(&address, &record.token_identifier).get();
+            if record.charge > deposit {
+                continue;
+            }
+
+            self.usage_deposits(&address, &record.token_identifier).set(deposit - &record.charge);
+            self.close_usage_record(&address, &usage_key, &record);
+
+            if record.charge > 0 {
+                let payment = EgldOrEsdtTokenPayment::new(record.token_identifier.clone(), 0, record.charge.clone());
+
+                if !self.plan_merchant(&usage_key.plan_id).is_empty() {
+                    self.settle_merchant_payment(self.plan_merchant(&usage_key.plan_id).get(), &payment);
+                } else {
+                    self.settle_payment(&usage_key.plan_id, &payment);
+                }
+            }
+
+            self.settle_usage_event(&address, &usage_key.plan_id, &usage_key.addon_id, usage_key.period, &record.charge);
+        }
+
+        self.user_open_usage(&address).len()
+    }
+
+    /**
+     * It returns the charge for the units used in a billing period, based on the add-on price tiers
+     */
+    fn get_usage_charge(&self, addon: &MeteredAddon<Self::Api>, units: u64) -> BigUint<Self::Api> {
+        let mut charge = BigUint::zero();
+        let mut tier_start = 0;
+
+        for tier in addon.tiers.iter() {
+            if units <= tier_start {
+                break;
+            }
+
+            let tier_end = if tier.up_to == 0 { units } else { units.min(tier.up_to) };
+
+            charge += &tier.unit_price * (tier_end - tier_start);
+            tier_start = tier_end;
+        }
+
+        charge
+    }
+
+    /**
+     * It returns the index of the current billing period of an add-on
+     */
+    #[inline]
+    fn get_current_usage_period(&self, addon: &MeteredAddon<Self::Api>) -> u64 {
+        self.blockchain().get_block_timestamp() / addon.period_duration
+    }
+
+    /**
+     * It removes a usage record of a user and its charge from the outstanding charges
+     */
+    #[inline]
+    fn close_usage_record(&self, address: &ManagedAddress, usage_key: &UsageKey<Self::Api>, record: &UsageRecord<Self::Api>) {
+        self.user_open_usage(address).remove(usage_key);
+        self.user_usage(address, usage_key).clear();
+        self.user_outstanding_usage_charge(address, &record.token_identifier).update(|charge| *charge -= &record.charge);
+    }
+}
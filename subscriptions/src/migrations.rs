@@ -0,0 +1,164 @@
+elrond_wasm::imports!();
+
+use crate::activation::EXPIRY_BUCKET_DURATION;
+use crate::structs::UserPlan;
+
+#[elrond_wasm::module]
+pub trait MigrationsModule:
+    crate::storage::StorageModule +
+    crate::pricing::PricingModule +
+    crate::activation::ActivationModule +
+    crate::plans::PlansModule
+{
+    /**
+     * It allows the smart contract owner to start the migration mode, in which the payments are disabled
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the migration was not finalized before
+     *
+     * Actions
+     * [x] It should set the `migration_mode` storage to true
+     */
+    #[only_owner]
+    #[endpoint(startMigration)]
+    fn start_migration(&self) {
+        require!(!self.migration_finalized().get(), "The migration was already finalized");
+
+        self.migration_mode().set(true);
+    }
+
+    /**
+     * It allows the smart contract owner to finalize the migration, the migration mode cannot be started again
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the migration mode is started
+     *
+     * Actions
+     * [x] It should set the `migration_mode` storage to false
+     * [x] It should set the `migration_finalized` storage to true
+     */
+    #[only_owner]
+    #[endpoint(finalizeMigration)]
+    fn finalize_migration(&self) {
+        self.require_migration_mode();
+
+        self.migration_mode().set(false);
+        self.migration_finalized().set(true);
+    }
+
+    /**
+     * It allows the smart contract owner to import a batch of user subscriptions from a previous deployment
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the migration mode is started
+     * [x] It should check that the subscription plans exist, the merchant plans are imported with their namespaced ids
+     *
+     * Actions
+     * [x] It should add the user addresses to the `users` storage
+     * [x] It should add the user subscriptions to the `user_plan_ids` and `user_plans` storage, replacing existing ones
+     * [x] It should keep the user subscriptions that expired before the `expiry_prune_day` storage out of the subscriber indexes
+     * [x] It should add the other user addresses to the `plan_subscribers` storage and count them as active
+     * [x] It should add the other user subscriptions to their expiry buckets
+     * [x] It should add the running pauses to their pause buckets, the paused subscriptions are not counted as active
+     */
+    #[only_owner]
+    #[endpoint(importUserPlans)]
+    fn import_user_plans(
+        &self,
+        user_plans: MultiValueEncoded<MultiValue2<ManagedAddress<Self::Api>, UserPlan<Self::Api>>>
+    ) {
+        self.require_migration_mode();
+
+        let current_timestamp = self.blockchain().get_block_timestamp();
+        let prune_day = self.expiry_prune_day().get();
+
+        for item in user_plans.into_iter() {
+            let (address, user_plan) = item.into_tuple();
+            let plan_id = user_plan.plan_id.clone();
+
+            require!(self.plan_ids().contains(&plan_id), "This plan does not exist");
+
+            if self.user_plan_ids(&address).contains(&plan_id) {
+                self.unindex_user_plan(&address, &plan_id);
+            }
+
+            self.users().insert(address.clone());
+            self.user_plan_ids(&address).insert(plan_id.clone());
+
+            if user_plan.expires_at / EXPIRY_BUCKET_DURATION >= prune_day {
+                self.add_plan_subscriber(&address, &plan_id);
+                self.index_user_plan_expiry(&address, &plan_id, user_plan.expires_at);
+
+                if user_plan.paused_until > current_timestamp {
+                    self.index_user_plan_pause(&address, &plan_id, user_plan.paused_until);
+                }
+            }
+
+            self.user_plans(&address, &plan_id).set(user_plan);
+        }
+    }
+
+    /**
+     * It allows the smart contract owner to import the total payments made to a previous deployment
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the migration mode is started
+     *
+     * Actions
+     * [x] It should add the tokens to the `payment_token_ids` storage
+     * [x] It should add the amounts to the `payment_token_amounts` storage
+     */
+    #[only_owner]
+    #[endpoint(importPaymentTotals)]
+    fn import_payment_totals(
+        &self,
+        totals: MultiValueEncoded<MultiValue2<EgldOrEsdtTokenIdentifier<Self::Api>, BigUint<Self::Api>>>
+    ) {
+        self.require_migration_mode();
+
+        for total in totals.into_iter() {
+            let (token_identifier, amount) = total.into_tuple();
+
+            self.payment_token_ids().insert(token_identifier.clone());
+            self.payment_token_amounts(&token_identifier).update(|total_amount| *total_amount += amount);
+        }
+    }
+
+    /**
+     * It allows the smart contract owner to import the total payments made by users to a previous deployment
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the migration mode is started
+     *
+     * Actions
+     * [x] It should add the user addresses to the `users` storage
+     * [x] It should add the tokens to the `user_payment_tokens` storage
+     * [x] It should add the amounts to the `user_payment_amounts` storage
+     */
+    #[only_owner]
+    #[endpoint(importUserPaymentTotals)]
+    fn import_user_payment_totals(
+        &self,
+        totals: MultiValueEncoded<MultiValue3<ManagedAddress<Self::Api>, EgldOrEsdtTokenIdentifier<Self::Api>, BigUint<Self::Api>>>
+    ) {
+        self.require_migration_mode();
+
+        for total in totals.into_iter() {
+            let (address, token_identifier, amount) = total.into_tuple();
+
+            self.users().insert(address.clone());
+            self.user_payment_tokens(&address).insert(token_identifier.clone());
+            self.user_payment_amounts(&address, &token_identifier).update(|total_amount| *total_amount += amount);
+        }
+    }
+
+    #[inline]
+    fn require_migration_mode(&self) {
+        require!(self.migration_mode().get(), "The migration mode is not started");
+    }
+}
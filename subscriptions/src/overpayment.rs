@@ -0,0 +1,70 @@
+elrond_wasm::imports!();
+
+use crate::structs::OverpaymentPolicy;
+
+#[elrond_wasm::module]
+pub trait OverpaymentModule:
+    crate::storage::StorageModule +
+    crate::pricing::PricingModule
+{
+    /**
+     * It allows the smart contract owner to choose how the payments greater than the price are handled for a plan
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the subscription plan exists
+     * [x] It should check that the subscription plan is not a merchant plan
+     *
+     * Actions
+     * [x] It should set the policy to `plan_overpayment_policy` storage
+     */
+    #[only_owner]
+    #[endpoint(setSubscriptionPlanOverpaymentPolicy)]
+    fn set_subscription_plan_overpayment_policy(
+        &self,
+        plan_id: ManagedBuffer<Self::Api>,
+        policy: OverpaymentPolicy
+    ) {
+        self.require_owner_plan(&plan_id);
+
+        self.plan_overpayment_policy(&plan_id).set(policy);
+    }
+
+    /**
+     * It takes the plan price from a payment and handles the surplus based on the plan overpayment policy
+     *
+     * The surplus of SFT, NFT or meta-ESDT payments is always refunded
+     *
+     * Validation
+     * [x] It should check that the payment amount is equal to the price, when overpayments are rejected
+     * [x] It should check that the payment amount is not lower than the price
+     *
+     * Actions
+     * [x] It should send the surplus back to the caller, when the policy is `Refund`
+     * [x] It should add the surplus to the `user_credits` storage, when the policy is `Credit`
+     */
+    fn take_plan_price(
+        &self,
+        plan_id: &ManagedBuffer,
+        caller: &ManagedAddress,
+        payment: EgldOrEsdtTokenPayment<Self::Api>,
+        price: BigUint<Self::Api>
+    ) -> EgldOrEsdtTokenPayment<Self::Api> {
+        if payment.amount == price {
+            return payment
+        }
+
+        let policy = self.plan_overpayment_policy(plan_id).get();
+        require!(policy != OverpaymentPolicy::Reject && payment.amount > price, "Invalid payment amount");
+
+        let surplus = &payment.amount - &price;
+
+        if policy == OverpaymentPolicy::Credit && payment.token_nonce == 0 {
+            self.user_credits(caller, &payment.token_identifier).update(|credit| *credit += surplus);
+        } else {
+            self.send().direct(caller, &payment.token_identifier, payment.token_nonce, &surplus);
+        }
+
+        EgldOrEsdtTokenPayment::new(payment.token_identifier, payment.token_nonce, price)
+    }
+}
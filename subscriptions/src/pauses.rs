@@ -0,0 +1,132 @@
+elrond_wasm::imports!();
+
+use crate::activation::LIFETIME_EXPIRY;
+use crate::structs::PauseSettings;
+
+#[elrond_wasm::module]
+pub trait PausesModule:
+    crate::storage::StorageModule +
+    crate::pricing::PricingModule +
+    crate::activation::ActivationModule +
+    crate::events::EventsModule
+{
+    /**
+     * It allows the smart contract owner to let the subscribers of a plan pause their subscription
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the subscription plan exists
+     * [x] It should check that the subscription plan is not a merchant plan
+     * [x] It should check that the maximum pause duration is not zero
+     *
+     * Actions
+     * [x] It should set the settings to `plan_pause_settings` storage
+     */
+    #[only_owner]
+    #[endpoint(setSubscriptionPlanPauseSettings)]
+    fn set_subscription_plan_pause_settings(&self, plan_id: ManagedBuffer<Self::Api>, max_duration: u64, cooldown: u64) {
+        self.require_owner_plan(&plan_id);
+        require!(max_duration > 0, "Invalid pause duration");
+
+        self.plan_pause_settings(&plan_id).set(PauseSettings {
+            max_duration,
+            cooldown,
+        });
+    }
+
+    /**
+     * It allows the smart contract owner to stop the subscribers of a plan from pausing their subscription
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the subscription plan is not a merchant plan
+     * [x] It should check that the plan has pause settings
+     *
+     * Actions
+     * [x] It should clear the `plan_pause_settings` storage
+     */
+    #[only_owner]
+    #[endpoint(removeSubscriptionPlanPauseSettings)]
+    fn remove_subscription_plan_pause_settings(&self, plan_id: ManagedBuffer<Self::Api>) {
+        self.require_owner_plan(&plan_id);
+        require!(!self.plan_pause_settings(&plan_id).is_empty(), "This plan cannot be paused");
+
+        self.plan_pause_settings(&plan_id).clear();
+    }
+
+    /**
+     * It allows a subscriber to pause an active subscription, for at most the plan maximum pause duration
+     *
+     * The subscription resumes by itself when the maximum pause duration is reached
+     *
+     * Validation
+     * [x] It should check that the plan can be paused
+     * [x] It should check that the caller has an active subscription for this plan
+     * [x] It should check that the subscription is not a lifetime subscription
+     * [x] It should check that the pause cooldown since the last pause has passed
+     *
+     * Actions
+     * [x] It should set the subscription `paused_until` date
+     * [x] It should move the subscription to the pause bucket of its end day, it is no longer counted as active
+     * [x] It should extend the subscription expiration date with the maximum pause duration
+     * [x] It should emit the `pauseSubscription` event
+     */
+    #[endpoint(pauseSubscription)]
+    fn pause_subscription(&self, plan_id: ManagedBuffer<Self::Api>) {
+        let caller = self.blockchain().get_caller();
+        let current_timestamp = self.blockchain().get_block_timestamp();
+
+        require!(!self.plan_pause_settings(&plan_id).is_empty(), "This plan cannot be paused");
+        require!(self.user_plan_ids(&caller).contains(&plan_id), "The user is not subscribed to this plan");
+
+        let settings = self.plan_pause_settings(&plan_id).get();
+        let user_plan = self.user_plans(&caller, &plan_id).get();
+
+        require!(self.is_user_plan_active(&user_plan, current_timestamp), "The user subscription is not active");
+        require!(user_plan.expires_at != LIFETIME_EXPIRY, "Lifetime subscriptions cannot be paused");
+        require!(
+            user_plan.paused_until == 0 || current_timestamp >= user_plan.paused_until + settings.cooldown,
+            "The subscription was paused too recently"
+        );
+
+        let paused_until = current_timestamp + settings.max_duration;
+
+        self.unindex_user_plan_pause(&caller, &plan_id, user_plan.paused_until);
+        self.index_user_plan_pause(&caller, &plan_id, paused_until);
+        self.set_user_plan_expiry(&caller, &plan_id, user_plan.expires_at + settings.max_duration);
+        self.user_plans(&caller, &plan_id).update(|user_plan| user_plan.paused_until = paused_until);
+
+        self.pause_subscription_event(&caller, &plan_id, paused_until);
+    }
+
+    /**
+     * It allows a subscriber to resume a paused subscription before the end of the pause
+     *
+     * Validation
+     * [x] It should check that the caller has a paused subscription for this plan
+     *
+     * Actions
+     * [x] It should remove the unused pause time from the subscription expiration date
+     * [x] It should set the subscription `paused_until` date to the current timestamp
+     * [x] It should remove the subscription from its pause bucket, it is counted as active again
+     * [x] It should emit the `resumeSubscription` event
+     */
+    #[endpoint(resumeSubscription)]
+    fn resume_subscription(&self, plan_id: ManagedBuffer<Self::Api>) {
+        let caller = self.blockchain().get_caller();
+        let current_timestamp = self.blockchain().get_block_timestamp();
+
+        require!(self.user_plan_ids(&caller).contains(&plan_id), "The user is not subscribed to this plan");
+
+        let user_plan = self.user_plans(&caller, &plan_id).get();
+        require!(user_plan.paused_until > current_timestamp, "The user subscription is not paused");
+
+        let expires_at = user_plan.expires_at - (user_plan.paused_until - current_timestamp);
+
+        self.unindex_user_plan_pause(&caller, &plan_id, user_plan.paused_until);
+        self.set_user_plan_expiry(&caller, &plan_id, expires_at);
+        self.user_plans(&caller, &plan_id).update(|user_plan| user_plan.paused_until = current_timestamp);
+
+        self.resume_subscription_event(&caller, &plan_id, expires_at);
+    }
+}
@@ -0,0 +1,380 @@
+elrond_wasm::imports!();
+
+use crate::structs::{Plan, PlanStatus, ActivationSource};
+use crate::activation::EXPIRY_BUCKET_DURATION;
+use crate::merchants::MERCHANT_PLAN_ID_SEPARATOR;
+
+pub const PLAN_ID_CHUNK_LENGTH: usize = 32;
+
+#[elrond_wasm::module]
+pub trait PlansModule:
+    crate::storage::StorageModule +
+    crate::pricing::PricingModule +
+    crate::activation::ActivationModule
+{
+    /**
+     * It adds a subscription plan
+     *
+     * Validation
+     * [x] It should check that the subscription plan does not exist
+     *
+     * Actions
+     * [x] It should add the subscription plan to storage
+     */
+    fn create_plan(&self, plan_id: &ManagedBuffer, validity: u64) {
+        require!(!self.plan_ids().contains(plan_id), "This plan already exists");
+        self.plan_ids().insert(plan_id.clone());
+
+        let plan: Plan<Self::Api> = Plan {
+            id: plan_id.clone(),
+            validity,
+            status: PlanStatus::Enabled,
+
+            /* not used at this time */
+            refund_period: 0,
+            allows_refund: false,
+        };
+
+        self.plans(plan_id).set(plan);
+    }
+
+    /**
+     * It checks that a plan id does not contain the merchant plan id separator, reserved for the merchant plans
+     */
+    fn require_valid_plan_id(&self, plan_id: &ManagedBuffer) {
+        let mut chunk = [0u8; PLAN_ID_CHUNK_LENGTH];
+        let mut position = 0;
+
+        while position < plan_id.len() {
+            let chunk_length = core::cmp::min(PLAN_ID_CHUNK_LENGTH, plan_id.len() - position);
+            let _ = plan_id.load_slice(position, &mut chunk[..chunk_length]);
+
+            require!(!chunk[..chunk_length].contains(&MERCHANT_PLAN_ID_SEPARATOR[0]), "Invalid plan id");
+            position += chunk_length;
+        }
+    }
+
+    /**
+     * It removes a subscription plan and all its settings
+     *
+     * Validation
+     * [x] It should check that the subscription plan exists
+     * [x] It should check that the subscription plan has no subscribers left
+     * [x] It should check that the subscription plan is not a bundle component
+     *
+     * Actions
+     * [x] It should remove the subscription plan ID from storage
+     * [x] It should remove the subscription plan from storage
+     * [x] It should remove the subscription plan token prices from storage
+     * [x] It should remove the subscription plan token nonce prices, settlements, promotions and payment addresses from storage
+     * [x] It should remove the subscription plan holder benefits from storage
+     * [x] It should remove the subscription plan overpayment policy from storage
+     * [x] It should remove the subscription plan renewal mode and maximum horizon from storage
+     * [x] It should remove the subscription plan pause settings from storage
+     * [x] It should remove the subscription plan delegate settings from storage
+     * [x] It should remove the subscription plan prerequisites and bundle components from storage
+     * [x] It should remove the subscription plan allowlist from storage
+     * [x] It should remove the subscription plan loyalty points price from storage
+     * [x] It should remove the subscription plan stake requirement from storage
+     * [x] It should remove the subscription plan entitlements from storage
+     * [x] It should remove the subscription plan metered add-ons from storage
+     * [x] It should remove the subscription plan from its merchant plans
+     */
+    fn delete_plan(&self, plan_id: &ManagedBuffer) {
+        require!(self.plan_ids().contains(plan_id), "This plan does not exist");
+        require!(self.plan_subscribers(plan_id).is_empty(), "This plan still has subscribers");
+        require!(self.bundle_component_count(plan_id).get() == 0, "This plan is a bundle component");
+
+        self.plan_ids().remove(plan_id);
+        self.plans(plan_id).clear();
+        self.plan_active_subscriber_count(plan_id).clear();
+
+        for token_identifier in self.plan_tokens(plan_id).iter() {
+            self.plan_prices(plan_id, &token_identifier).clear();
+            self.clear_plan_token_settings(plan_id, &token_identifier);
+        }
+
+        for token_identifier in self.plan_nonce_tokens(plan_id).iter() {
+            self.clear_plan_token_settings(plan_id, &token_identifier);
+        }
+
+        self.plan_tokens(plan_id).clear();
+        self.plan_nonce_tokens(plan_id).clear();
+        self.plan_holder_benefits(plan_id).clear();
+        self.plan_overpayment_policy(plan_id).clear();
+        self.plan_renewal_mode(plan_id).clear();
+        self.plan_max_horizon(plan_id).clear();
+        self.plan_pause_settings(plan_id).clear();
+        self.plan_delegate_settings(plan_id).clear();
+        self.plan_prerequisites(plan_id).clear();
+
+        for component_id in self.plan_bundle_components(plan_id).iter() {
+            self.plan_bundle_validity(plan_id, &component_id).clear();
+            self.bundle_component_count(&component_id).update(|count| *count = count.saturating_sub(1));
+        }
+
+        self.plan_bundle_components(plan_id).clear();
+        self.bundle_component_count(plan_id).clear();
+        self.plan_private(plan_id).clear();
+        self.plan_allowlist(plan_id).clear();
+        self.plan_allowlist_root(plan_id).clear();
+        self.plan_points_per_day(plan_id).clear();
+        self.plan_stake_requirement(plan_id).clear();
+
+        for key in self.plan_entitlement_keys(plan_id).iter() {
+            self.plan_entitlements(plan_id, &key).clear();
+            self.entitlement_plan_ids(&key).remove(plan_id);
+        }
+
+        self.plan_entitlement_keys(plan_id).clear();
+
+        for addon_id in self.plan_addon_ids(plan_id).iter() {
+            self.plan_addons(plan_id, &addon_id).clear();
+        }
+
+        self.plan_addon_ids(plan_id).clear();
+
+        if !self.plan_merchant(plan_id).is_empty() {
+            self.merchant_plan_ids(self.plan_merchant(plan_id).get()).remove(plan_id);
+            self.plan_merchant(plan_id).clear();
+        }
+    }
+
+    /**
+     * It updates the status of a subscription plan
+     *
+     * Validation
+     * [x] It should check that the subscription plan exists
+     *
+     * Actions
+     * [x] It should update the plan status
+     */
+    fn set_plan_status(&self, plan_id: &ManagedBuffer, status: PlanStatus) {
+        require!(self.plan_ids().contains(plan_id), "This plan does not exist");
+
+        let mut plan = self.plans(plan_id).get();
+        plan.status = status;
+
+        self.plans(plan_id).set(plan);
+    }
+
+    /**
+     * It sets the subscription payment amount for a token
+     *
+     * Validation
+     * [x] It should check that the subscription plan exists
+     *
+     * Actions
+     * [x] It should add the token to `plan_tokens` storage if it does not exist
+     * [x] It should set the price on `plan_prices` storage
+     */
+    fn set_plan_price(
+        &self,
+        plan_id: &ManagedBuffer,
+        token_identifier: &EgldOrEsdtTokenIdentifier,
+        price: BigUint<Self::Api>
+    ) {
+        require!(self.plan_ids().contains(plan_id), "This plan does not exist");
+
+        if !self.plan_tokens(plan_id).contains(token_identifier) {
+            self.plan_tokens(plan_id).insert(token_identifier.clone());
+        }
+
+        self.plan_prices(plan_id, token_identifier).set(price);
+    }
+
+    /**
+     * It removes a token from plan payments
+     *
+     * Validation
+     * [x] It should check that the subscription plan exists
+     * [x] It should check that the token or some of its nonces are enabled for this subscription plan
+     *
+     * Actions
+     * [x] It should remove the token from `plan_tokens` and `plan_nonce_tokens` storage
+     * [x] It should remove the token prices from `plan_prices` storage
+     * [x] It should remove the token nonces, nonce prices, settlement, promotions and payment address from storage
+     */
+    fn delete_plan_token(&self, plan_id: &ManagedBuffer, token_identifier: &EgldOrEsdtTokenIdentifier) {
+        require!(self.plan_ids().contains(plan_id), "This plan does not exist");
+        require!(self.is_plan_token(plan_id, token_identifier), "No price configured for this plan/token combination");
+
+        self.plan_tokens(plan_id).remove(token_identifier);
+        self.plan_nonce_tokens(plan_id).remove(token_identifier);
+        self.plan_prices(plan_id, token_identifier).clear();
+        self.clear_plan_token_settings(plan_id, token_identifier);
+    }
+
+    /**
+     * It releases a batch of subscribers of an archived plan and returns the number of subscribers left
+     *
+     * The subscribers with remaining time are compensated on the compensation plan with the same amount of time.
+     * Without a compensation plan, the release is refused while active subscribers exist.
+     *
+     * Validation
+     * [x] It should check that the subscription plan exists and is archived
+     * [x] It should check that the compensation plan exists and is a different plan
+     * [x] It should check that no active subscribers exist when there is no compensation plan
+     *
+     * Actions
+     * [x] It should unlock the locked stakes, the stakers are only compensated for the time paid before staking
+     * [x] It should call `activate_user_plan` function with the `Compensation` source for the active subscribers
+     * [x] It should remove the quota usage of the released subscribers
+     * [x] It should call `remove_user_plan` function for the released subscribers
+     */
+    fn release_plan_subscribers(
+        &self,
+        plan_id: &ManagedBuffer,
+        compensation_plan_id: Option<ManagedBuffer>,
+        max_count: usize
+    ) -> usize {
+        require!(self.plan_ids().contains(plan_id), "This plan does not exist");
+        require!(self.plans(plan_id).get().status == PlanStatus::Archived, "Subscription plan is not archived");
+
+        let compensation_plan = compensation_plan_id.map(|compensation_plan_id| {
+            require!(&compensation_plan_id != plan_id, "Invalid compensation plan");
+            require!(self.plan_ids().contains(&compensation_plan_id), "The compensation plan does not exist");
+
+            self.plans(&compensation_plan_id).get()
+        });
+
+        let current_timestamp = self.blockchain().get_block_timestamp();
+        let mut subscribers: ManagedVec<Self::Api, ManagedAddress<Self::Api>> = ManagedVec::new();
+
+        for subscriber in self.plan_subscribers(plan_id).iter().take(max_count) {
+            subscribers.push(subscriber);
+        }
+
+        for subscriber in subscribers.iter() {
+            let user_plan = self.user_plans(&subscriber, plan_id).get();
+            let mut remaining_time = self.get_remaining_time(&user_plan, current_timestamp);
+
+            if !self.user_stakes(&subscriber, plan_id).is_empty() {
+                let mut stake = self.user_stakes(&subscriber, plan_id).get();
+
+                if stake.unbonding_ends_at == 0 {
+                    remaining_time = stake.paid_time;
+                    stake.unbonding_ends_at = current_timestamp;
+                    self.user_stakes(&subscriber, plan_id).set(stake);
+                }
+            }
+
+            if remaining_time > 0 {
+                match &compensation_plan {
+                    Some(compensation_plan) => {
+                        self.activate_user_plan(
+                            &subscriber,
+                            compensation_plan,
+                            remaining_time,
+                            ActivationSource::Compensation
+                        );
+                    },
+                    None => sc_panic!("This plan still has active subscribers"),
+                }
+            }
+
+            for key in self.plan_entitlement_keys(plan_id).iter() {
+                self.user_quota_usage(&subscriber, plan_id, &key).clear();
+            }
+
+            self.remove_user_plan(&subscriber, plan_id);
+        }
+
+        self.plan_subscribers(plan_id).len()
+    }
+
+    /**
+     * It checks that a user can pay for a subscription plan
+     *
+     * The enabled plans are available to everyone, the deprecated plans can only be renewed by their subscribers
+     */
+    fn require_plan_available(&self, plan: &Plan<Self::Api>, user_address: &ManagedAddress) {
+        match plan.status {
+            PlanStatus::Enabled => {},
+            PlanStatus::Deprecated => require!(
+                self.user_plan_ids(user_address).contains(&plan.id),
+                "This plan is not available to new subscribers"
+            ),
+            PlanStatus::Disabled => sc_panic!("Subscription plan is disabled"),
+            PlanStatus::Archived => sc_panic!("Subscription plan is archived"),
+        }
+    }
+
+    /**
+     * It prunes the user subscriptions that expired before the current day, starting with the oldest expiry day not pruned
+     *
+     * The pruned user subscriptions are only removed from the subscriber indexes, their entries are kept as history.
+     * The pauses that ended in a pruned day are processed first, so the subscriptions are counted as active again.
+     *
+     * Each day checked, each pause ended and each user subscription pruned counts as one step
+     *
+     * Actions
+     * [x] It should call `unindex_user_plan_pause` function for the pauses that ended
+     * [x] It should call `unindex_user_plan` function for the expired user subscriptions
+     * [x] It should move the `expiry_prune_day` storage to the first day with user subscriptions left to prune
+     *
+     * It returns the number of user subscriptions pruned
+     */
+    fn prune_expired_user_plans(&self, max_steps: usize) -> usize {
+        let current_day = self.blockchain().get_block_timestamp() / EXPIRY_BUCKET_DURATION;
+        let mut day = self.expiry_prune_day().get();
+        let mut steps = 0;
+        let mut pruned = 0;
+
+        while day < current_day && steps < max_steps {
+            steps += 1;
+
+            if let Some(entry) = self.pause_buckets(day).iter().next() {
+                self.unindex_user_plan_pause(&entry.address, &entry.plan_id, entry.expires_at);
+                continue;
+            }
+
+            match self.expiry_buckets(day).iter().next() {
+                Some(entry) => {
+                    self.unindex_user_plan(&entry.address, &entry.plan_id);
+                    pruned += 1;
+                },
+                None => day += 1,
+            }
+        }
+
+        self.expiry_prune_day().set(day);
+
+        pruned
+    }
+
+    /**
+     * It removes the user entries of the plans that no longer exist
+     *
+     * Actions
+     * [x] It should remove the user entries of the removed plans
+     * [x] It should keep the user entries that expired before the `expiry_prune_day` storage out of the subscriber indexes
+     * [x] It should add the user address to the `plan_subscribers` storage when missing
+     * [x] It should add the user subscription to its expiry bucket when missing
+     */
+    fn remove_orphaned_user_plans(&self, user_address: &ManagedAddress) {
+        let mut plan_ids: ManagedVec<Self::Api, ManagedBuffer<Self::Api>> = ManagedVec::new();
+
+        for plan_id in self.user_plan_ids(user_address).iter() {
+            plan_ids.push(plan_id);
+        }
+
+        let prune_day = self.expiry_prune_day().get();
+
+        for plan_id in plan_ids.iter() {
+            if !self.plan_ids().contains(&plan_id) {
+                self.remove_user_plan(user_address, &plan_id);
+                continue;
+            }
+
+            let expires_at = self.user_plans(user_address, &plan_id).get().expires_at;
+
+            if expires_at / EXPIRY_BUCKET_DURATION < prune_day {
+                self.unindex_user_plan(user_address, &plan_id);
+            } else {
+                self.add_plan_subscriber(user_address, &plan_id);
+                self.index_user_plan_expiry(user_address, &plan_id, expires_at);
+            }
+        }
+    }
+}
@@ -0,0 +1,265 @@
+elrond_wasm::imports!();
+
+use crate::structs::PaymentSettlement;
+
+#[elrond_wasm::module]
+pub trait PricingModule:
+    crate::storage::StorageModule
+{
+    /**
+     * It allows the smart contract owner to set the subscription payment amount for a specific token nonce
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the subscription plan exists
+     * [x] It should check that the subscription plan is not a merchant plan
+     * [x] It should check that the token is an ESDT and the nonce is not zero
+     *
+     * Actions
+     * [x] It should add the token to `plan_nonce_tokens` storage if it does not exist
+     * [x] It should add the nonce to `plan_token_nonces` storage if it does not exist
+     * [x] It should set the price on `plan_nonce_prices` storage
+     */
+    #[only_owner]
+    #[endpoint(setSubscriptionPlanNoncePrice)]
+    fn set_subscription_plan_nonce_price(
+        &self,
+        plan_id: ManagedBuffer<Self::Api>,
+        token_identifier: &EgldOrEsdtTokenIdentifier<Self::Api>,
+        nonce: u64,
+        price: BigUint<Self::Api>
+    ) {
+        self.require_owner_plan(&plan_id);
+        require!(token_identifier.is_esdt() && nonce > 0, "Only SFT, NFT or meta-ESDT nonces can be priced");
+
+        self.plan_nonce_tokens(&plan_id).insert(token_identifier.clone());
+        self.plan_token_nonces(&plan_id, token_identifier).insert(nonce);
+        self.plan_nonce_prices(&plan_id, token_identifier, nonce).set(price);
+    }
+
+    /**
+     * It allows the smart contract owner to accept token nonces at the token price set through `setSubscriptionPlanPrice`
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the subscription plan exists
+     * [x] It should check that the subscription plan is not a merchant plan
+     * [x] It should check that the token is enabled for this subscription plan
+     * [x] It should check that the token is an ESDT and the nonces are not zero
+     *
+     * Actions
+     * [x] It should add the token to `plan_nonce_tokens` storage if it does not exist
+     * [x] It should add the nonces to `plan_token_nonces` storage
+     */
+    #[only_owner]
+    #[endpoint(allowSubscriptionPlanTokenNonces)]
+    fn allow_subscription_plan_token_nonces(
+        &self,
+        plan_id: ManagedBuffer<Self::Api>,
+        token_identifier: &EgldOrEsdtTokenIdentifier<Self::Api>,
+        nonces: MultiValueEncoded<u64>
+    ) {
+        self.require_owner_plan(&plan_id);
+        require!(self.plan_tokens(&plan_id).contains(token_identifier), "No price configured for this plan/token combination");
+        require!(token_identifier.is_esdt(), "Only SFT, NFT or meta-ESDT nonces can be priced");
+
+        self.plan_nonce_tokens(&plan_id).insert(token_identifier.clone());
+
+        for nonce in nonces.into_iter() {
+            require!(nonce > 0, "Only SFT, NFT or meta-ESDT nonces can be priced");
+
+            self.plan_token_nonces(&plan_id, token_identifier).insert(nonce);
+        }
+    }
+
+    /**
+     * It allows the smart contract owner to remove a token nonce from plan payments
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the subscription plan is not a merchant plan
+     * [x] It should check that the nonce is accepted for this subscription plan
+     *
+     * Actions
+     * [x] It should remove the nonce from `plan_token_nonces` storage
+     * [x] It should remove the nonce price from `plan_nonce_prices` storage
+     * [x] It should remove the token from `plan_nonce_tokens` storage when no nonce is left
+     */
+    #[only_owner]
+    #[endpoint(removeSubscriptionPlanTokenNonce)]
+    fn remove_subscription_plan_token_nonce(
+        &self,
+        plan_id: ManagedBuffer<Self::Api>,
+        token_identifier: &EgldOrEsdtTokenIdentifier<Self::Api>,
+        nonce: u64
+    ) {
+        self.require_owner_plan(&plan_id);
+        require!(self.plan_token_nonces(&plan_id, token_identifier).contains(&nonce), "This nonce is not accepted for this plan/token combination");
+
+        self.plan_token_nonces(&plan_id, token_identifier).remove(&nonce);
+        self.plan_nonce_prices(&plan_id, token_identifier, nonce).clear();
+
+        if self.plan_token_nonces(&plan_id, token_identifier).is_empty() {
+            self.plan_nonce_tokens(&plan_id).remove(token_identifier);
+        }
+    }
+
+    /**
+     * It allows the smart contract owner to choose what happens with the payments made with a token [forwarded/burned]
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the subscription plan is not a merchant plan
+     * [x] It should check that the token or some of its nonces are enabled for this subscription plan
+     * [x] It should check that EGLD payments are not set to be burned
+     *
+     * Actions
+     * [x] It should set the settlement to `plan_token_settlement` storage
+     */
+    #[only_owner]
+    #[endpoint(setSubscriptionPlanTokenSettlement)]
+    fn set_subscription_plan_token_settlement(
+        &self,
+        plan_id: ManagedBuffer<Self::Api>,
+        token_identifier: &EgldOrEsdtTokenIdentifier<Self::Api>,
+        settlement: PaymentSettlement
+    ) {
+        self.require_owner_plan(&plan_id);
+        require!(self.is_plan_token(&plan_id, token_identifier), "No price configured for this plan/token combination");
+        require!(token_identifier.is_esdt() || settlement != PaymentSettlement::Burn, "EGLD payments cannot be burned");
+
+        self.plan_token_settlement(&plan_id, token_identifier).set(settlement);
+    }
+
+    /**
+     * It returns the price of a plan for the token and nonce of a payment
+     *
+     * The promotional prices apply to the payments without a nonce price
+     *
+     * Validation
+     * [x] It should check that the payment token has a price for this plan, when the nonce is zero
+     * [x] It should check that the payment nonce is accepted for this plan, when the nonce is not zero
+     */
+    fn get_plan_payment_price(
+        &self,
+        plan_id: &ManagedBuffer,
+        payment: &EgldOrEsdtTokenPayment<Self::Api>
+    ) -> BigUint<Self::Api> {
+        if payment.token_nonce == 0 {
+            return self.get_plan_token_price(plan_id, &payment.token_identifier)
+        }
+
+        require!(
+            self.plan_token_nonces(plan_id, &payment.token_identifier).contains(&payment.token_nonce),
+            "This token nonce is not enabled"
+        );
+
+        let nonce_price = self.plan_nonce_prices(plan_id, &payment.token_identifier, payment.token_nonce);
+        if !nonce_price.is_empty() {
+            return nonce_price.get()
+        }
+
+        self.get_plan_token_price(plan_id, &payment.token_identifier)
+    }
+
+    /**
+     * It returns the price of a plan for a token, or the promotional price when a promotion is running
+     *
+     * Validation
+     * [x] It should check that the token has a price for this plan
+     */
+    fn get_plan_token_price(&self, plan_id: &ManagedBuffer, token_identifier: &EgldOrEsdtTokenIdentifier) -> BigUint<Self::Api> {
+        require!(!self.plan_prices(plan_id, token_identifier).is_empty(), "This token is not enabled");
+
+        let current_timestamp = self.blockchain().get_block_timestamp();
+
+        for promotion in self.plan_promotions(plan_id, token_identifier).iter() {
+            if promotion.starts_at <= current_timestamp && current_timestamp < promotion.ends_at {
+                return promotion.price
+            }
+        }
+
+        self.plan_prices(plan_id, token_identifier).get()
+    }
+
+    /**
+     * It checks if a token has a price or accepted nonces for a plan
+     */
+    fn is_plan_token(&self, plan_id: &ManagedBuffer, token_identifier: &EgldOrEsdtTokenIdentifier) -> bool {
+        self.plan_tokens(plan_id).contains(token_identifier) || self.plan_nonce_tokens(plan_id).contains(token_identifier)
+    }
+
+    /**
+     * It checks that a subscription plan exists and is not a merchant plan [the merchant plans are managed by their merchants only]
+     */
+    fn require_owner_plan(&self, plan_id: &ManagedBuffer) {
+        require!(self.plan_ids().contains(plan_id), "This plan does not exist");
+        require!(self.plan_merchant(plan_id).is_empty(), "This plan is managed by its merchant");
+    }
+
+    /**
+     * It clears the accepted nonces, the nonce prices, the settlement, the promotions and the payment address of a plan token
+     */
+    fn clear_plan_token_settings(&self, plan_id: &ManagedBuffer, token_identifier: &EgldOrEsdtTokenIdentifier) {
+        for nonce in self.plan_token_nonces(plan_id, token_identifier).iter() {
+            self.plan_nonce_prices(plan_id, token_identifier, nonce).clear();
+        }
+
+        self.plan_token_nonces(plan_id, token_identifier).clear();
+        self.plan_token_settlement(plan_id, token_identifier).clear();
+        self.plan_promotions(plan_id, token_identifier).clear();
+        self.plan_token_payment_address(plan_id, token_identifier).clear();
+    }
+
+    /**
+     * It returns the address where to send the payments made with a token [token payment address or `payment_address`]
+     */
+    fn get_token_payment_address(&self, token_identifier: &EgldOrEsdtTokenIdentifier) -> ManagedAddress<Self::Api> {
+        if self.token_payment_address(token_identifier).is_empty() {
+            return self.payment_address().get()
+        }
+
+        self.token_payment_address(token_identifier).get()
+    }
+
+    /**
+     * It returns the address where to send the payments made with a plan token
+     * [merchant payment address, plan token payment address, token payment address or `payment_address`]
+     *
+     * The merchant plan payments go to their merchant, only the platform fee goes to the token payment address
+     */
+    fn get_payment_destination(&self, plan_id: &ManagedBuffer, token_identifier: &EgldOrEsdtTokenIdentifier) -> ManagedAddress<Self::Api> {
+        if !self.plan_merchant(plan_id).is_empty() {
+            return self.merchants(self.plan_merchant(plan_id).get()).get().payment_address
+        }
+
+        if self.plan_token_payment_address(plan_id, token_identifier).is_empty() {
+            return self.get_token_payment_address(token_identifier)
+        }
+
+        self.plan_token_payment_address(plan_id, token_identifier).get()
+    }
+
+    /**
+     * It forwards the payment to its payment destination or burns it, based on the plan token settlement
+     */
+    fn settle_payment(&self, plan_id: &ManagedBuffer, payment: &EgldOrEsdtTokenPayment<Self::Api>) {
+        match self.plan_token_settlement(plan_id, &payment.token_identifier).get() {
+            PaymentSettlement::Forward => {
+                self.send().direct(
+                    &self.get_payment_destination(plan_id, &payment.token_identifier),
+                    &payment.token_identifier,
+                    payment.token_nonce,
+                    &payment.amount
+                );
+            },
+            PaymentSettlement::Burn => {
+                self.send().esdt_local_burn(
+                    &payment.token_identifier.clone().unwrap_esdt(),
+                    payment.token_nonce,
+                    &payment.amount
+                );
+            },
+        }
+    }
+}
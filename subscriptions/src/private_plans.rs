@@ -0,0 +1,135 @@
+elrond_wasm::imports!();
+
+use crate::merkle::Hash;
+use crate::structs::{AllowlistRoot, HashAlgorithm};
+
+#[elrond_wasm::module]
+pub trait PrivatePlansModule:
+    crate::storage::StorageModule +
+    crate::pricing::PricingModule +
+    crate::merkle::MerkleModule
+{
+    /**
+     * It allows the smart contract owner to make a plan private, only the allowed addresses can purchase it
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the subscription plan exists
+     * [x] It should check that the subscription plan is not a merchant plan
+     *
+     * Actions
+     * [x] It should set the status to `plan_private` storage
+     */
+    #[only_owner]
+    #[endpoint(setSubscriptionPlanPrivate)]
+    fn set_subscription_plan_private(&self, plan_id: ManagedBuffer<Self::Api>, private: bool) {
+        self.require_owner_plan(&plan_id);
+
+        self.plan_private(&plan_id).set(private);
+    }
+
+    /**
+     * It allows the smart contract owner to allow addresses to purchase a private plan
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the subscription plan exists
+     * [x] It should check that the subscription plan is not a merchant plan
+     *
+     * Actions
+     * [x] It should add the addresses to `plan_allowlist` storage
+     */
+    #[only_owner]
+    #[endpoint(addSubscriptionPlanAllowedAddresses)]
+    fn add_subscription_plan_allowed_addresses(&self, plan_id: ManagedBuffer<Self::Api>, addresses: MultiValueEncoded<ManagedAddress<Self::Api>>) {
+        self.require_owner_plan(&plan_id);
+
+        for address in addresses {
+            self.plan_allowlist(&plan_id).insert(address);
+        }
+    }
+
+    /**
+     * It allows the smart contract owner to remove addresses from the allowlist of a private plan
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the subscription plan is not a merchant plan
+     *
+     * Actions
+     * [x] It should remove the addresses from `plan_allowlist` storage
+     */
+    #[only_owner]
+    #[endpoint(removeSubscriptionPlanAllowedAddresses)]
+    fn remove_subscription_plan_allowed_addresses(&self, plan_id: ManagedBuffer<Self::Api>, addresses: MultiValueEncoded<ManagedAddress<Self::Api>>) {
+        self.require_owner_plan(&plan_id);
+
+        for address in addresses {
+            self.plan_allowlist(&plan_id).remove(&address);
+        }
+    }
+
+    /**
+     * It allows the smart contract owner to set the Merkle root of the addresses allowed to purchase a private plan
+     *
+     * The leaves are the hashes of the address bytes and the pairs are hashed in sorted order, with the same algorithm
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the subscription plan exists
+     * [x] It should check that the subscription plan is not a merchant plan
+     *
+     * Actions
+     * [x] It should set the root to `plan_allowlist_root` storage
+     */
+    #[only_owner]
+    #[endpoint(setSubscriptionPlanAllowlistRoot)]
+    fn set_subscription_plan_allowlist_root(&self, plan_id: ManagedBuffer<Self::Api>, root: Hash<Self::Api>, algorithm: HashAlgorithm) {
+        self.require_owner_plan(&plan_id);
+
+        self.plan_allowlist_root(&plan_id).set(AllowlistRoot {
+            root,
+            algorithm,
+        });
+    }
+
+    /**
+     * It allows the smart contract owner to remove the Merkle root of a private plan
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the subscription plan is not a merchant plan
+     *
+     * Actions
+     * [x] It should clear the `plan_allowlist_root` storage
+     */
+    #[only_owner]
+    #[endpoint(clearSubscriptionPlanAllowlistRoot)]
+    fn clear_subscription_plan_allowlist_root(&self, plan_id: ManagedBuffer<Self::Api>) {
+        self.require_owner_plan(&plan_id);
+
+        self.plan_allowlist_root(&plan_id).clear();
+    }
+
+    /**
+     * It checks that an address can purchase a plan
+     *
+     * The private plans can be purchased by the addresses in the plan allowlist
+     * or by the addresses that provide a valid Merkle proof for the plan allowlist root
+     * The proof is empty when the tree has a single address, its leaf is the root
+     */
+    fn require_plan_access(&self, plan_id: &ManagedBuffer, address: &ManagedAddress, proof: &ManagedVec<Self::Api, Hash<Self::Api>>) {
+        if !self.plan_private(plan_id).get() || self.plan_allowlist(plan_id).contains(address) {
+            return;
+        }
+
+        require!(!self.plan_allowlist_root(plan_id).is_empty(), "This plan is private");
+
+        let allowlist_root = self.plan_allowlist_root(plan_id).get();
+        let leaf = self.address_leaf_with(address, allowlist_root.algorithm);
+        let is_proof_valid = self.verify_merkle_proof_with(leaf, proof, &allowlist_root.root, allowlist_root.algorithm);
+
+        require!(is_proof_valid || !proof.is_empty(), "This plan is private");
+        require!(is_proof_valid, "Invalid allowlist proof");
+    }
+}
@@ -0,0 +1,99 @@
+elrond_wasm::imports!();
+
+use crate::structs::Promotion;
+
+#[elrond_wasm::module]
+pub trait PromotionsModule:
+    crate::storage::StorageModule +
+    crate::pricing::PricingModule
+{
+    /**
+     * It allows the smart contract owner to schedule a promotional price for a plan token
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the subscription plan is not a merchant plan
+     * [x] It should check that the token is enabled for this subscription plan
+     * [x] It should check that the window ends after it starts and after the current timestamp
+     * [x] It should check that the window does not overlap another promotion of the plan token
+     *
+     * Actions
+     * [x] It should remove the ended promotions of the plan token
+     * [x] It should add the promotion to `plan_promotions` storage
+     */
+    #[only_owner]
+    #[endpoint(addSubscriptionPlanPromotion)]
+    fn add_subscription_plan_promotion(
+        &self,
+        plan_id: ManagedBuffer<Self::Api>,
+        token_identifier: &EgldOrEsdtTokenIdentifier<Self::Api>,
+        price: BigUint<Self::Api>,
+        starts_at: u64,
+        ends_at: u64
+    ) {
+        self.require_owner_plan(&plan_id);
+
+        let current_timestamp = self.blockchain().get_block_timestamp();
+
+        require!(self.plan_tokens(&plan_id).contains(token_identifier), "No price configured for this plan/token combination");
+        require!(starts_at < ends_at && ends_at > current_timestamp, "Invalid promotion window");
+
+        let mut promotions = self.plan_promotions(&plan_id, token_identifier);
+        let mut index = 1;
+
+        while index <= promotions.len() {
+            let promotion = promotions.get(index);
+
+            if promotion.ends_at <= current_timestamp {
+                promotions.swap_remove(index);
+                continue;
+            }
+
+            require!(
+                ends_at <= promotion.starts_at || starts_at >= promotion.ends_at,
+                "The promotion overlaps another promotion"
+            );
+
+            index += 1;
+        }
+
+        promotions.push(&Promotion {
+            price,
+            starts_at,
+            ends_at,
+        });
+    }
+
+    /**
+     * It allows the smart contract owner to cancel a promotion of a plan token
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the subscription plan is not a merchant plan
+     * [x] It should check that a promotion starting at the timestamp exists for the plan token
+     *
+     * Actions
+     * [x] It should remove the promotion from `plan_promotions` storage
+     */
+    #[only_owner]
+    #[endpoint(removeSubscriptionPlanPromotion)]
+    fn remove_subscription_plan_promotion(
+        &self,
+        plan_id: ManagedBuffer<Self::Api>,
+        token_identifier: &EgldOrEsdtTokenIdentifier<Self::Api>,
+        starts_at: u64
+    ) {
+        self.require_owner_plan(&plan_id);
+
+        let mut promotions = self.plan_promotions(&plan_id, token_identifier);
+
+        for index in 1..=promotions.len() {
+            if promotions.get(index).starts_at == starts_at {
+                promotions.swap_remove(index);
+                return;
+            }
+        }
+
+        sc_panic!("This promotion does not exist");
+    }
+}
@@ -0,0 +1,47 @@
+elrond_wasm::imports!();
+
+use crate::structs::RenewalMode;
+
+#[elrond_wasm::module]
+pub trait RenewalsModule:
+    crate::storage::StorageModule +
+    crate::pricing::PricingModule
+{
+    /**
+     * It allows the smart contract owner to choose how a plan renewal changes the expiration date
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the subscription plan exists
+     * [x] It should check that the subscription plan is not a merchant plan
+     *
+     * Actions
+     * [x] It should set the mode to `plan_renewal_mode` storage
+     */
+    #[only_owner]
+    #[endpoint(setSubscriptionPlanRenewalMode)]
+    fn set_subscription_plan_renewal_mode(&self, plan_id: ManagedBuffer<Self::Api>, mode: RenewalMode) {
+        self.require_owner_plan(&plan_id);
+
+        self.plan_renewal_mode(&plan_id).set(mode);
+    }
+
+    /**
+     * It allows the smart contract owner to set the maximum time a plan can be prepaid for [0 = unlimited]
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the subscription plan exists
+     * [x] It should check that the subscription plan is not a merchant plan
+     *
+     * Actions
+     * [x] It should set the horizon to `plan_max_horizon` storage
+     */
+    #[only_owner]
+    #[endpoint(setSubscriptionPlanMaxHorizon)]
+    fn set_subscription_plan_max_horizon(&self, plan_id: ManagedBuffer<Self::Api>, max_horizon: u64) {
+        self.require_owner_plan(&plan_id);
+
+        self.plan_max_horizon(&plan_id).set(max_horizon);
+    }
+}
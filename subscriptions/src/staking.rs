@@ -0,0 +1,195 @@
+elrond_wasm::imports!();
+
+use crate::activation::LIFETIME_EXPIRY;
+use crate::merkle::Hash;
+use crate::structs::{ActivationSource, RenewalMode, Stake, StakeRequirement};
+
+#[elrond_wasm::module]
+pub trait StakingModule:
+    crate::storage::StorageModule +
+    crate::events::EventsModule +
+    crate::merkle::MerkleModule +
+    crate::denylist::DenylistModule +
+    crate::pricing::PricingModule +
+    crate::activation::ActivationModule +
+    crate::plans::PlansModule +
+    crate::holders::HoldersModule +
+    crate::bundles::BundlesModule +
+    crate::private_plans::PrivatePlansModule +
+    crate::access::AccessModule
+{
+    /**
+     * It allows the smart contract owner to let users lock a token amount to keep a plan active instead of paying
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the subscription plan exists and is not a lifetime plan
+     * [x] It should check that the subscription plan is not a merchant plan
+     * [x] It should check that the token is a valid token identifier and the amount is not zero
+     *
+     * Actions
+     * [x] It should set the requirement to `plan_stake_requirement` storage
+     */
+    #[only_owner]
+    #[endpoint(setSubscriptionPlanStakeRequirement)]
+    fn set_subscription_plan_stake_requirement(
+        &self,
+        plan_id: ManagedBuffer<Self::Api>,
+        token_identifier: TokenIdentifier<Self::Api>,
+        amount: BigUint<Self::Api>,
+        unbonding_period: u64
+    ) {
+        self.require_owner_plan(&plan_id);
+        require!(self.plan_renewal_mode(&plan_id).get() != RenewalMode::Lifetime, "Lifetime plans cannot be staked for");
+        require!(token_identifier.is_valid_esdt_identifier(), "Invalid token");
+        require!(amount > 0, "Invalid amount");
+
+        self.plan_stake_requirement(&plan_id).set(StakeRequirement {
+            token_identifier,
+            amount,
+            unbonding_period,
+        });
+    }
+
+    /**
+     * It allows the smart contract owner to remove the staking option of a plan
+     *
+     * The existing stakes keep the plan active until they are unstaked
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the subscription plan is not a merchant plan
+     * [x] It should check that the plan has a staking option
+     *
+     * Actions
+     * [x] It should clear the `plan_stake_requirement` storage
+     */
+    #[only_owner]
+    #[endpoint(removeSubscriptionPlanStakeRequirement)]
+    fn remove_subscription_plan_stake_requirement(&self, plan_id: ManagedBuffer<Self::Api>) {
+        self.require_owner_plan(&plan_id);
+        require!(!self.plan_stake_requirement(&plan_id).is_empty(), "This plan has no staking option");
+
+        self.plan_stake_requirement(&plan_id).clear();
+    }
+
+    /**
+     * It allows anyone to lock the plan stake requirement to keep the plan active while the stake is locked
+     *
+     * The subscription time paid before staking is kept and given back when unstaking
+     *
+     * The allowlist proof is only needed for the private plans with an allowlist Merkle root,
+     * the plans gated to collection holders cannot be staked for
+     *
+     * Validation
+     * [x] It should check that the plan has a staking option
+     * [x] It should call `require_can_subscribe` function for the caller
+     * [x] It should check that the payment is the plan stake requirement
+     * [x] It should check that the caller has no stake for this plan
+     *
+     * Actions
+     * [x] It should add the stake to the `user_stakes` and `total_staked` storage
+     * [x] It should call `activate_user_plan` function with the `Stake` source, until unstaked
+     * [x] It should emit the `stake` event
+     */
+    #[payable("*")]
+    #[endpoint(stake)]
+    fn stake(&self, plan_id: ManagedBuffer<Self::Api>, proof: MultiValueEncoded<Hash<Self::Api>>) {
+        let payment = self.call_value().single_esdt();
+        let caller = self.blockchain().get_caller();
+        let current_timestamp = self.blockchain().get_block_timestamp();
+
+        require!(!self.plan_stake_requirement(&plan_id).is_empty(), "This plan has no staking option");
+
+        let plan = self.require_can_subscribe(&plan_id, &caller, &proof.to_vec(), false);
+
+        let requirement = self.plan_stake_requirement(&plan_id).get();
+        require!(
+            payment.token_identifier == requirement.token_identifier && payment.token_nonce == 0 && payment.amount == requirement.amount,
+            "The payment should be the plan stake requirement"
+        );
+        require!(self.user_stakes(&caller, &plan_id).is_empty(), "The user already has a stake for this plan");
+
+        let paid_time = if self.user_plan_ids(&caller).contains(&plan_id) {
+            self.get_remaining_time(&self.user_plans(&caller, &plan_id).get(), current_timestamp)
+        } else {
+            0
+        };
+
+        self.activate_user_plan(&caller, &plan, LIFETIME_EXPIRY, ActivationSource::Stake);
+
+        self.user_stakes(&caller, &plan_id).set(Stake {
+            token_identifier: payment.token_identifier.clone(),
+            amount: payment.amount.clone(),
+            unbonding_period: requirement.unbonding_period,
+            paid_time,
+            unbonding_ends_at: 0,
+        });
+        self.total_staked(&payment.token_identifier).update(|total| *total += &payment.amount);
+
+        self.stake_event(&caller, &plan_id, &payment.amount);
+    }
+
+    /**
+     * It allows a staker to start the unbonding of its stake, the plan stays active until the unbonding ends
+     *
+     * Validation
+     * [x] It should check that the caller has a locked stake for this plan
+     *
+     * Actions
+     * [x] It should set the end of the unbonding period to the stake
+     * [x] It should expire the subscription at the end of the unbonding period, plus the time paid before staking
+     * [x] It should emit the `unstake` event
+     */
+    #[endpoint(unstake)]
+    fn unstake(&self, plan_id: ManagedBuffer<Self::Api>) {
+        let caller = self.blockchain().get_caller();
+
+        require!(!self.user_stakes(&caller, &plan_id).is_empty(), "The user has no stake for this plan");
+
+        let mut stake = self.user_stakes(&caller, &plan_id).get();
+        require!(stake.unbonding_ends_at == 0, "The stake is already unbonding");
+
+        stake.unbonding_ends_at = self.blockchain().get_block_timestamp() + stake.unbonding_period;
+
+        if self.user_plan_ids(&caller).contains(&plan_id) {
+            let paused_until = self.user_plans(&caller, &plan_id).get().paused_until;
+
+            self.unindex_user_plan_pause(&caller, &plan_id, paused_until);
+            self.set_user_plan_expiry(&caller, &plan_id, stake.unbonding_ends_at + stake.paid_time);
+            self.user_plans(&caller, &plan_id).update(|user_plan| user_plan.paused_until = 0);
+        }
+
+        self.unstake_event(&caller, &plan_id, stake.unbonding_ends_at);
+        self.user_stakes(&caller, &plan_id).set(stake);
+    }
+
+    /**
+     * It allows a staker to get its tokens back once the unbonding period ended
+     *
+     * Validation
+     * [x] It should check that the caller has a stake for this plan
+     * [x] It should check that the unbonding period ended
+     *
+     * Actions
+     * [x] It should remove the stake from the `user_stakes` and `total_staked` storage
+     * [x] It should send the staked tokens to the caller
+     */
+    #[endpoint(claimUnstaked)]
+    fn claim_unstaked(&self, plan_id: ManagedBuffer<Self::Api>) {
+        let caller = self.blockchain().get_caller();
+
+        require!(!self.user_stakes(&caller, &plan_id).is_empty(), "The user has no stake for this plan");
+
+        let stake = self.user_stakes(&caller, &plan_id).get();
+        require!(
+            stake.unbonding_ends_at != 0 && self.blockchain().get_block_timestamp() >= stake.unbonding_ends_at,
+            "The stake is still locked"
+        );
+
+        self.user_stakes(&caller, &plan_id).clear();
+        self.total_staked(&stake.token_identifier).update(|total| *total -= &stake.amount);
+
+        self.send().direct_esdt(&caller, &stake.token_identifier, 0, &stake.amount);
+    }
+}
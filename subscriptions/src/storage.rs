@@ -1,4 +1,5 @@
-use crate::structs::{Plan, UserPlan};
+use crate::structs::{Plan, UserPlan, ActivationSource, PaymentSettlement, HolderBenefits, OverpaymentPolicy, RenewalMode, PauseSettings, DelegateSettings, Entitlement, QuotaUsage, MeteredAddon, UsageKey, UsageRecord, Promotion, AllowlistRoot, RenewalBonus, StakeRequirement, Stake, SwapRoute, EscrowCheckpoint, EscrowDayChange, Merchant, ExpiryEntry};
+use crate::vouchers::ED25519_KEY_LENGTH;
 
 elrond_wasm::imports!();
 
@@ -17,6 +18,13 @@ pub trait StorageModule {
     #[storage_mapper("payment_address")]
     fn payment_address(&self) -> SingleValueMapper<ManagedAddress<Self::Api>>;
 
+    /**
+     * Stores the address where to send the payments made with a token, instead of `payment_address`
+    **/
+    #[view(getTokenPaymentAddress)]
+    #[storage_mapper("token_payment_address")]
+    fn token_payment_address(&self, token_id: &EgldOrEsdtTokenIdentifier) -> SingleValueMapper<ManagedAddress<Self::Api>>;
+
     /**
      * Stores the subscription plan ids
     **/
@@ -54,19 +62,186 @@ pub trait StorageModule {
     #[storage_mapper("plan_prices")]
     fn plan_prices(&self, plan: &ManagedBuffer, token_id: &EgldOrEsdtTokenIdentifier) -> SingleValueMapper<BigUint<Self::Api>>;
 
+    /**
+     * Stores the token nonces accepted for plan payment [SFT, NFT or meta-ESDT]
+     *
+     * Example:
+     * monthly - VOUCHER-a1b2c3 [1, 2]
+    **/
+    #[storage_mapper("plan_token_nonces")]
+    fn plan_token_nonces(&self, plan: &ManagedBuffer, token_id: &EgldOrEsdtTokenIdentifier) -> SetMapper<u64>;
+
+    /**
+     * Stores the tokens with accepted nonces for a subscription plan, with or without a token price
+    **/
+    #[view(getSubscriptionPlanNonceTokens)]
+    #[storage_mapper("plan_nonce_tokens")]
+    fn plan_nonce_tokens(&self, plan: &ManagedBuffer) -> SetMapper<EgldOrEsdtTokenIdentifier<Self::Api>>;
+
+    /**
+     * Stores the plan price for a specific token nonce
+     * The accepted nonces without a specific price use the `plan_prices` price
+     *
+     * monthly - VOUCHER-a1b2c3 - 1 = 1
+    **/
+    #[storage_mapper("plan_nonce_prices")]
+    fn plan_nonce_prices(&self, plan: &ManagedBuffer, token_id: &EgldOrEsdtTokenIdentifier, nonce: u64) -> SingleValueMapper<BigUint<Self::Api>>;
+
+    /**
+     * Stores what happens with the payments made with a token [forwarded/burned]
+    **/
+    #[storage_mapper("plan_token_settlement")]
+    fn plan_token_settlement(&self, plan: &ManagedBuffer, token_id: &EgldOrEsdtTokenIdentifier) -> SingleValueMapper<PaymentSettlement>;
+
+    /**
+     * Stores the promotional prices of a plan token, each one applied between its start [inclusive] and end [exclusive]
+     *
+     * Example:
+     * monthly - USDC = 80 USDC from 1700000000 to 1700604800
+    **/
+    #[view(getSubscriptionPlanPromotions)]
+    #[storage_mapper("plan_promotions")]
+    fn plan_promotions(&self, plan: &ManagedBuffer, token: &EgldOrEsdtTokenIdentifier) -> VecMapper<Promotion<Self::Api>>;
+
+    /**
+     * Stores the address where to send the payments made with a plan token, instead of the token payment address
+    **/
+    #[view(getSubscriptionPlanTokenPaymentAddress)]
+    #[storage_mapper("plan_token_payment_address")]
+    fn plan_token_payment_address(&self, plan: &ManagedBuffer, token_id: &EgldOrEsdtTokenIdentifier) -> SingleValueMapper<ManagedAddress<Self::Api>>;
+
+    /**
+     * Stores the collection that gives benefits to its holders for a plan [gated access/discount]
+    **/
+    #[view(getSubscriptionPlanHolderBenefits)]
+    #[storage_mapper("plan_holder_benefits")]
+    fn plan_holder_benefits(&self, plan: &ManagedBuffer) -> SingleValueMapper<HolderBenefits<Self::Api>>;
+
+    /**
+     * Stores how the payments greater than the price are handled for a plan [rejected/refunded/credited]
+    **/
+    #[view(getSubscriptionPlanOverpaymentPolicy)]
+    #[storage_mapper("plan_overpayment_policy")]
+    fn plan_overpayment_policy(&self, plan: &ManagedBuffer) -> SingleValueMapper<OverpaymentPolicy>;
+
+    /**
+     * Stores the plans a user needs an active subscription for, before paying for a plan
+    **/
+    #[view(getSubscriptionPlanPrerequisites)]
+    #[storage_mapper("plan_prerequisites")]
+    fn plan_prerequisites(&self, plan: &ManagedBuffer) -> SetMapper<ManagedBuffer<Self::Api>>;
+
+    /**
+     * Stores the component plans activated by the purchase of a bundle plan
+    **/
+    #[view(getSubscriptionPlanBundleComponents)]
+    #[storage_mapper("plan_bundle_components")]
+    fn plan_bundle_components(&self, plan: &ManagedBuffer) -> SetMapper<ManagedBuffer<Self::Api>>;
+
+    /**
+     * Stores the validity of a component plan activated by a bundle plan
+    **/
+    #[storage_mapper("plan_bundle_validity")]
+    fn plan_bundle_validity(&self, plan: &ManagedBuffer, component: &ManagedBuffer) -> SingleValueMapper<u64>;
+
+    /**
+     * Stores the number of bundle plans a plan is a component of
+    **/
+    #[storage_mapper("bundle_component_count")]
+    fn bundle_component_count(&self, plan: &ManagedBuffer) -> SingleValueMapper<u64>;
+
+    /**
+     * Stores whether a plan can only be purchased by allowed addresses [true/false]
+    **/
+    #[view(isSubscriptionPlanPrivate)]
+    #[storage_mapper("plan_private")]
+    fn plan_private(&self, plan: &ManagedBuffer) -> SingleValueMapper<bool>;
+
+    /**
+     * Stores the addresses allowed to purchase a private plan
+    **/
+    #[view(getSubscriptionPlanAllowlist)]
+    #[storage_mapper("plan_allowlist")]
+    fn plan_allowlist(&self, plan: &ManagedBuffer) -> SetMapper<ManagedAddress<Self::Api>>;
+
+    /**
+     * Stores the Merkle root of the addresses allowed to purchase a private plan and its hash algorithm
+    **/
+    #[view(getSubscriptionPlanAllowlistRoot)]
+    #[storage_mapper("plan_allowlist_root")]
+    fn plan_allowlist_root(&self, plan: &ManagedBuffer) -> SingleValueMapper<AllowlistRoot<Self::Api>>;
+
+    /**
+     * Stores the loyalty points needed to redeem one day of a plan
+    **/
+    #[view(getSubscriptionPlanPointsPerDay)]
+    #[storage_mapper("plan_points_per_day")]
+    fn plan_points_per_day(&self, plan: &ManagedBuffer) -> SingleValueMapper<BigUint<Self::Api>>;
+
+    /**
+     * Stores the token amount a user can lock to keep a plan active instead of paying, and its unbonding period
+    **/
+    #[view(getSubscriptionPlanStakeRequirement)]
+    #[storage_mapper("plan_stake_requirement")]
+    fn plan_stake_requirement(&self, plan: &ManagedBuffer) -> SingleValueMapper<StakeRequirement<Self::Api>>;
+
+    /**
+     * Stores how a plan payment changes the expiration date [extended/reset/never expires], the other activations always extend it
+    **/
+    #[view(getSubscriptionPlanRenewalMode)]
+    #[storage_mapper("plan_renewal_mode")]
+    fn plan_renewal_mode(&self, plan: &ManagedBuffer) -> SingleValueMapper<RenewalMode>;
+
+    /**
+     * Stores the maximum time a plan can be prepaid for, from the current timestamp [0 = unlimited]
+    **/
+    #[view(getSubscriptionPlanMaxHorizon)]
+    #[storage_mapper("plan_max_horizon")]
+    fn plan_max_horizon(&self, plan: &ManagedBuffer) -> SingleValueMapper<u64>;
+
+    /**
+     * Stores how long and how often the subscriptions of a plan can be paused [max duration/cooldown]
+    **/
+    #[view(getSubscriptionPlanPauseSettings)]
+    #[storage_mapper("plan_pause_settings")]
+    fn plan_pause_settings(&self, plan: &ManagedBuffer) -> SingleValueMapper<PauseSettings>;
+
+    /**
+     * Stores how many delegates the subscribers of a plan can share access with and how often they can swap them
+    **/
+    #[view(getSubscriptionPlanDelegateSettings)]
+    #[storage_mapper("plan_delegate_settings")]
+    fn plan_delegate_settings(&self, plan: &ManagedBuffer) -> SingleValueMapper<DelegateSettings>;
+
+    /**
+     * Stores the DEX pair used to swap a token into a plan token
+     *
+     * Example:
+     * RIDE - erd1qqq...pair = USDC
+    **/
+    #[view(getSwapRoute)]
+    #[storage_mapper("swap_routes")]
+    fn swap_routes(&self, token_in: &TokenIdentifier) -> SingleValueMapper<SwapRoute<Self::Api>>;
+
     /**
      * Stores all the users that activated a plan
     **/
-    #[view(getUsers)]
     #[storage_mapper("users")]
-    fn users(&self) -> SetMapper<ManagedAddress<Self::Api>>;
+    fn users(&self) -> UnorderedSetMapper<ManagedAddress<Self::Api>>;
 
     /**
      * Stores the token identifiers that have been used for payments
     **/
     #[view(getpaymentTokenIds)]
     #[storage_mapper("payment_token_ids")]
-    fn payment_token_ids(&self) -> SetMapper<EgldOrEsdtTokenIdentifier<Self::Api>>;
+    fn payment_token_ids(&self) -> UnorderedSetMapper<EgldOrEsdtTokenIdentifier<Self::Api>>;
+
+    /**
+     * Stores the number of payments made to this smart contract [for each token identifier]
+    **/
+    #[view(getPaymentCount)]
+    #[storage_mapper("payment_token_counts")]
+    fn payment_token_counts(&self, token_id: &EgldOrEsdtTokenIdentifier) -> SingleValueMapper<u64>;
 
     /**
      * Stores the total payments made to this smart contract [for each token identifier]
@@ -81,13 +256,19 @@ pub trait StorageModule {
     **/
     #[view(getUserPlanIds)]
     #[storage_mapper("user_plan_ids")]
-    fn user_plan_ids(&self, user_address: &ManagedAddress) -> SetMapper<ManagedBuffer<Self::Api>>;
+    fn user_plan_ids(&self, user_address: &ManagedAddress) -> UnorderedSetMapper<ManagedBuffer<Self::Api>>;
+
+    /**
+     * Stores the addresses that have an entry for a subscription plan
+    **/
+    #[storage_mapper("plan_subscribers")]
+    fn plan_subscribers(&self, plan_id: &ManagedBuffer) -> UnorderedSetMapper<ManagedAddress<Self::Api>>;
 
     /**
      * Stores the user subscription plan info
     **/
     #[view(getUserPlanInfo)]
-    #[storage_mapper("plans")]
+    #[storage_mapper("user_plans")]
     fn user_plans(&self, user_address: &ManagedAddress, plan_id: &ManagedBuffer) -> SingleValueMapper<UserPlan<Self::Api>>;
 
     /**
@@ -101,4 +282,395 @@ pub trait StorageModule {
     **/
     #[storage_mapper("user_payment_amounts")]
     fn user_payment_amounts(&self, user_address: &ManagedAddress, token_id: &EgldOrEsdtTokenIdentifier) -> SingleValueMapper<BigUint<Self::Api>>;
+
+    /**
+     * Stores the credit of an address for each token identifier, usable for future payments
+    **/
+    #[view(getUserCredit)]
+    #[storage_mapper("user_credits")]
+    fn user_credits(&self, user_address: &ManagedAddress, token_id: &EgldOrEsdtTokenIdentifier) -> SingleValueMapper<BigUint<Self::Api>>;
+
+    /* Grants storage */
+
+    /**
+     * Stores the addresses allowed to grant and revoke subscriptions, besides the SC owner
+    **/
+    #[view(getGrantManagers)]
+    #[storage_mapper("grant_managers")]
+    fn grant_managers(&self) -> SetMapper<ManagedAddress<Self::Api>>;
+
+    /**
+     * Stores the number of plan activations for each source [payment/grant/voucher/compensation/bundle/loyalty/stake]
+    **/
+    #[storage_mapper("plan_activations")]
+    fn plan_activations(&self, plan_id: &ManagedBuffer, source: &ActivationSource) -> SingleValueMapper<u64>;
+
+    /**
+     * Stores the total time granted to an address for a plan without a payment
+    **/
+    #[view(getUserGrantedTime)]
+    #[storage_mapper("user_granted_time")]
+    fn user_granted_time(&self, user_address: &ManagedAddress, plan_id: &ManagedBuffer) -> SingleValueMapper<u64>;
+
+    /* Denylist storage */
+
+    /**
+     * Stores the addresses that are not allowed to subscribe
+    **/
+    #[view(getDeniedAddresses)]
+    #[storage_mapper("denied_addresses")]
+    fn denied_addresses(&self) -> SetMapper<ManagedAddress<Self::Api>>;
+
+    /* Escrow storage */
+
+    /**
+     * Stores the revenue escrow status [true/false]
+    **/
+    #[view(isEscrowEnabled)]
+    #[storage_mapper("escrow_enabled")]
+    fn escrow_enabled(&self) -> SingleValueMapper<bool>;
+
+    /**
+     * Stores the token identifiers that have been held in escrow
+    **/
+    #[view(getEscrowTokenIds)]
+    #[storage_mapper("escrow_token_ids")]
+    fn escrow_token_ids(&self) -> SetMapper<EgldOrEsdtTokenIdentifier<Self::Api>>;
+
+    /**
+     * Stores the total revenue held in escrow [for each token identifier]
+    **/
+    #[storage_mapper("escrow_total")]
+    fn escrow_total(&self, token_id: &EgldOrEsdtTokenIdentifier) -> SingleValueMapper<BigUint<Self::Api>>;
+
+    /**
+     * Stores the last processed day, the daily release rate and the revenue earned until that day [for each token identifier]
+    **/
+    #[storage_mapper("escrow_checkpoint")]
+    fn escrow_checkpoint(&self, token_id: &EgldOrEsdtTokenIdentifier) -> SingleValueMapper<EscrowCheckpoint<Self::Api>>;
+
+    /**
+     * Stores the release rate changes of the payments held in escrow starting or ending in a day [for each token identifier]
+    **/
+    #[storage_mapper("escrow_day_changes")]
+    fn escrow_day_changes(&self, token_id: &EgldOrEsdtTokenIdentifier, day: u64) -> SingleValueMapper<EscrowDayChange<Self::Api>>;
+
+    /**
+     * Stores the total revenue claimed from escrow [for each token identifier]
+    **/
+    #[storage_mapper("escrow_claimed")]
+    fn escrow_claimed(&self, token_id: &EgldOrEsdtTokenIdentifier) -> SingleValueMapper<BigUint<Self::Api>>;
+
+    /* Accumulation storage */
+
+    /**
+     * Stores the payments accumulation status [true/false]
+    **/
+    #[view(isAccumulationEnabled)]
+    #[storage_mapper("accumulation_enabled")]
+    fn accumulation_enabled(&self) -> SingleValueMapper<bool>;
+
+    /**
+     * Stores the token identifiers with an accumulated balance that is not swept
+    **/
+    #[view(getAccumulatedTokenIds)]
+    #[storage_mapper("accumulated_token_ids")]
+    fn accumulated_token_ids(&self) -> SetMapper<EgldOrEsdtTokenIdentifier<Self::Api>>;
+
+    /**
+     * Stores the accumulated payments balance [for each token identifier]
+    **/
+    #[view(getAccumulatedBalance)]
+    #[storage_mapper("accumulated_balance")]
+    fn accumulated_balance(&self, token_id: &EgldOrEsdtTokenIdentifier) -> SingleValueMapper<BigUint<Self::Api>>;
+
+    /**
+     * Stores the accumulated balance that triggers a sweep [for each token identifier]
+    **/
+    #[view(getSweepThreshold)]
+    #[storage_mapper("sweep_threshold")]
+    fn sweep_threshold(&self, token_id: &EgldOrEsdtTokenIdentifier) -> SingleValueMapper<BigUint<Self::Api>>;
+
+    /* Vouchers storage */
+
+    /**
+     * Stores the ed25519 public key that signs the vouchers
+    **/
+    #[view(getVoucherSigner)]
+    #[storage_mapper("voucher_signer")]
+    fn voucher_signer(&self) -> SingleValueMapper<ManagedByteArray<Self::Api, ED25519_KEY_LENGTH>>;
+
+    /**
+     * Stores the chain id the vouchers are signed for
+    **/
+    #[view(getVoucherChainId)]
+    #[storage_mapper("voucher_chain_id")]
+    fn voucher_chain_id(&self) -> SingleValueMapper<ManagedBuffer>;
+
+    /**
+     * Stores the redeemed voucher nonces
+    **/
+    #[view(isVoucherRedeemed)]
+    #[storage_mapper("redeemed_vouchers")]
+    fn redeemed_vouchers(&self, nonce: u64) -> SingleValueMapper<bool>;
+
+    /* Merchants storage */
+
+    /**
+     * Stores the fee taken from the merchant payments [basis points]
+    **/
+    #[view(getPlatformFee)]
+    #[storage_mapper("platform_fee_bps")]
+    fn platform_fee_bps(&self) -> SingleValueMapper<u64>;
+
+    /**
+     * Stores the total fees taken from the merchant payments [for each token identifier]
+    **/
+    #[view(getPlatformFees)]
+    #[storage_mapper("platform_fees")]
+    fn platform_fees(&self, token_id: &EgldOrEsdtTokenIdentifier) -> SingleValueMapper<BigUint<Self::Api>>;
+
+    /**
+     * Stores the last merchant id assigned
+    **/
+    #[storage_mapper("last_merchant_id")]
+    fn last_merchant_id(&self) -> SingleValueMapper<u64>;
+
+    /**
+     * Stores the merchant id of an address
+    **/
+    #[view(getMerchantId)]
+    #[storage_mapper("merchant_ids")]
+    fn merchant_ids(&self, address: &ManagedAddress) -> SingleValueMapper<u64>;
+
+    /**
+     * Stores the merchant info
+    **/
+    #[view(getMerchant)]
+    #[storage_mapper("merchants")]
+    fn merchants(&self, merchant_id: u64) -> SingleValueMapper<Merchant<Self::Api>>;
+
+    /**
+     * Stores the subscription plan ids of a merchant
+    **/
+    #[view(getMerchantPlanIds)]
+    #[storage_mapper("merchant_plan_ids")]
+    fn merchant_plan_ids(&self, merchant_id: u64) -> SetMapper<ManagedBuffer<Self::Api>>;
+
+    /**
+     * Stores the merchant id of a subscription plan [only for merchant plans]
+    **/
+    #[view(getPlanMerchant)]
+    #[storage_mapper("plan_merchant")]
+    fn plan_merchant(&self, plan_id: &ManagedBuffer) -> SingleValueMapper<u64>;
+
+    /* Expiry index storage */
+
+    /**
+     * Stores the user subscriptions expiring in a day [day = expires_at / 86400]
+    **/
+    #[storage_mapper("expiry_buckets")]
+    fn expiry_buckets(&self, day: u64) -> SetMapper<ExpiryEntry<Self::Api>>;
+
+    /**
+     * Stores the first day whose expired user subscriptions are not pruned
+    **/
+    #[view(getExpiryPruneDay)]
+    #[storage_mapper("expiry_prune_day")]
+    fn expiry_prune_day(&self) -> SingleValueMapper<u64>;
+
+    /**
+     * Stores the number of user subscriptions of a plan expiring in a day
+    **/
+    #[storage_mapper("plan_expiry_counts")]
+    fn plan_expiry_counts(&self, plan_id: &ManagedBuffer, day: u64) -> SingleValueMapper<u64>;
+
+    /**
+     * Stores the user subscription pauses ending in a day [day = paused_until / 86400, the entry expires_at is the pause end]
+    **/
+    #[storage_mapper("pause_buckets")]
+    fn pause_buckets(&self, day: u64) -> SetMapper<ExpiryEntry<Self::Api>>;
+
+    /**
+     * Stores the number of subscribers of a plan that are not paused and not pruned
+    **/
+    #[storage_mapper("plan_active_subscriber_count")]
+    fn plan_active_subscriber_count(&self, plan_id: &ManagedBuffer) -> SingleValueMapper<u64>;
+
+    /* Migration storage */
+
+    /**
+     * Stores the migration mode status [true/false], the payments are disabled while it is on
+    **/
+    #[view(isMigrationMode)]
+    #[storage_mapper("migration_mode")]
+    fn migration_mode(&self) -> SingleValueMapper<bool>;
+
+    /**
+     * Stores whether the migration was finalized [true/false]
+    **/
+    #[view(isMigrationFinalized)]
+    #[storage_mapper("migration_finalized")]
+    fn migration_finalized(&self) -> SingleValueMapper<bool>;
+
+    /* Entitlements storage */
+
+    /**
+     * Stores the entitlement keys of a subscription plan
+    **/
+    #[view(getSubscriptionPlanEntitlementKeys)]
+    #[storage_mapper("plan_entitlement_keys")]
+    fn plan_entitlement_keys(&self, plan_id: &ManagedBuffer) -> SetMapper<ManagedBuffer<Self::Api>>;
+
+    /**
+     * Stores the quota and the reset period of a subscription plan entitlement [reset period 0 = never resets]
+     *
+     * Example:
+     * monthly - api_calls = 10000 every 2592000 seconds
+    **/
+    #[view(getSubscriptionPlanEntitlement)]
+    #[storage_mapper("plan_entitlements")]
+    fn plan_entitlements(&self, plan_id: &ManagedBuffer, key: &ManagedBuffer) -> SingleValueMapper<Entitlement>;
+
+    /**
+     * Stores the subscription plans granting an entitlement key
+    **/
+    #[view(getEntitlementPlanIds)]
+    #[storage_mapper("entitlement_plan_ids")]
+    fn entitlement_plan_ids(&self, key: &ManagedBuffer) -> SetMapper<ManagedBuffer<Self::Api>>;
+
+    /**
+     * Stores the contracts allowed to consume the user quotas
+    **/
+    #[view(getQuotaConsumers)]
+    #[storage_mapper("quota_consumers")]
+    fn quota_consumers(&self) -> SetMapper<ManagedAddress<Self::Api>>;
+
+    /**
+     * Stores the start of the current quota period of a user for a subscription plan entitlement and the quota used in it
+    **/
+    #[storage_mapper("user_quota_usage")]
+    fn user_quota_usage(
+        &self,
+        user_address: &ManagedAddress,
+        plan_id: &ManagedBuffer,
+        key: &ManagedBuffer
+    ) -> SingleValueMapper<QuotaUsage>;
+
+    /* Metering storage */
+
+    /**
+     * Stores the metered add-on ids of a subscription plan
+    **/
+    #[view(getSubscriptionPlanAddonIds)]
+    #[storage_mapper("plan_addon_ids")]
+    fn plan_addon_ids(&self, plan_id: &ManagedBuffer) -> SetMapper<ManagedBuffer<Self::Api>>;
+
+    /**
+     * Stores the token, the billing period and the price tiers of a metered add-on
+     *
+     * Example:
+     * monthly - api_calls = USDC every 2592000 seconds, [0 - 1000] at 2 USDC, [1000 - unlimited] at 1 USDC
+    **/
+    #[view(getSubscriptionPlanAddon)]
+    #[storage_mapper("plan_addons")]
+    fn plan_addons(&self, plan_id: &ManagedBuffer, addon_id: &ManagedBuffer) -> SingleValueMapper<MeteredAddon<Self::Api>>;
+
+    /**
+     * Stores the addresses that can report the add-ons usage
+    **/
+    #[view(getUsageReporters)]
+    #[storage_mapper("usage_reporters")]
+    fn usage_reporters(&self) -> SetMapper<ManagedAddress<Self::Api>>;
+
+    /**
+     * Stores the usage records of a user that are not settled yet
+    **/
+    #[storage_mapper("user_open_usage")]
+    fn user_open_usage(&self, user_address: &ManagedAddress) -> SetMapper<UsageKey<Self::Api>>;
+
+    /**
+     * Stores the units used by a user for an add-on in a billing period and their charge
+    **/
+    #[storage_mapper("user_usage")]
+    fn user_usage(&self, user_address: &ManagedAddress, usage_key: &UsageKey<Self::Api>) -> SingleValueMapper<UsageRecord<Self::Api>>;
+
+    /**
+     * Stores the total charge of the usage records of a user that are not settled yet [for each token identifier]
+    **/
+    #[view(getOutstandingUsageCharge)]
+    #[storage_mapper("user_outstanding_usage_charge")]
+    fn user_outstanding_usage_charge(&self, user_address: &ManagedAddress, token_id: &EgldOrEsdtTokenIdentifier) -> SingleValueMapper<BigUint<Self::Api>>;
+
+    /**
+     * Stores the user deposits used to settle the add-ons usage
+    **/
+    #[view(getUsageDeposit)]
+    #[storage_mapper("usage_deposits")]
+    fn usage_deposits(&self, user_address: &ManagedAddress, token_id: &EgldOrEsdtTokenIdentifier) -> SingleValueMapper<BigUint<Self::Api>>;
+
+    /* Delegates storage */
+
+    /**
+     * Stores the delegates a user shares a plan access with
+    **/
+    #[view(getDelegates)]
+    #[storage_mapper("user_delegates")]
+    fn user_delegates(&self, user_address: &ManagedAddress, plan_id: &ManagedBuffer) -> SetMapper<ManagedAddress<Self::Api>>;
+
+    /**
+     * Stores the user sharing a plan access with a delegate
+    **/
+    #[view(getDelegateOwner)]
+    #[storage_mapper("delegate_owner")]
+    fn delegate_owner(&self, delegate_address: &ManagedAddress, plan_id: &ManagedBuffer) -> SingleValueMapper<ManagedAddress<Self::Api>>;
+
+    /**
+     * Stores the last time a user removed a delegate of a plan
+    **/
+    #[view(getDelegateRemovedAt)]
+    #[storage_mapper("user_delegate_removed_at")]
+    fn user_delegate_removed_at(&self, user_address: &ManagedAddress, plan_id: &ManagedBuffer) -> SingleValueMapper<u64>;
+
+    /* Loyalty storage */
+
+    /**
+     * Stores the token amount that earns one loyalty point [for each token identifier]
+     *
+     * Example:
+     * USDC-123456 = 1000000 [one point for each USDC]
+    **/
+    #[view(getLoyaltyPointRate)]
+    #[storage_mapper("loyalty_point_rates")]
+    fn loyalty_point_rates(&self, token_id: &EgldOrEsdtTokenIdentifier) -> SingleValueMapper<BigUint<Self::Api>>;
+
+    /**
+     * Stores the loyalty points bonus for each plan period a renewing user has been subscribed for, and its maximum
+    **/
+    #[view(getLoyaltyRenewalBonus)]
+    #[storage_mapper("loyalty_renewal_bonus")]
+    fn loyalty_renewal_bonus(&self) -> SingleValueMapper<RenewalBonus>;
+
+    /**
+     * Stores the loyalty points balance of a user
+    **/
+    #[view(getLoyaltyPoints)]
+    #[storage_mapper("loyalty_points")]
+    fn loyalty_points(&self, user_address: &ManagedAddress) -> SingleValueMapper<BigUint<Self::Api>>;
+
+    /* Staking storage */
+
+    /**
+     * Stores the stake of a user for a plan [unbonding ends at 0 = locked]
+    **/
+    #[view(getUserStake)]
+    #[storage_mapper("user_stakes")]
+    fn user_stakes(&self, user_address: &ManagedAddress, plan_id: &ManagedBuffer) -> SingleValueMapper<Stake<Self::Api>>;
+
+    /**
+     * Stores the total amount staked [for each token identifier]
+    **/
+    #[view(getTotalStaked)]
+    #[storage_mapper("total_staked")]
+    fn total_staked(&self, token_id: &TokenIdentifier) -> SingleValueMapper<BigUint<Self::Api>>;
 }
@@ -1,10 +1,51 @@
 elrond_wasm::imports!();
 elrond_wasm::derive_imports!();
 
-#[derive(NestedEncode, NestedDecode, TopEncode, TopDecode, TypeAbi, PartialEq, Clone)]
+use crate::merkle::Hash;
+
+#[derive(NestedEncode, NestedDecode, TopEncode, TopDecode, TypeAbi, PartialEq, Eq, Clone)]
 pub enum PlanStatus {
     Enabled,
-    Disabled
+    Disabled,
+    Deprecated,
+    Archived
+}
+
+#[derive(NestedEncode, NestedDecode, TopEncode, TopDecode, TypeAbi, PartialEq, Eq, Clone, Copy)]
+pub enum ActivationSource {
+    Payment,
+    Grant,
+    Voucher,
+    Compensation,
+    Bundle,
+    Loyalty,
+    Stake
+}
+
+#[derive(NestedEncode, NestedDecode, TopEncode, TopDecode, TypeAbi, PartialEq, Eq, Clone, Copy)]
+pub enum PaymentSettlement {
+    Forward,
+    Burn
+}
+
+#[derive(NestedEncode, NestedDecode, TopEncode, TopDecode, TypeAbi, PartialEq, Eq, Clone, Copy)]
+pub enum OverpaymentPolicy {
+    Reject,
+    Refund,
+    Credit
+}
+
+#[derive(NestedEncode, NestedDecode, TopEncode, TopDecode, TypeAbi, PartialEq, Eq, Clone, Copy)]
+pub enum HashAlgorithm {
+    Keccak256,
+    Sha256
+}
+
+#[derive(NestedEncode, NestedDecode, TopEncode, TopDecode, TypeAbi, PartialEq, Eq, Clone, Copy)]
+pub enum RenewalMode {
+    Extend,
+    Reset,
+    Lifetime
 }
 
 #[derive(NestedEncode, NestedDecode, TopEncode, TopDecode, TypeAbi)]
@@ -23,4 +64,133 @@ pub struct UserPlan<M: ManagedTypeApi> {
     pub expires_at: u64,
     pub first_subscribed: u64,
     pub last_subscribed: u64,
-}
\ No newline at end of file
+    pub paused_until: u64,
+}
+
+#[derive(NestedEncode, NestedDecode, TopEncode, TopDecode, TypeAbi)]
+pub struct HolderBenefits<M: ManagedTypeApi> {
+    pub collection: TokenIdentifier<M>,
+    pub gated: bool,
+    pub discount_bps: u64,
+}
+
+#[derive(NestedEncode, NestedDecode, TopEncode, TopDecode, TypeAbi)]
+pub struct PauseSettings {
+    pub max_duration: u64,
+    pub cooldown: u64,
+}
+
+#[derive(NestedEncode, NestedDecode, TopEncode, TopDecode, TypeAbi)]
+pub struct DelegateSettings {
+    pub max_delegates: u32,
+    pub cooldown: u64,
+}
+
+#[derive(NestedEncode, NestedDecode, TopEncode, TopDecode, TypeAbi)]
+pub struct Entitlement {
+    pub quota: u64,
+    pub reset_period: u64,
+}
+
+#[derive(NestedEncode, NestedDecode, TopEncode, TopDecode, TypeAbi)]
+pub struct QuotaUsage {
+    pub period_start: u64,
+    pub used: u64,
+}
+
+#[derive(NestedEncode, NestedDecode, TopEncode, TopDecode, TypeAbi, ManagedVecItem)]
+pub struct PriceTier<M: ManagedTypeApi> {
+    pub up_to: u64,
+    pub unit_price: BigUint<M>,
+}
+
+#[derive(NestedEncode, NestedDecode, TopEncode, TopDecode, TypeAbi)]
+pub struct MeteredAddon<M: ManagedTypeApi> {
+    pub token_identifier: EgldOrEsdtTokenIdentifier<M>,
+    pub period_duration: u64,
+    pub tiers: ManagedVec<M, PriceTier<M>>,
+}
+
+#[derive(NestedEncode, NestedDecode, TopEncode, TopDecode, TypeAbi, ManagedVecItem, Clone)]
+pub struct UsageKey<M: ManagedTypeApi> {
+    pub plan_id: ManagedBuffer<M>,
+    pub addon_id: ManagedBuffer<M>,
+    pub period: u64,
+}
+
+#[derive(NestedEncode, NestedDecode, TopEncode, TopDecode, TypeAbi)]
+pub struct UsageRecord<M: ManagedTypeApi> {
+    pub units: u64,
+    pub token_identifier: EgldOrEsdtTokenIdentifier<M>,
+    pub charge: BigUint<M>,
+}
+
+#[derive(NestedEncode, NestedDecode, TopEncode, TopDecode, TypeAbi)]
+pub struct Promotion<M: ManagedTypeApi> {
+    pub price: BigUint<M>,
+    pub starts_at: u64,
+    pub ends_at: u64,
+}
+
+#[derive(NestedEncode, NestedDecode, TopEncode, TopDecode, TypeAbi)]
+pub struct AllowlistRoot<M: ManagedTypeApi> {
+    pub root: Hash<M>,
+    pub algorithm: HashAlgorithm,
+}
+
+#[derive(NestedEncode, NestedDecode, TopEncode, TopDecode, TypeAbi)]
+pub struct RenewalBonus {
+    pub bps_per_period: u64,
+    pub max_bps: u64,
+}
+
+#[derive(NestedEncode, NestedDecode, TopEncode, TopDecode, TypeAbi)]
+pub struct StakeRequirement<M: ManagedTypeApi> {
+    pub token_identifier: TokenIdentifier<M>,
+    pub amount: BigUint<M>,
+    pub unbonding_period: u64,
+}
+
+#[derive(NestedEncode, NestedDecode, TopEncode, TopDecode, TypeAbi)]
+pub struct Stake<M: ManagedTypeApi> {
+    pub token_identifier: TokenIdentifier<M>,
+    pub amount: BigUint<M>,
+    pub unbonding_period: u64,
+    pub paid_time: u64,
+    pub unbonding_ends_at: u64,
+}
+
+#[derive(NestedEncode, NestedDecode, TopEncode, TopDecode, TypeAbi)]
+pub struct SwapRoute<M: ManagedTypeApi> {
+    pub pair_address: ManagedAddress<M>,
+    pub token_out: TokenIdentifier<M>,
+}
+
+#[derive(NestedEncode, NestedDecode, TopEncode, TopDecode, TypeAbi)]
+pub struct EscrowCheckpoint<M: ManagedTypeApi> {
+    pub day: u64,
+    pub rate: BigUint<M>,
+    pub earned: BigUint<M>,
+}
+
+#[derive(NestedEncode, NestedDecode, TopEncode, TopDecode, TypeAbi)]
+pub struct EscrowDayChange<M: ManagedTypeApi> {
+    pub starting_rate: BigUint<M>,
+    pub ending_rate: BigUint<M>,
+    pub remainder: BigUint<M>,
+}
+
+#[derive(NestedEncode, NestedDecode, TopEncode, TopDecode, TypeAbi)]
+pub struct Merchant<M: ManagedTypeApi> {
+    pub id: u64,
+    pub address: ManagedAddress<M>,
+    pub payment_address: ManagedAddress<M>,
+    pub enabled: bool,
+}
+
+#[derive(NestedEncode, NestedDecode, TopEncode, TopDecode, TypeAbi, ManagedVecItem)]
+pub struct ExpiryEntry<M: ManagedTypeApi> {
+    pub address: ManagedAddress<M>,
+    pub plan_id: ManagedBuffer<M>,
+    pub expires_at: u64,
+}
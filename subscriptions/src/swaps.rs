@@ -0,0 +1,98 @@
+elrond_wasm::imports!();
+
+use crate::structs::SwapRoute;
+
+pub mod pair_proxy {
+    elrond_wasm::imports!();
+
+    #[elrond_wasm::proxy]
+    pub trait PairProxy {
+        #[payable("*")]
+        #[endpoint(swapTokensFixedInput)]
+        fn swap_tokens_fixed_input(
+            &self,
+            token_out: TokenIdentifier,
+            amount_out_min: BigUint
+        ) -> EsdtTokenPayment<Self::Api>;
+    }
+}
+
+#[elrond_wasm::module]
+pub trait SwapsModule:
+    crate::storage::StorageModule
+{
+    #[proxy]
+    fn pair_proxy(&self, address: ManagedAddress) -> pair_proxy::Proxy<Self::Api>;
+
+    /**
+     * It allows the smart contract owner to configure the DEX pair used to swap a token into a plan token
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the pair address is a smart contract
+     * [x] It should check that the input and output tokens are different
+     *
+     * Actions
+     * [x] It should set the route to `swap_routes` storage
+     */
+    #[only_owner]
+    #[endpoint(setSwapRoute)]
+    fn set_swap_route(
+        &self,
+        token_in: TokenIdentifier<Self::Api>,
+        pair_address: ManagedAddress<Self::Api>,
+        token_out: TokenIdentifier<Self::Api>
+    ) {
+        require!(self.blockchain().is_smart_contract(&pair_address), "Invalid pair address");
+        require!(token_in != token_out, "The input and output tokens should be different");
+
+        self.swap_routes(&token_in).set(SwapRoute {
+            pair_address,
+            token_out,
+        });
+    }
+
+    /**
+     * It allows the smart contract owner to remove a swap route
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the route exists
+     *
+     * Actions
+     * [x] It should clear the `swap_routes` storage
+     */
+    #[only_owner]
+    #[endpoint(removeSwapRoute)]
+    fn remove_swap_route(&self, token_in: TokenIdentifier<Self::Api>) {
+        require!(!self.swap_routes(&token_in).is_empty(), "No swap route configured for this token");
+
+        self.swap_routes(&token_in).clear();
+    }
+
+    /**
+     * It swaps a payment through the configured DEX pair and returns the output payment
+     *
+     * Validation
+     * [x] It should check that a swap route is configured for the payment token
+     * [x] It should check that the output token is the route output token
+     */
+    fn swap_payment(
+        &self,
+        payment: EsdtTokenPayment<Self::Api>,
+        min_out: BigUint<Self::Api>
+    ) -> EsdtTokenPayment<Self::Api> {
+        require!(!self.swap_routes(&payment.token_identifier).is_empty(), "No swap route configured for this token");
+
+        let route = self.swap_routes(&payment.token_identifier).get();
+
+        let output: EsdtTokenPayment<Self::Api> = self.pair_proxy(route.pair_address)
+            .swap_tokens_fixed_input(route.token_out.clone(), min_out)
+            .add_esdt_token_transfer(payment.token_identifier, payment.token_nonce, payment.amount)
+            .execute_on_dest_context();
+
+        require!(output.token_identifier == route.token_out, "Invalid swap output token");
+
+        output
+    }
+}
@@ -1,10 +1,21 @@
 elrond_wasm::imports!();
 
-use crate::structs::{Plan, UserPlan};
+use crate::structs::{Plan, UserPlan, ActivationSource, ExpiryEntry};
+use crate::activation::EXPIRY_BUCKET_DURATION;
 
 #[elrond_wasm::module]
 pub trait ViewsModule: 
-    crate::storage::StorageModule
+    crate::storage::StorageModule +
+    crate::escrow::EscrowModule +
+    crate::pricing::PricingModule +
+    crate::events::EventsModule +
+    crate::activation::ActivationModule +
+    crate::entitlements::EntitlementsModule +
+    crate::plans::PlansModule +
+    crate::merchants::MerchantsModule +
+    crate::metering::MeteringModule +
+    crate::delegates::DelegatesModule +
+    crate::denylist::DenylistModule
 {
     /**
      * It returns the subscription plans available
@@ -34,12 +45,36 @@ pub trait ViewsModule:
     ) -> MultiValueEncoded<(EgldOrEsdtTokenIdentifier<Self::Api>, BigUint<Self::Api>)> {
         let mut prices_vec = MultiValueEncoded::new();
 
-        if self.plan_tokens(&plan_id).is_empty() {
+        if self.plan_tokens(plan_id).is_empty() {
             return prices_vec
         }
 
-        for token_id in self.plan_tokens(&plan_id).iter() {
-            prices_vec.push((token_id.clone(), self.plan_prices(&plan_id, &token_id).get()));
+        for token_id in self.plan_tokens(plan_id).iter() {
+            prices_vec.push((token_id.clone(), self.plan_prices(plan_id, &token_id).get()));
+        }
+
+        prices_vec
+    }
+
+    /**
+     * It returns the accepted nonces and their prices for a subscription plan token
+     */
+    #[view(getSubscriptionPlanNoncePrices)]
+    fn get_subscription_plan_nonce_prices(
+        &self,
+        plan_id: &ManagedBuffer<Self::Api>,
+        token_identifier: &EgldOrEsdtTokenIdentifier<Self::Api>
+    ) -> MultiValueEncoded<(u64, BigUint<Self::Api>)> {
+        let mut prices_vec = MultiValueEncoded::new();
+
+        for nonce in self.plan_token_nonces(plan_id, token_identifier).iter() {
+            let nonce_price = self.plan_nonce_prices(plan_id, token_identifier, nonce);
+
+            if nonce_price.is_empty() {
+                prices_vec.push((nonce, self.plan_prices(plan_id, token_identifier).get()));
+            } else {
+                prices_vec.push((nonce, nonce_price.get()));
+            }
         }
 
         prices_vec
@@ -52,12 +87,436 @@ pub trait ViewsModule:
     fn get_user_plans(&self, address: &ManagedAddress) -> MultiValueEncoded<UserPlan<Self::Api>> {
         let mut items_vec = MultiValueEncoded::new();
 
-        if !self.users().contains(&address) {
+        if !self.users().contains(address) {
             return items_vec
         }
 
-        for plan_id in self.user_plan_ids(&address).iter() {
-           items_vec.push(self.user_plans(&address, &plan_id).get());
+        for plan_id in self.user_plan_ids(address).iter() {
+           items_vec.push(self.user_plans(address, &plan_id).get());
+        }
+
+        items_vec
+    }
+
+    /**
+     * It returns the price of a plan for a token and nonce at the current timestamp, with the running promotion applied
+     */
+    #[view(getEffectivePrice)]
+    fn get_effective_price_view(
+        &self,
+        plan_id: &ManagedBuffer<Self::Api>,
+        token_identifier: EgldOrEsdtTokenIdentifier<Self::Api>,
+        nonce: u64
+    ) -> BigUint<Self::Api> {
+        self.get_plan_payment_price(plan_id, &EgldOrEsdtTokenPayment::new(token_identifier, nonce, BigUint::zero()))
+    }
+
+    /**
+     * It returns the subscription plans of a merchant
+     */
+    #[view(getMerchantSubscriptionPlans)]
+    fn get_merchant_subscription_plans(&self, merchant_id: u64) -> MultiValueEncoded<Plan<Self::Api>> {
+        let mut items_vec = MultiValueEncoded::new();
+
+        for plan_id in self.merchant_plan_ids(merchant_id).iter() {
+           items_vec.push(self.plans(&plan_id).get());
+        }
+
+        items_vec
+    }
+
+    /**
+     * It returns the prices for a specific merchant subscription plan
+     */
+    #[view(getMerchantSubscriptionPlanPrices)]
+    fn get_merchant_subscription_plan_prices(
+        &self,
+        merchant_id: u64,
+        plan_id: &ManagedBuffer<Self::Api>
+    ) -> MultiValueEncoded<(EgldOrEsdtTokenIdentifier<Self::Api>, BigUint<Self::Api>)> {
+        self.get_subscription_plan_prices(&self.get_merchant_plan_id(merchant_id, plan_id))
+    }
+
+    /**
+     * It returns the user plans of a merchant
+     */
+    #[view(getMerchantUserPlans)]
+    fn get_merchant_user_plans(&self, merchant_id: u64, address: &ManagedAddress) -> MultiValueEncoded<UserPlan<Self::Api>> {
+        let mut items_vec = MultiValueEncoded::new();
+
+        for plan_id in self.user_plan_ids(address).iter() {
+            if self.merchant_plan_ids(merchant_id).contains(&plan_id) {
+                items_vec.push(self.user_plans(address, &plan_id).get());
+            }
+        }
+
+        items_vec
+    }
+
+    /**
+     * It returns the number of plan activations for each source [payment/grant/voucher]
+     */
+    #[view(getPlanActivations)]
+    fn get_plan_activations(
+        &self,
+        plan_id: &ManagedBuffer<Self::Api>
+    ) -> MultiValueEncoded<(ActivationSource, u64)> {
+        let mut items_vec = MultiValueEncoded::new();
+
+        for source in [
+            ActivationSource::Payment,
+            ActivationSource::Grant,
+            ActivationSource::Voucher,
+            ActivationSource::Compensation,
+            ActivationSource::Bundle,
+            ActivationSource::Loyalty,
+            ActivationSource::Stake
+        ] {
+            items_vec.push((source, self.plan_activations(plan_id, &source).get()));
+        }
+
+        items_vec
+    }
+
+    /**
+     * It returns whether an address is denied
+     */
+    #[view(isDenied)]
+    fn is_denied(&self, address: &ManagedAddress) -> bool {
+        self.denied_addresses().contains(address)
+    }
+
+    /**
+     * It returns whether an address has access to a plan [own or delegated subscription, not expired and not paused]
+     */
+    #[view(hasActiveSubscription)]
+    fn has_active_subscription(&self, address: &ManagedAddress, plan_id: &ManagedBuffer<Self::Api>) -> bool {
+        self.has_plan_access(address, plan_id)
+    }
+
+    /**
+     * It returns the delegates of a user for all its plans [plan, delegate]
+     */
+    #[view(getUserDelegates)]
+    fn get_user_delegates(
+        &self,
+        address: &ManagedAddress
+    ) -> MultiValueEncoded<MultiValue2<ManagedBuffer<Self::Api>, ManagedAddress<Self::Api>>> {
+        let mut items_vec = MultiValueEncoded::new();
+
+        for plan_id in self.user_plan_ids(address).iter() {
+            for delegate_address in self.user_delegates(address, &plan_id).iter() {
+                items_vec.push((plan_id.clone(), delegate_address).into());
+            }
+        }
+
+        items_vec
+    }
+
+    /**
+     * It returns the component plans activated by a bundle plan [component plan, validity]
+     */
+    #[view(getSubscriptionPlanBundle)]
+    fn get_subscription_plan_bundle(
+        &self,
+        plan_id: &ManagedBuffer<Self::Api>
+    ) -> MultiValueEncoded<MultiValue2<ManagedBuffer<Self::Api>, u64>> {
+        let mut items_vec = MultiValueEncoded::new();
+
+        for component_id in self.plan_bundle_components(plan_id).iter() {
+            let validity = self.plan_bundle_validity(plan_id, &component_id).get();
+
+            items_vec.push((component_id, validity).into());
+        }
+
+        items_vec
+    }
+
+    /**
+     * It returns the number of days of a plan that a user can redeem with its loyalty points
+     */
+    #[view(getRedeemableDays)]
+    fn get_redeemable_days(&self, address: &ManagedAddress, plan_id: &ManagedBuffer<Self::Api>) -> u64 {
+        let points_per_day = self.plan_points_per_day(plan_id).get();
+
+        if points_per_day == 0 {
+            return 0
+        }
+
+        (self.loyalty_points(address).get() / points_per_day).to_u64().unwrap_or(u64::MAX)
+    }
+
+    /**
+     * It returns the entitlements of a plan [key, quota, reset period]
+     */
+    #[view(getSubscriptionPlanEntitlements)]
+    fn get_subscription_plan_entitlements(
+        &self,
+        plan_id: &ManagedBuffer<Self::Api>
+    ) -> MultiValueEncoded<MultiValue3<ManagedBuffer<Self::Api>, u64, u64>> {
+        let mut items_vec = MultiValueEncoded::new();
+
+        for key in self.plan_entitlement_keys(plan_id).iter() {
+            let entitlement = self.plan_entitlements(plan_id, &key).get();
+
+            items_vec.push((key, entitlement.quota, entitlement.reset_period).into());
+        }
+
+        items_vec
+    }
+
+    /**
+     * It returns the quota left to a user for an entitlement key in the current period
+     */
+    #[view(getRemainingQuota)]
+    fn get_remaining_quota_view(&self, address: &ManagedAddress, key: &ManagedBuffer<Self::Api>) -> u64 {
+        self.get_remaining_quota(address, key)
+    }
+
+    /**
+     * It returns the usage of a user that is not settled yet [plan, add-on, period, units, token, charge]
+     *
+     * The usage of the removed add-ons is listed until a settlement drops it, as it is part of the outstanding charge
+     */
+    #[view(getOutstandingUsageCharges)]
+    fn get_outstanding_usage_charges(
+        &self,
+        address: &ManagedAddress
+    ) -> MultiValueEncoded<MultiValue6<ManagedBuffer<Self::Api>, ManagedBuffer<Self::Api>, u64, u64, EgldOrEsdtTokenIdentifier<Self::Api>, BigUint<Self::Api>>> {
+        let mut items_vec = MultiValueEncoded::new();
+
+        for usage_key in self.user_open_usage(address).iter() {
+            let record = self.user_usage(address, &usage_key).get();
+
+            items_vec.push((usage_key.plan_id, usage_key.addon_id, usage_key.period, record.units, record.token_identifier, record.charge).into());
+        }
+
+        items_vec
+    }
+
+    /**
+     * It returns the address where the payments made with a plan token are sent to, the merchant payment address for the merchant plans
+     */
+    #[view(getPaymentDestination)]
+    fn get_payment_destination_view(
+        &self,
+        plan_id: ManagedBuffer<Self::Api>,
+        token_identifier: EgldOrEsdtTokenIdentifier<Self::Api>
+    ) -> ManagedAddress<Self::Api> {
+        self.get_payment_destination(&plan_id, &token_identifier)
+    }
+
+    /**
+     * It returns the escrow revenue for each token [earned and not claimed, deferred, claimed]
+     */
+    #[view(getEscrowRevenue)]
+    fn get_escrow_revenue_view(
+        &self
+    ) -> MultiValueEncoded<(EgldOrEsdtTokenIdentifier<Self::Api>, BigUint<Self::Api>, BigUint<Self::Api>, BigUint<Self::Api>)> {
+        let mut items_vec = MultiValueEncoded::new();
+
+        for token_identifier in self.escrow_token_ids().iter() {
+            let (earned, deferred) = self.get_escrow_revenue(&token_identifier);
+            let claimed = self.escrow_claimed(&token_identifier).get();
+
+            items_vec.push((token_identifier, earned, deferred, claimed));
+        }
+
+        items_vec
+    }
+
+    /**
+     * It returns a page of the user subscriptions expiring between two timestamps [from inclusive, to exclusive]
+     */
+    #[view(getExpiringBetween)]
+    fn get_expiring_between(
+        &self,
+        from: u64,
+        to: u64,
+        start: usize,
+        count: usize
+    ) -> MultiValueEncoded<ExpiryEntry<Self::Api>> {
+        let mut items_vec = MultiValueEncoded::new();
+
+        if from >= to || count == 0 {
+            return items_vec
+        }
+
+        let mut skipped = 0;
+        let mut added = 0;
+
+        for day in from / EXPIRY_BUCKET_DURATION..=(to - 1) / EXPIRY_BUCKET_DURATION {
+            for entry in self.expiry_buckets(day).iter() {
+                if entry.expires_at < from || entry.expires_at >= to {
+                    continue;
+                }
+
+                if skipped < start {
+                    skipped += 1;
+                    continue;
+                }
+
+                items_vec.push(entry);
+                added += 1;
+
+                if added == count {
+                    return items_vec
+                }
+            }
+        }
+
+        items_vec
+    }
+
+    /**
+     * It returns the number of expired user subscriptions for a plan that are not pruned
+     *
+     * Only the expiry days since the last pruned day are checked
+     */
+    #[view(getExpiredCount)]
+    fn get_expired_count(&self, plan_id: &ManagedBuffer<Self::Api>) -> u64 {
+        let current_timestamp = self.blockchain().get_block_timestamp();
+        let current_day = current_timestamp / EXPIRY_BUCKET_DURATION;
+        let mut expired_count = 0;
+
+        for day in self.expiry_prune_day().get()..current_day {
+            expired_count += self.plan_expiry_counts(plan_id, day).get();
+        }
+
+        if self.plan_expiry_counts(plan_id, current_day).get() > 0 {
+            expired_count += self.expiry_buckets(current_day)
+                .iter()
+                .filter(|entry| &entry.plan_id == plan_id && entry.expires_at <= current_timestamp)
+                .count() as u64;
+        }
+
+        expired_count
+    }
+
+    /**
+     * It returns the number of users that activated a plan
+     */
+    #[view(getUserCount)]
+    fn get_user_count(&self) -> usize {
+        self.users().len()
+    }
+
+    /**
+     * It returns a page of the users that activated a plan, starting with the user at position `start`
+     */
+    #[view(getUsers)]
+    fn get_users(&self, start: usize, count: usize) -> MultiValueEncoded<ManagedAddress<Self::Api>> {
+        let mut items_vec = MultiValueEncoded::new();
+        let end = self.users().len().min(start.saturating_add(count));
+
+        for index in start..end {
+            items_vec.push(self.users().get_by_index(index + 1));
+        }
+
+        items_vec
+    }
+
+    /**
+     * It returns the number of addresses that have an entry for a plan [active, paused or expired and not pruned]
+     */
+    #[view(getSubscriptionPlanSubscriberCount)]
+    fn get_subscription_plan_subscriber_count(&self, plan_id: &ManagedBuffer<Self::Api>) -> usize {
+        self.plan_subscribers(plan_id).len()
+    }
+
+    /**
+     * It returns the number of addresses with a subscription for a plan that is not expired and not paused
+     *
+     * The expirations and the pause ends are counted once their day is pruned
+     */
+    #[view(getActiveSubscriberCount)]
+    fn get_active_subscriber_count(&self, plan_id: &ManagedBuffer<Self::Api>) -> u64 {
+        self.plan_active_subscriber_count(plan_id).get()
+    }
+
+    /**
+     * It returns a page of the addresses that have an entry for a plan, starting with the address at position `start`
+     */
+    #[view(getSubscriptionPlanSubscribers)]
+    fn get_subscription_plan_subscribers(
+        &self,
+        plan_id: &ManagedBuffer<Self::Api>,
+        start: usize,
+        count: usize
+    ) -> MultiValueEncoded<ManagedAddress<Self::Api>> {
+        let mut items_vec = MultiValueEncoded::new();
+        let end = self.plan_subscribers(plan_id).len().min(start.saturating_add(count));
+
+        for index in start..end {
+            items_vec.push(self.plan_subscribers(plan_id).get_by_index(index + 1));
+        }
+
+        items_vec
+    }
+
+    /**
+     * It returns a page of the user subscriptions, in the `importUserPlans` format
+     *
+     * The page is made of the subscriptions of `count` users, starting with the user at position `start`
+     */
+    #[view(exportUserPlans)]
+    fn export_user_plans(
+        &self,
+        start: usize,
+        count: usize
+    ) -> MultiValueEncoded<MultiValue2<ManagedAddress<Self::Api>, UserPlan<Self::Api>>> {
+        let mut items_vec = MultiValueEncoded::new();
+        let end = self.users().len().min(start.saturating_add(count));
+
+        for index in start..end {
+            let address = self.users().get_by_index(index + 1);
+
+            for plan_id in self.user_plan_ids(&address).iter() {
+                items_vec.push((address.clone(), self.user_plans(&address, &plan_id).get()).into());
+            }
+        }
+
+        items_vec
+    }
+
+    /**
+     * It returns the total payments made to this smart contract, in the `importPaymentTotals` format
+     */
+    #[view(exportPaymentTotals)]
+    fn export_payment_totals(&self) -> MultiValueEncoded<MultiValue2<EgldOrEsdtTokenIdentifier<Self::Api>, BigUint<Self::Api>>> {
+        let mut items_vec = MultiValueEncoded::new();
+
+        for token_identifier in self.payment_token_ids().iter() {
+            let amount = self.payment_token_amounts(&token_identifier).get();
+
+            items_vec.push((token_identifier, amount).into());
+        }
+
+        items_vec
+    }
+
+    /**
+     * It returns a page of the total payments made by users, in the `importUserPaymentTotals` format
+     *
+     * The page is made of the totals of `count` users, starting with the user at position `start`
+     */
+    #[view(exportUserPaymentTotals)]
+    fn export_user_payment_totals(
+        &self,
+        start: usize,
+        count: usize
+    ) -> MultiValueEncoded<MultiValue3<ManagedAddress<Self::Api>, EgldOrEsdtTokenIdentifier<Self::Api>, BigUint<Self::Api>>> {
+        let mut items_vec = MultiValueEncoded::new();
+        let end = self.users().len().min(start.saturating_add(count));
+
+        for index in start..end {
+            let address = self.users().get_by_index(index + 1);
+
+            for token_identifier in self.user_payment_tokens(&address).iter() {
+                let amount = self.user_payment_amounts(&address, &token_identifier).get();
+
+                items_vec.push((address.clone(), token_identifier, amount).into());
+            }
         }
 
         items_vec
@@ -0,0 +1,126 @@
+elrond_wasm::imports!();
+
+use crate::structs::ActivationSource;
+
+pub const ED25519_KEY_LENGTH: usize = 32;
+pub const ED25519_SIGNATURE_LENGTH: usize = 64;
+pub const MAX_VOUCHER_MESSAGE_LENGTH: usize = 384;
+pub const VOUCHER_DOMAIN: &[u8] = b"subscriptions.voucher";
+
+#[elrond_wasm::module]
+pub trait VouchersModule:
+    crate::storage::StorageModule +
+    crate::pricing::PricingModule +
+    crate::activation::ActivationModule +
+    crate::denylist::DenylistModule +
+    crate::events::EventsModule +
+    crate::merkle::MerkleModule
+{
+    /**
+     * It allows the smart contract owner to set the ed25519 public key that signs the vouchers
+     * and the chain id the vouchers are signed for
+     *
+     * Validation
+     * [x] It should check that the caller is the SC owner
+     * [x] It should check that the chain id is not empty
+     *
+     * Actions
+     * [x] It should set the public key to `voucher_signer` storage
+     * [x] It should set the chain id to `voucher_chain_id` storage
+     */
+    #[only_owner]
+    #[endpoint(setVoucherSigner)]
+    fn set_voucher_signer(&self, public_key: ManagedByteArray<Self::Api, ED25519_KEY_LENGTH>, chain_id: ManagedBuffer<Self::Api>) {
+        require!(!chain_id.is_empty(), "Invalid chain id");
+
+        self.voucher_signer().set(public_key);
+        self.voucher_chain_id().set(chain_id);
+    }
+
+    /**
+     * It allows anyone to activate a plan with a voucher signed by the voucher signer
+     *
+     * The signed message is the concatenation of:
+     * "subscriptions.voucher" | chain id length [4 bytes, big endian] | chain id | contract address [32 bytes]
+     * | caller address [32 bytes] | plan id length [4 bytes, big endian] | plan id | duration [8 bytes, big endian]
+     * | nonce [8 bytes, big endian] | expiry [8 bytes, big endian]
+     *
+     * Validation
+     * [x] It should check that the voucher signer is configured
+     * [x] It should check that the plan exists
+     * [x] It should check that the subscription plan is not a merchant plan
+     * [x] It should check that the duration is not zero
+     * [x] It should check that the voucher is not expired
+     * [x] It should check that the voucher was not redeemed before
+     * [x] It should check that the caller is not denied
+     * [x] It should check that the signature is valid
+     *
+     * Actions
+     * [x] It should mark the voucher nonce as redeemed
+     * [x] It should call `activate_user_plan` function with the `Voucher` source
+     * [x] It should emit the `redeemVoucher` event
+     */
+    #[endpoint(redeemVoucher)]
+    fn redeem_voucher(
+        &self,
+        plan_id: ManagedBuffer<Self::Api>,
+        duration: u64,
+        nonce: u64,
+        expiry: u64,
+        signature: ManagedByteArray<Self::Api, ED25519_SIGNATURE_LENGTH>
+    ) {
+        let caller = self.blockchain().get_caller();
+
+        require!(!self.voucher_signer().is_empty(), "Voucher signer is not configured");
+        self.require_owner_plan(&plan_id);
+        require!(duration > 0, "Invalid duration");
+        require!(self.blockchain().get_block_timestamp() <= expiry, "This voucher is expired");
+        require!(!self.redeemed_vouchers(nonce).get(), "This voucher was already redeemed");
+        self.require_not_denied(&caller);
+
+        let message = self.get_voucher_message(&caller, &plan_id, duration, nonce, expiry);
+        require!(message.len() <= MAX_VOUCHER_MESSAGE_LENGTH, "Invalid plan id");
+        require!(
+            self.crypto().verify_ed25519_legacy_managed::<MAX_VOUCHER_MESSAGE_LENGTH>(
+                &self.voucher_signer().get(),
+                &message,
+                &signature
+            ),
+            "Invalid voucher signature"
+        );
+
+        self.redeemed_vouchers(nonce).set(true);
+
+        let plan = self.plans(&plan_id).get();
+        self.activate_user_plan(&caller, &plan, duration, ActivationSource::Voucher);
+
+        self.redeem_voucher_event(&caller, &plan_id, nonce, duration);
+    }
+
+    /**
+     * It builds the message signed for a voucher, bound to the chain and to this contract
+     */
+    fn get_voucher_message(
+        &self,
+        caller: &ManagedAddress,
+        plan_id: &ManagedBuffer,
+        duration: u64,
+        nonce: u64,
+        expiry: u64
+    ) -> ManagedBuffer<Self::Api> {
+        let chain_id = self.voucher_chain_id().get();
+        let mut message = ManagedBuffer::new_from_bytes(VOUCHER_DOMAIN);
+
+        message.append_u32_be(chain_id.len() as u32);
+        message.append(&chain_id);
+        message.append(self.blockchain().get_sc_address().as_managed_buffer());
+        message.append(caller.as_managed_buffer());
+        message.append_u32_be(plan_id.len() as u32);
+        message.append(plan_id);
+        message.append_bytes(&duration.to_be_bytes());
+        message.append_bytes(&nonce.to_be_bytes());
+        message.append_bytes(&expiry.to_be_bytes());
+
+        message
+    }
+}
@@ -0,0 +1,71 @@
+mod setup;
+
+use elrond_wasm::types::EgldOrEsdtTokenIdentifier;
+use elrond_wasm_debug::{managed_biguint, rust_biguint};
+use setup::*;
+use subscriptions::accumulation::AccumulationModule;
+use subscriptions::storage::StorageModule;
+
+const SWEEP_THRESHOLD: u64 = PLAN_PRICE * 3 / 2;
+
+#[test]
+fn sweep_test() {
+    let mut setup = setup_subscriptions(subscriptions::contract_obj);
+    let user_address = setup.create_user(PLAN_PRICE);
+    let keeper_address = setup.create_user(0);
+    let payment_address = setup.payment_address.clone();
+
+    setup
+        .user_tx(&payment_address, |sc| sc.sweep())
+        .assert_user_error("Nothing to sweep");
+    setup
+        .owner_tx(|sc| sc.set_accumulation_mode(true))
+        .assert_ok();
+
+    setup.pay_egld(&user_address, PLAN_PRICE).assert_ok();
+    setup.blockchain_wrapper.check_egld_balance(&payment_address, &rust_biguint!(0u64));
+    setup.query(|sc| {
+        assert_eq!(sc.accumulated_balance(&EgldOrEsdtTokenIdentifier::egld()).get(), managed_biguint!(PLAN_PRICE));
+    });
+
+    setup
+        .user_tx(&keeper_address, |sc| sc.sweep())
+        .assert_user_error("Only the payment address can sweep the payments");
+    setup.user_tx(&payment_address, |sc| sc.sweep()).assert_ok();
+    setup.blockchain_wrapper.check_egld_balance(&payment_address, &rust_biguint!(PLAN_PRICE));
+    setup.query(|sc| assert!(sc.accumulated_token_ids().is_empty()));
+}
+
+#[test]
+fn sweep_threshold_test() {
+    let mut setup = setup_subscriptions(subscriptions::contract_obj);
+    let user_address = setup.create_user(PLAN_PRICE);
+    let other_address = setup.create_user(PLAN_PRICE);
+    let last_address = setup.create_user(PLAN_PRICE);
+    let payment_address = setup.payment_address.clone();
+
+    setup
+        .owner_tx(|sc| {
+            sc.set_accumulation_mode(true);
+            sc.set_sweep_threshold(EgldOrEsdtTokenIdentifier::egld(), managed_biguint!(SWEEP_THRESHOLD));
+        })
+        .assert_ok();
+
+    setup.pay_egld(&user_address, PLAN_PRICE).assert_ok();
+    setup.blockchain_wrapper.check_egld_balance(&payment_address, &rust_biguint!(0u64));
+
+    // the payment that reaches the threshold sweeps the accumulated payments to the payment address
+    setup.pay_egld(&other_address, PLAN_PRICE).assert_ok();
+    setup.blockchain_wrapper.check_egld_balance(&payment_address, &rust_biguint!(PLAN_PRICE * 2));
+    setup.query(|sc| {
+        assert!(sc.accumulated_balance(&EgldOrEsdtTokenIdentifier::egld()).is_empty());
+        assert!(sc.accumulated_token_ids().is_empty());
+    });
+
+    // the accumulation starts again after the sweep
+    setup.pay_egld(&last_address, PLAN_PRICE).assert_ok();
+    setup.blockchain_wrapper.check_egld_balance(&payment_address, &rust_biguint!(PLAN_PRICE * 2));
+    setup.query(|sc| {
+        assert_eq!(sc.accumulated_balance(&EgldOrEsdtTokenIdentifier::egld()).get(), managed_biguint!(PLAN_PRICE));
+    });
+}
@@ -0,0 +1,98 @@
+mod setup;
+
+use elrond_wasm_debug::{managed_address, managed_buffer};
+use setup::*;
+use subscriptions::bundles::BundlesModule;
+use subscriptions::renewals::RenewalsModule;
+use subscriptions::storage::StorageModule;
+use subscriptions::structs::RenewalMode;
+use subscriptions::Subscriptions;
+
+const COMPONENT_ID: &[u8] = b"addon";
+
+#[test]
+fn bundle_component_configuration_test() {
+    let mut setup = setup_subscriptions(subscriptions::contract_obj);
+
+    setup
+        .owner_tx(|sc| {
+            sc.add_plan(managed_buffer!(COMPONENT_ID), PLAN_VALIDITY);
+            sc.archive_subscription_plan(managed_buffer!(COMPONENT_ID));
+        })
+        .assert_ok();
+    setup
+        .owner_tx(|sc| sc.set_subscription_plan_bundle_component(managed_buffer!(PLAN_ID), managed_buffer!(COMPONENT_ID), DAY))
+        .assert_user_error("An archived plan cannot be a bundle component");
+
+    setup
+        .owner_tx(|sc| {
+            sc.enable_subscription_plan(managed_buffer!(COMPONENT_ID));
+            sc.set_subscription_plan_renewal_mode(managed_buffer!(COMPONENT_ID), RenewalMode::Lifetime);
+        })
+        .assert_ok();
+    setup
+        .owner_tx(|sc| sc.set_subscription_plan_bundle_component(managed_buffer!(PLAN_ID), managed_buffer!(COMPONENT_ID), DAY))
+        .assert_user_error("A lifetime plan cannot be a bundle component");
+
+    setup
+        .owner_tx(|sc| sc.set_subscription_plan_renewal_mode(managed_buffer!(COMPONENT_ID), RenewalMode::Extend))
+        .assert_ok();
+    setup
+        .owner_tx(|sc| sc.set_subscription_plan_bundle_component(managed_buffer!(PLAN_ID), managed_buffer!(COMPONENT_ID), DAY))
+        .assert_ok();
+
+    // a component cannot be removed while a bundle still lists it
+    setup
+        .owner_tx(|sc| sc.remove_plan(managed_buffer!(COMPONENT_ID)))
+        .assert_user_error("This plan is a bundle component");
+    setup
+        .owner_tx(|sc| {
+            sc.remove_subscription_plan_bundle_component(managed_buffer!(PLAN_ID), managed_buffer!(COMPONENT_ID));
+            sc.remove_plan(managed_buffer!(COMPONENT_ID));
+        })
+        .assert_ok();
+}
+
+#[test]
+fn bundle_skips_unavailable_components_test() {
+    let mut setup = setup_subscriptions(subscriptions::contract_obj);
+    let user_address = setup.create_user(PLAN_PRICE * 2);
+
+    setup
+        .owner_tx(|sc| {
+            sc.add_plan(managed_buffer!(COMPONENT_ID), PLAN_VALIDITY);
+            sc.set_subscription_plan_bundle_component(managed_buffer!(PLAN_ID), managed_buffer!(COMPONENT_ID), DAY);
+        })
+        .assert_ok();
+
+    setup.pay_egld(&user_address, PLAN_PRICE).assert_ok();
+    setup.query(|sc| {
+        let user_plan = sc.user_plans(&managed_address!(&user_address), &managed_buffer!(COMPONENT_ID)).get();
+
+        assert_eq!(user_plan.expires_at, START_TIMESTAMP + DAY);
+    });
+
+    // the component became a lifetime plan the user already holds, the bundle keeps working
+    setup
+        .owner_tx(|sc| sc.set_subscription_plan_renewal_mode(managed_buffer!(COMPONENT_ID), RenewalMode::Lifetime))
+        .assert_ok();
+    setup.set_timestamp(START_TIMESTAMP + DAY / 2);
+    setup.pay_egld(&user_address, PLAN_PRICE).assert_ok();
+    setup.query(|sc| {
+        let user_plan = sc.user_plans(&managed_address!(&user_address), &managed_buffer!(COMPONENT_ID)).get();
+
+        assert_eq!(user_plan.expires_at, START_TIMESTAMP + DAY);
+    });
+
+    // an archived component is skipped instead of reverting the bundle purchase
+    let other_address = setup.create_user(PLAN_PRICE);
+
+    setup
+        .owner_tx(|sc| sc.archive_subscription_plan(managed_buffer!(COMPONENT_ID)))
+        .assert_ok();
+    setup.pay_egld(&other_address, PLAN_PRICE).assert_ok();
+    setup.query(|sc| {
+        assert!(sc.user_plans(&managed_address!(&other_address), &managed_buffer!(COMPONENT_ID)).is_empty());
+        assert!(sc.user_plans(&managed_address!(&other_address), &managed_buffer!(PLAN_ID)).get().expires_at > START_TIMESTAMP);
+    });
+}
@@ -0,0 +1,94 @@
+mod setup;
+
+use elrond_wasm::types::MultiValueEncoded;
+use elrond_wasm_debug::{managed_address, managed_buffer};
+use setup::*;
+use subscriptions::delegates::DelegatesModule;
+use subscriptions::denylist::DenylistModule;
+use subscriptions::grants::GrantsModule;
+use subscriptions::views::ViewsModule;
+
+#[test]
+fn denied_address_test() {
+    let mut setup = setup_subscriptions(subscriptions::contract_obj);
+    let user_address = setup.create_user(PLAN_PRICE * 2);
+    let denied_address = user_address.clone();
+
+    setup
+        .owner_tx(|sc| {
+            let mut addresses = MultiValueEncoded::new();
+            addresses.push(managed_address!(&denied_address));
+
+            sc.add_denied_addresses(addresses);
+        })
+        .assert_ok();
+
+    setup.query(|sc| assert!(sc.is_denied(&managed_address!(&denied_address))));
+    setup.pay_egld(&user_address, PLAN_PRICE).assert_user_error("This address is denied");
+    setup
+        .owner_tx(|sc| sc.grant_subscription(managed_address!(&denied_address), managed_buffer!(PLAN_ID), DAY))
+        .assert_user_error("This address is denied");
+
+    setup
+        .owner_tx(|sc| {
+            let mut addresses = MultiValueEncoded::new();
+            addresses.push(managed_address!(&denied_address));
+
+            sc.remove_denied_addresses(addresses);
+        })
+        .assert_ok();
+
+    setup.query(|sc| assert!(!sc.is_denied(&managed_address!(&denied_address))));
+    setup.pay_egld(&user_address, PLAN_PRICE).assert_ok();
+}
+
+#[test]
+fn denied_delegate_test() {
+    let mut setup = setup_subscriptions(subscriptions::contract_obj);
+    let user_address = setup.create_user(PLAN_PRICE);
+    let delegate_address = setup.create_user(0);
+    let denied_address = setup.create_user(0);
+
+    setup
+        .owner_tx(|sc| sc.set_subscription_plan_delegate_settings(managed_buffer!(PLAN_ID), 2, 0))
+        .assert_ok();
+    setup.pay_egld(&user_address, PLAN_PRICE).assert_ok();
+
+    setup
+        .owner_tx(|sc| {
+            let mut addresses = MultiValueEncoded::new();
+            addresses.push(managed_address!(&denied_address));
+
+            sc.add_denied_addresses(addresses);
+        })
+        .assert_ok();
+
+    setup
+        .user_tx(&user_address, |sc| sc.add_delegate(managed_buffer!(PLAN_ID), managed_address!(&denied_address)))
+        .assert_user_error("This address is denied");
+    setup
+        .user_tx(&user_address, |sc| sc.add_delegate(managed_buffer!(PLAN_ID), managed_address!(&delegate_address)))
+        .assert_ok();
+    setup.query(|sc| {
+        assert!(sc.has_active_subscription(&managed_address!(&user_address), &managed_buffer!(PLAN_ID)));
+        assert!(sc.has_active_subscription(&managed_address!(&delegate_address), &managed_buffer!(PLAN_ID)));
+    });
+
+    // denying the subscriber also removes the access of its delegates
+    setup
+        .owner_tx(|sc| {
+            let mut addresses = MultiValueEncoded::new();
+            addresses.push(managed_address!(&user_address));
+
+            sc.add_denied_addresses(addresses);
+        })
+        .assert_ok();
+
+    setup.query(|sc| {
+        assert!(!sc.has_active_subscription(&managed_address!(&user_address), &managed_buffer!(PLAN_ID)));
+        assert!(!sc.has_active_subscription(&managed_address!(&delegate_address), &managed_buffer!(PLAN_ID)));
+    });
+    setup
+        .user_tx(&user_address, |sc| sc.add_delegate(managed_buffer!(PLAN_ID), managed_address!(&denied_address)))
+        .assert_user_error("This address is denied");
+}
@@ -0,0 +1,118 @@
+mod setup;
+
+use elrond_wasm::types::{Address, EgldOrEsdtTokenIdentifier};
+use elrond_wasm_debug::{managed_address, managed_biguint, managed_buffer, managed_token_id, rust_biguint, DebugApi};
+use setup::*;
+use subscriptions::destinations::DestinationsModule;
+use subscriptions::merchants::MerchantsModule;
+use subscriptions::views::ViewsModule;
+
+const MERCHANT_PLAN_ID: &[u8] = b"1/pro";
+
+fn check_payment_destination<SubscriptionsBuilder>(setup: &mut SubscriptionsSetup<SubscriptionsBuilder>, plan_id: &[u8], address: &Address)
+where
+    SubscriptionsBuilder: 'static + Copy + Fn() -> subscriptions::ContractObj<DebugApi>,
+{
+    setup.query(|sc| {
+        assert_eq!(sc.get_payment_destination_view(managed_buffer!(plan_id), EgldOrEsdtTokenIdentifier::egld()), managed_address!(address));
+    });
+}
+
+#[test]
+fn token_payment_address_test() {
+    let mut setup = setup_subscriptions(subscriptions::contract_obj);
+    let user_address = setup.create_user(PLAN_PRICE * 2);
+    let token_address = setup.create_user(0);
+    let payment_address = setup.payment_address.clone();
+
+    setup
+        .owner_tx(|sc| sc.set_token_payment_address(EgldOrEsdtTokenIdentifier::esdt(managed_token_id!(b"INVALID")), managed_address!(&token_address)))
+        .assert_user_error("Invalid token");
+    setup
+        .owner_tx(|sc| sc.remove_token_payment_address(EgldOrEsdtTokenIdentifier::egld()))
+        .assert_user_error("This token has no payment address");
+
+    setup
+        .owner_tx(|sc| sc.set_token_payment_address(EgldOrEsdtTokenIdentifier::egld(), managed_address!(&token_address)))
+        .assert_ok();
+    check_payment_destination(&mut setup, PLAN_ID, &token_address);
+    setup.pay_egld(&user_address, PLAN_PRICE).assert_ok();
+    setup.blockchain_wrapper.check_egld_balance(&token_address, &rust_biguint!(PLAN_PRICE));
+
+    setup
+        .owner_tx(|sc| sc.remove_token_payment_address(EgldOrEsdtTokenIdentifier::egld()))
+        .assert_ok();
+    check_payment_destination(&mut setup, PLAN_ID, &payment_address);
+    setup.pay_egld(&user_address, PLAN_PRICE).assert_ok();
+    setup.blockchain_wrapper.check_egld_balance(&payment_address, &rust_biguint!(PLAN_PRICE));
+}
+
+#[test]
+fn plan_token_payment_address_test() {
+    let mut setup = setup_subscriptions(subscriptions::contract_obj);
+    let user_address = setup.create_user(PLAN_PRICE * 2);
+    let token_address = setup.create_user(0);
+    let plan_address = setup.create_user(0);
+
+    setup
+        .owner_tx(|sc| {
+            sc.set_subscription_plan_token_payment_address(
+                managed_buffer!(PLAN_ID),
+                EgldOrEsdtTokenIdentifier::esdt(managed_token_id!(b"USDC-123456")),
+                managed_address!(&plan_address),
+            );
+        })
+        .assert_user_error("No price configured for this plan/token combination");
+    setup
+        .owner_tx(|sc| sc.remove_subscription_plan_token_payment_address(managed_buffer!(PLAN_ID), EgldOrEsdtTokenIdentifier::egld()))
+        .assert_user_error("This plan/token combination has no payment address");
+
+    // the plan token payment address comes before the token payment address
+    setup
+        .owner_tx(|sc| {
+            sc.set_token_payment_address(EgldOrEsdtTokenIdentifier::egld(), managed_address!(&token_address));
+            sc.set_subscription_plan_token_payment_address(managed_buffer!(PLAN_ID), EgldOrEsdtTokenIdentifier::egld(), managed_address!(&plan_address));
+        })
+        .assert_ok();
+    check_payment_destination(&mut setup, PLAN_ID, &plan_address);
+    setup.pay_egld(&user_address, PLAN_PRICE).assert_ok();
+    setup.blockchain_wrapper.check_egld_balance(&plan_address, &rust_biguint!(PLAN_PRICE));
+
+    setup
+        .owner_tx(|sc| sc.remove_subscription_plan_token_payment_address(managed_buffer!(PLAN_ID), EgldOrEsdtTokenIdentifier::egld()))
+        .assert_ok();
+    check_payment_destination(&mut setup, PLAN_ID, &token_address);
+    setup.pay_egld(&user_address, PLAN_PRICE).assert_ok();
+    setup.blockchain_wrapper.check_egld_balance(&token_address, &rust_biguint!(PLAN_PRICE));
+}
+
+#[test]
+fn merchant_plan_payment_destination_test() {
+    let mut setup = setup_subscriptions(subscriptions::contract_obj);
+    let user_address = setup.create_user(PLAN_PRICE);
+    let merchant_address = setup.create_user(0);
+    let merchant_payment_address = setup.create_user(0);
+    let token_address = setup.create_user(0);
+
+    setup
+        .user_tx(&merchant_address, |sc| {
+            sc.register_merchant(managed_address!(&merchant_payment_address));
+            sc.add_merchant_plan(managed_buffer!(b"pro"), PLAN_VALIDITY);
+            sc.set_merchant_plan_price(managed_buffer!(b"pro"), &EgldOrEsdtTokenIdentifier::egld(), managed_biguint!(PLAN_PRICE));
+        })
+        .assert_ok();
+    setup
+        .owner_tx(|sc| sc.set_token_payment_address(EgldOrEsdtTokenIdentifier::egld(), managed_address!(&token_address)))
+        .assert_ok();
+    setup
+        .owner_tx(|sc| {
+            sc.set_subscription_plan_token_payment_address(managed_buffer!(MERCHANT_PLAN_ID), EgldOrEsdtTokenIdentifier::egld(), managed_address!(&token_address));
+        })
+        .assert_user_error("This plan is managed by its merchant");
+
+    // the merchant plan payments go to the merchant, whatever the token payment address
+    check_payment_destination(&mut setup, MERCHANT_PLAN_ID, &merchant_payment_address);
+    setup.pay_egld_for(&user_address, MERCHANT_PLAN_ID, PLAN_PRICE).assert_ok();
+    setup.blockchain_wrapper.check_egld_balance(&merchant_payment_address, &rust_biguint!(PLAN_PRICE));
+    setup.blockchain_wrapper.check_egld_balance(&token_address, &rust_biguint!(0u64));
+}
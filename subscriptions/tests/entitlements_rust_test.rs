@@ -0,0 +1,142 @@
+mod setup;
+
+use elrond_wasm::types::Address;
+use elrond_wasm_debug::{managed_address, managed_buffer, rust_biguint, tx_mock::TxResult, DebugApi};
+use setup::*;
+use subscriptions::delegates::DelegatesModule;
+use subscriptions::entitlements::EntitlementsModule;
+use subscriptions::views::ViewsModule;
+use subscriptions::Subscriptions;
+
+const KEY: &[u8] = b"calls";
+const QUOTA: u64 = 10;
+
+struct QuotaSetup<SubscriptionsBuilder>
+where
+    SubscriptionsBuilder: 'static + Copy + Fn() -> subscriptions::ContractObj<DebugApi>,
+{
+    setup: SubscriptionsSetup<SubscriptionsBuilder>,
+    consumer_address: Address,
+}
+
+fn setup_quota<SubscriptionsBuilder>(subscriptions_builder: SubscriptionsBuilder, reset_period: u64) -> QuotaSetup<SubscriptionsBuilder>
+where
+    SubscriptionsBuilder: 'static + Copy + Fn() -> subscriptions::ContractObj<DebugApi>,
+{
+    let mut setup = setup_subscriptions(subscriptions_builder);
+    let owner_address = setup.owner_address.clone();
+    let consumer_address = setup
+        .blockchain_wrapper
+        .create_sc_account(&rust_biguint!(0u64), Some(&owner_address), subscriptions_builder, SUBSCRIPTIONS_WASM_PATH)
+        .address_ref()
+        .clone();
+
+    setup
+        .owner_tx(|sc| {
+            sc.set_subscription_plan_entitlement(managed_buffer!(PLAN_ID), managed_buffer!(KEY), QUOTA, reset_period);
+            sc.set_subscription_plan_delegate_settings(managed_buffer!(PLAN_ID), 2, 0);
+            sc.add_quota_consumer(managed_address!(&consumer_address));
+        })
+        .assert_ok();
+
+    QuotaSetup { setup, consumer_address }
+}
+
+impl<SubscriptionsBuilder> QuotaSetup<SubscriptionsBuilder>
+where
+    SubscriptionsBuilder: 'static + Copy + Fn() -> subscriptions::ContractObj<DebugApi>,
+{
+    fn consume(&mut self, address: &Address, amount: u64) -> TxResult {
+        let consumer_address = self.consumer_address.clone();
+
+        self.setup.user_tx(&consumer_address, |sc| {
+            sc.consume_quota(managed_address!(address), managed_buffer!(KEY), amount);
+        })
+    }
+
+    fn check_remaining_quota(&mut self, address: &Address, remaining_quota: u64) {
+        self.setup.query(|sc| {
+            assert_eq!(sc.get_remaining_quota_view(&managed_address!(address), &managed_buffer!(KEY)), remaining_quota);
+        });
+    }
+}
+
+#[test]
+fn quota_period_reset_test() {
+    let mut quota = setup_quota(subscriptions::contract_obj, 7 * DAY);
+    let user_address = quota.setup.create_user(PLAN_PRICE);
+
+    quota.consume(&user_address, 1).assert_user_error("Quota exceeded");
+    quota.setup.pay_egld(&user_address, PLAN_PRICE).assert_ok();
+
+    quota.setup.set_timestamp(START_TIMESTAMP + DAY);
+    quota.consume(&user_address, 6).assert_ok();
+    quota.consume(&user_address, 5).assert_user_error("Quota exceeded");
+    quota.consume(&user_address, 4).assert_ok();
+    quota.check_remaining_quota(&user_address, 0);
+
+    // the period started with the first usage
+    quota.setup.set_timestamp(START_TIMESTAMP + 8 * DAY - 1);
+    quota.check_remaining_quota(&user_address, 0);
+
+    quota.setup.set_timestamp(START_TIMESTAMP + 8 * DAY);
+    quota.check_remaining_quota(&user_address, QUOTA);
+    quota.consume(&user_address, 3).assert_ok();
+
+    // the skipped periods keep the period boundaries
+    quota.setup.set_timestamp(START_TIMESTAMP + 29 * DAY);
+    quota.check_remaining_quota(&user_address, QUOTA);
+    quota.consume(&user_address, QUOTA).assert_ok();
+    quota.check_remaining_quota(&user_address, 0);
+}
+
+#[test]
+fn quota_period_kept_after_pruning_test() {
+    let mut quota = setup_quota(subscriptions::contract_obj, 40 * DAY);
+    let user_address = quota.setup.create_user(PLAN_PRICE * 2);
+
+    quota.setup.pay_egld(&user_address, PLAN_PRICE).assert_ok();
+    quota.consume(&user_address, QUOTA).assert_ok();
+
+    quota.setup.set_timestamp(START_TIMESTAMP + PLAN_VALIDITY + 2 * DAY);
+    quota.setup.user_tx(&user_address, |sc| assert_eq!(sc.prune_expired_subscriptions(100), 1)).assert_ok();
+
+    quota.setup.pay_egld(&user_address, PLAN_PRICE).assert_ok();
+    quota.check_remaining_quota(&user_address, 0);
+    quota.consume(&user_address, 1).assert_user_error("Quota exceeded");
+
+    quota.setup.set_timestamp(START_TIMESTAMP + 40 * DAY);
+    quota.check_remaining_quota(&user_address, QUOTA);
+}
+
+#[test]
+fn delegate_quota_test() {
+    let mut quota = setup_quota(subscriptions::contract_obj, 7 * DAY);
+    let user_address = quota.setup.create_user(PLAN_PRICE);
+    let delegate_address = quota.setup.create_user(0);
+
+    quota.setup.pay_egld(&user_address, PLAN_PRICE).assert_ok();
+    quota
+        .setup
+        .user_tx(&user_address, |sc| sc.add_delegate(managed_buffer!(PLAN_ID), managed_address!(&delegate_address)))
+        .assert_ok();
+
+    quota.check_remaining_quota(&delegate_address, QUOTA);
+    quota.consume(&user_address, 6).assert_ok();
+    quota.check_remaining_quota(&delegate_address, QUOTA - 6);
+
+    // the delegate shares the subscriber quota
+    quota.consume(&delegate_address, 5).assert_user_error("Quota exceeded");
+    quota.consume(&delegate_address, 4).assert_ok();
+    quota.check_remaining_quota(&user_address, 0);
+
+    quota
+        .setup
+        .user_tx(&user_address, |sc| sc.remove_delegate(managed_buffer!(PLAN_ID), managed_address!(&delegate_address)))
+        .assert_ok();
+
+    quota.setup.set_timestamp(START_TIMESTAMP + 7 * DAY);
+    quota.check_remaining_quota(&user_address, QUOTA);
+    quota.check_remaining_quota(&delegate_address, 0);
+    quota.consume(&delegate_address, 1).assert_user_error("Quota exceeded");
+}
@@ -0,0 +1,88 @@
+mod setup;
+
+use elrond_wasm::types::EgldOrEsdtTokenIdentifier;
+use elrond_wasm_debug::{managed_biguint, rust_biguint, DebugApi};
+use setup::*;
+use subscriptions::escrow::EscrowModule;
+use subscriptions::storage::StorageModule;
+
+const DAY_START_TIMESTAMP: u64 = (START_TIMESTAMP / DAY + 1) * DAY;
+
+fn check_escrow_revenue<SubscriptionsBuilder>(setup: &mut SubscriptionsSetup<SubscriptionsBuilder>, earned: u64, deferred: u64, claimed: u64)
+where
+    SubscriptionsBuilder: 'static + Copy + Fn() -> subscriptions::ContractObj<DebugApi>,
+{
+    setup.query(|sc| {
+        let (escrow_earned, escrow_deferred) = sc.get_escrow_revenue(&EgldOrEsdtTokenIdentifier::egld());
+
+        assert_eq!(escrow_earned, managed_biguint!(earned));
+        assert_eq!(escrow_deferred, managed_biguint!(deferred));
+        assert_eq!(sc.escrow_claimed(&EgldOrEsdtTokenIdentifier::egld()).get(), managed_biguint!(claimed));
+    });
+}
+
+fn claim_revenue<SubscriptionsBuilder>(setup: &mut SubscriptionsSetup<SubscriptionsBuilder>) -> elrond_wasm_debug::tx_mock::TxResult
+where
+    SubscriptionsBuilder: 'static + Copy + Fn() -> subscriptions::ContractObj<DebugApi>,
+{
+    let payment_address = setup.payment_address.clone();
+
+    setup.user_tx(&payment_address, |sc| sc.claim_revenue())
+}
+
+#[test]
+fn escrow_linear_release_test() {
+    let mut setup = setup_subscriptions(subscriptions::contract_obj);
+    let user_address = setup.create_user(PLAN_PRICE * 2);
+    let daily_amount = PLAN_PRICE / 30;
+
+    setup.owner_tx(|sc| sc.set_escrow_mode(true)).assert_ok();
+
+    setup.set_timestamp(DAY_START_TIMESTAMP);
+    setup.pay_egld(&user_address, PLAN_PRICE).assert_ok();
+
+    check_escrow_revenue(&mut setup, 0, PLAN_PRICE, 0);
+    claim_revenue(&mut setup).assert_user_error("Nothing to claim");
+    setup.blockchain_wrapper.check_egld_balance(&setup.payment_address, &rust_biguint!(0u64));
+
+    // the renewal is released over the extended period only
+    setup.set_timestamp(DAY_START_TIMESTAMP + 5 * DAY);
+    setup.pay_egld(&user_address, PLAN_PRICE).assert_ok();
+
+    setup.set_timestamp(DAY_START_TIMESTAMP + 10 * DAY + DAY / 2);
+    check_escrow_revenue(&mut setup, 10 * daily_amount, 2 * PLAN_PRICE - 10 * daily_amount, 0);
+    claim_revenue(&mut setup).assert_ok();
+    check_escrow_revenue(&mut setup, 0, 2 * PLAN_PRICE - 10 * daily_amount, 10 * daily_amount);
+    setup.blockchain_wrapper.check_egld_balance(&setup.payment_address, &rust_biguint!(10 * daily_amount));
+
+    // the first payment is fully earned, including the division remainder, and the renewal is half earned
+    setup.set_timestamp(DAY_START_TIMESTAMP + 45 * DAY);
+    check_escrow_revenue(
+        &mut setup,
+        PLAN_PRICE - 10 * daily_amount + 15 * daily_amount,
+        PLAN_PRICE - 15 * daily_amount,
+        10 * daily_amount,
+    );
+
+    setup.set_timestamp(DAY_START_TIMESTAMP + 60 * DAY);
+    check_escrow_revenue(&mut setup, 2 * PLAN_PRICE - 10 * daily_amount, 0, 10 * daily_amount);
+    claim_revenue(&mut setup).assert_ok();
+    check_escrow_revenue(&mut setup, 0, 0, 2 * PLAN_PRICE);
+    setup.blockchain_wrapper.check_egld_balance(&setup.payment_address, &rust_biguint!(2 * PLAN_PRICE));
+
+    claim_revenue(&mut setup).assert_user_error("Nothing to claim");
+}
+
+#[test]
+fn escrow_claim_caller_test() {
+    let mut setup = setup_subscriptions(subscriptions::contract_obj);
+    let user_address = setup.create_user(PLAN_PRICE);
+
+    setup.owner_tx(|sc| sc.set_escrow_mode(true)).assert_ok();
+    setup.pay_egld(&user_address, PLAN_PRICE).assert_ok();
+    setup.set_timestamp(START_TIMESTAMP + 10 * DAY);
+
+    setup
+        .user_tx(&user_address, |sc| sc.claim_revenue())
+        .assert_user_error("Only the payment address can claim the revenue");
+}
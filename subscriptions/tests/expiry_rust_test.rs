@@ -0,0 +1,126 @@
+mod setup;
+
+use elrond_wasm::types::Address;
+use elrond_wasm_debug::{managed_buffer, DebugApi};
+use setup::*;
+use subscriptions::views::ViewsModule;
+use subscriptions::Subscriptions;
+
+const HOUR: u64 = 60 * 60;
+
+fn expiring_addresses<SubscriptionsBuilder>(
+    setup: &mut SubscriptionsSetup<SubscriptionsBuilder>,
+    from: u64,
+    to: u64,
+    start: usize,
+    count: usize,
+) -> Vec<(Address, u64)>
+where
+    SubscriptionsBuilder: 'static + Copy + Fn() -> subscriptions::ContractObj<DebugApi>,
+{
+    let mut expiring = Vec::new();
+
+    setup.query(|sc| {
+        for entry in sc.get_expiring_between(from, to, start, count).into_iter() {
+            assert_eq!(entry.plan_id, managed_buffer!(PLAN_ID));
+
+            expiring.push((entry.address.to_address(), entry.expires_at));
+        }
+    });
+
+    expiring
+}
+
+#[test]
+fn expiring_between_test() {
+    let mut setup = setup_subscriptions(subscriptions::contract_obj);
+    let first_address = setup.create_user(PLAN_PRICE * 2);
+    let second_address = setup.create_user(PLAN_PRICE);
+    let third_address = setup.create_user(PLAN_PRICE);
+
+    setup.pay_egld(&first_address, PLAN_PRICE).assert_ok();
+    setup.set_timestamp(START_TIMESTAMP + 2 * HOUR);
+    setup.pay_egld(&second_address, PLAN_PRICE).assert_ok();
+    setup.set_timestamp(START_TIMESTAMP + 2 * DAY);
+    setup.pay_egld(&third_address, PLAN_PRICE).assert_ok();
+
+    let first_expiry = START_TIMESTAMP + PLAN_VALIDITY;
+    let second_expiry = START_TIMESTAMP + 2 * HOUR + PLAN_VALIDITY;
+    let third_expiry = START_TIMESTAMP + 2 * DAY + PLAN_VALIDITY;
+
+    assert_eq!(
+        expiring_addresses(&mut setup, first_expiry, third_expiry + 1, 0, 10),
+        vec![
+            (first_address.clone(), first_expiry),
+            (second_address.clone(), second_expiry),
+            (third_address.clone(), third_expiry),
+        ]
+    );
+
+    // the upper bound is exclusive and the lower bound is inclusive
+    assert_eq!(
+        expiring_addresses(&mut setup, first_expiry, second_expiry, 0, 10),
+        vec![(first_address.clone(), first_expiry)]
+    );
+    assert_eq!(
+        expiring_addresses(&mut setup, second_expiry, third_expiry, 0, 10),
+        vec![(second_address.clone(), second_expiry)]
+    );
+
+    // the results are paged
+    assert_eq!(
+        expiring_addresses(&mut setup, first_expiry, third_expiry + 1, 1, 1),
+        vec![(second_address.clone(), second_expiry)]
+    );
+    assert_eq!(
+        expiring_addresses(&mut setup, first_expiry, third_expiry + 1, 3, 10),
+        vec![]
+    );
+
+    // an empty range or page returns nothing
+    assert_eq!(expiring_addresses(&mut setup, third_expiry, first_expiry, 0, 10), vec![]);
+    assert_eq!(expiring_addresses(&mut setup, first_expiry, third_expiry + 1, 0, 0), vec![]);
+
+    // a renewal moves the subscription to its new expiry
+    setup.pay_egld(&first_address, PLAN_PRICE).assert_ok();
+
+    assert_eq!(
+        expiring_addresses(&mut setup, first_expiry, third_expiry + 1, 0, 10),
+        vec![(second_address.clone(), second_expiry), (third_address.clone(), third_expiry)]
+    );
+    assert_eq!(
+        expiring_addresses(&mut setup, first_expiry + PLAN_VALIDITY, first_expiry + PLAN_VALIDITY + 1, 0, 10),
+        vec![(first_address, first_expiry + PLAN_VALIDITY)]
+    );
+}
+
+#[test]
+fn expired_count_test() {
+    let mut setup = setup_subscriptions(subscriptions::contract_obj);
+    let first_address = setup.create_user(PLAN_PRICE);
+    let second_address = setup.create_user(PLAN_PRICE);
+
+    setup.pay_egld(&first_address, PLAN_PRICE).assert_ok();
+    setup.set_timestamp(START_TIMESTAMP + 2 * DAY);
+    setup.pay_egld(&second_address, PLAN_PRICE).assert_ok();
+
+    setup.set_timestamp(START_TIMESTAMP + PLAN_VALIDITY - 1);
+    setup.query(|sc| assert_eq!(sc.get_expired_count(&managed_buffer!(PLAN_ID)), 0));
+
+    setup.set_timestamp(START_TIMESTAMP + PLAN_VALIDITY);
+    setup.query(|sc| assert_eq!(sc.get_expired_count(&managed_buffer!(PLAN_ID)), 1));
+
+    setup.set_timestamp(START_TIMESTAMP + PLAN_VALIDITY + 3 * DAY);
+    setup.query(|sc| assert_eq!(sc.get_expired_count(&managed_buffer!(PLAN_ID)), 2));
+
+    // the pruned subscriptions are no longer counted
+    setup
+        .user_tx(&first_address, |sc| {
+            sc.prune_expired_subscriptions(100);
+        })
+        .assert_ok();
+    setup.query(|sc| {
+        assert_eq!(sc.get_expired_count(&managed_buffer!(PLAN_ID)), 0);
+        assert!(sc.get_expiring_between(START_TIMESTAMP, START_TIMESTAMP + PLAN_VALIDITY + 3 * DAY, 0, 10).is_empty());
+    });
+}
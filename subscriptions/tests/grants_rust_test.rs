@@ -0,0 +1,132 @@
+mod setup;
+
+use elrond_wasm::elrond_codec::multi_types::MultiValue3;
+use elrond_wasm::types::MultiValueEncoded;
+use elrond_wasm_debug::{managed_address, managed_biguint, managed_buffer, managed_token_id, rust_biguint};
+use setup::*;
+use subscriptions::grants::GrantsModule;
+use subscriptions::staking::StakingModule;
+use subscriptions::storage::StorageModule;
+use subscriptions::views::ViewsModule;
+
+const STAKE_TOKEN: &[u8] = b"STAKE-123456";
+const STAKE_AMOUNT: u64 = 1_000;
+
+#[test]
+fn grant_subscription_test() {
+    let mut setup = setup_subscriptions(subscriptions::contract_obj);
+    let manager_address = setup.create_user(0);
+    let user_address = setup.create_user(0);
+    let other_address = setup.create_user(0);
+
+    setup
+        .user_tx(&manager_address, |sc| sc.grant_subscription(managed_address!(&user_address), managed_buffer!(PLAN_ID), DAY))
+        .assert_user_error("Only the owner or a grant manager can manage grants");
+
+    setup
+        .owner_tx(|sc| sc.add_grant_manager(managed_address!(&manager_address)))
+        .assert_ok();
+    setup
+        .user_tx(&manager_address, |sc| sc.grant_subscription(managed_address!(&user_address), managed_buffer!(PLAN_ID), 0))
+        .assert_user_error("Invalid duration");
+    setup
+        .user_tx(&manager_address, |sc| sc.grant_subscription(managed_address!(&user_address), managed_buffer!(b"missing"), DAY))
+        .assert_user_error("This plan does not exist");
+
+    setup
+        .user_tx(&manager_address, |sc| {
+            let mut grants = MultiValueEncoded::new();
+            grants.push(MultiValue3::from((managed_address!(&user_address), managed_buffer!(PLAN_ID), 2 * DAY)));
+            grants.push(MultiValue3::from((managed_address!(&other_address), managed_buffer!(PLAN_ID), DAY)));
+            grants.push(MultiValue3::from((managed_address!(&user_address), managed_buffer!(PLAN_ID), 3 * DAY)));
+
+            sc.grant_subscriptions(grants);
+        })
+        .assert_ok();
+
+    setup.query(|sc| {
+        let user_plan = sc.user_plans(&managed_address!(&user_address), &managed_buffer!(PLAN_ID)).get();
+
+        assert_eq!(user_plan.expires_at, START_TIMESTAMP + 5 * DAY);
+        assert_eq!(sc.user_granted_time(&managed_address!(&user_address), &managed_buffer!(PLAN_ID)).get(), 5 * DAY);
+        assert_eq!(sc.user_granted_time(&managed_address!(&other_address), &managed_buffer!(PLAN_ID)).get(), DAY);
+    });
+
+    setup
+        .owner_tx(|sc| sc.remove_grant_manager(&managed_address!(&manager_address)))
+        .assert_ok();
+    setup
+        .user_tx(&manager_address, |sc| sc.grant_subscription(managed_address!(&user_address), managed_buffer!(PLAN_ID), DAY))
+        .assert_user_error("Only the owner or a grant manager can manage grants");
+}
+
+#[test]
+fn revoke_subscription_test() {
+    let mut setup = setup_subscriptions(subscriptions::contract_obj);
+    let user_address = setup.create_user(PLAN_PRICE);
+
+    setup
+        .owner_tx(|sc| sc.revoke_subscription(managed_address!(&user_address), managed_buffer!(PLAN_ID)))
+        .assert_user_error("The user is not subscribed to this plan");
+
+    setup.pay_egld(&user_address, PLAN_PRICE).assert_ok();
+    setup.set_timestamp(START_TIMESTAMP + DAY);
+    setup
+        .owner_tx(|sc| sc.revoke_subscription(managed_address!(&user_address), managed_buffer!(PLAN_ID)))
+        .assert_ok();
+
+    setup.query(|sc| {
+        assert!(!sc.has_active_subscription(&managed_address!(&user_address), &managed_buffer!(PLAN_ID)));
+        assert_eq!(
+            sc.user_plans(&managed_address!(&user_address), &managed_buffer!(PLAN_ID)).get().expires_at,
+            START_TIMESTAMP + DAY
+        );
+    });
+    setup
+        .owner_tx(|sc| sc.revoke_subscription(managed_address!(&user_address), managed_buffer!(PLAN_ID)))
+        .assert_user_error("The user subscription is not active");
+}
+
+#[test]
+fn revoke_staked_subscription_test() {
+    let mut setup = setup_subscriptions(subscriptions::contract_obj);
+    let staker_address = setup.create_user(0);
+
+    setup
+        .blockchain_wrapper
+        .set_esdt_balance(&staker_address, STAKE_TOKEN, &rust_biguint!(STAKE_AMOUNT));
+    setup
+        .owner_tx(|sc| {
+            sc.set_subscription_plan_stake_requirement(
+                managed_buffer!(PLAN_ID),
+                managed_token_id!(STAKE_TOKEN),
+                managed_biguint!(STAKE_AMOUNT),
+                DAY,
+            );
+        })
+        .assert_ok();
+    setup
+        .blockchain_wrapper
+        .execute_esdt_transfer(
+            &staker_address,
+            &setup.subscriptions_wrapper,
+            STAKE_TOKEN,
+            0,
+            &rust_biguint!(STAKE_AMOUNT),
+            |sc| sc.stake(managed_buffer!(PLAN_ID), MultiValueEncoded::new()),
+        )
+        .assert_ok();
+
+    // revoking would be undone by the unstake, which gives back the unbonding period
+    setup
+        .owner_tx(|sc| sc.revoke_subscription(managed_address!(&staker_address), managed_buffer!(PLAN_ID)))
+        .assert_user_error("The plan is active through a stake");
+
+    setup
+        .user_tx(&staker_address, |sc| sc.unstake(managed_buffer!(PLAN_ID)))
+        .assert_ok();
+    setup
+        .owner_tx(|sc| sc.revoke_subscription(managed_address!(&staker_address), managed_buffer!(PLAN_ID)))
+        .assert_ok();
+    setup.query(|sc| assert!(!sc.has_active_subscription(&managed_address!(&staker_address), &managed_buffer!(PLAN_ID))));
+}
@@ -0,0 +1,25 @@
+mod setup;
+
+use elrond_wasm::types::EgldOrEsdtTokenIdentifier;
+use elrond_wasm_debug::{managed_address, managed_biguint};
+use setup::*;
+use subscriptions::storage::StorageModule;
+
+#[test]
+fn register_historic_data_test() {
+    let mut setup = setup_subscriptions(subscriptions::contract_obj);
+    let user_address = setup.create_user(PLAN_PRICE * 2);
+
+    setup.pay_egld(&user_address, PLAN_PRICE).assert_ok();
+    setup.pay_egld(&user_address, PLAN_PRICE).assert_ok();
+
+    setup.query(|sc| {
+        let egld = EgldOrEsdtTokenIdentifier::egld();
+
+        assert!(sc.payment_token_ids().contains(&egld));
+        assert_eq!(sc.payment_token_ids().len(), 1);
+        assert_eq!(sc.payment_token_amounts(&egld).get(), managed_biguint!(PLAN_PRICE * 2));
+        assert!(sc.user_payment_tokens(&managed_address!(&user_address)).contains(&egld));
+        assert_eq!(sc.user_payment_amounts(&managed_address!(&user_address), &egld).get(), managed_biguint!(PLAN_PRICE * 2));
+    });
+}
@@ -0,0 +1,160 @@
+mod setup;
+
+use elrond_wasm::elrond_codec::multi_types::OptionalValue;
+use elrond_wasm::types::{Address, EgldOrEsdtTokenIdentifier, MultiValueEncoded};
+use elrond_wasm_debug::{managed_biguint, managed_buffer, managed_token_id, rust_biguint, tx_mock::{TxInputESDT, TxResult}, DebugApi};
+use setup::*;
+use subscriptions::holders::HoldersModule;
+use subscriptions::Subscriptions;
+
+const PAYMENT_TOKEN: &[u8] = b"USDC-123456";
+const COLLECTION: &[u8] = b"HOLDER-123456";
+const OTHER_COLLECTION: &[u8] = b"OTHER-123456";
+const HOLDER_NONCE: u64 = 1;
+const DISCOUNT_BPS: u64 = 2_000;
+const DISCOUNTED_PRICE: u64 = PLAN_PRICE * (10_000 - DISCOUNT_BPS) / 10_000;
+
+fn setup_holders<SubscriptionsBuilder>(
+    subscriptions_builder: SubscriptionsBuilder,
+    gated: bool,
+    discount_bps: u64,
+) -> SubscriptionsSetup<SubscriptionsBuilder>
+where
+    SubscriptionsBuilder: 'static + Copy + Fn() -> subscriptions::ContractObj<DebugApi>,
+{
+    let mut setup = setup_subscriptions(subscriptions_builder);
+
+    setup
+        .owner_tx(|sc| {
+            sc.set_subscription_plan_price(
+                managed_buffer!(PLAN_ID),
+                &EgldOrEsdtTokenIdentifier::esdt(managed_token_id!(PAYMENT_TOKEN)),
+                managed_biguint!(PLAN_PRICE),
+            );
+            sc.set_subscription_plan_holder_benefits(
+                managed_buffer!(PLAN_ID),
+                managed_token_id!(COLLECTION),
+                gated,
+                discount_bps,
+            );
+        })
+        .assert_ok();
+
+    setup
+}
+
+fn create_holder<SubscriptionsBuilder>(setup: &mut SubscriptionsSetup<SubscriptionsBuilder>, collection: &[u8]) -> Address
+where
+    SubscriptionsBuilder: 'static + Copy + Fn() -> subscriptions::ContractObj<DebugApi>,
+{
+    let holder_address = setup.create_user(0);
+    setup
+        .blockchain_wrapper
+        .set_esdt_balance(&holder_address, PAYMENT_TOKEN, &rust_biguint!(PLAN_PRICE * 2));
+    setup
+        .blockchain_wrapper
+        .set_nft_balance(&holder_address, collection, HOLDER_NONCE, &rust_biguint!(1u64), &0u8);
+
+    holder_address
+}
+
+fn pay_as_holder<SubscriptionsBuilder>(
+    setup: &mut SubscriptionsSetup<SubscriptionsBuilder>,
+    user_address: &Address,
+    amount: u64,
+    collection: &[u8],
+) -> TxResult
+where
+    SubscriptionsBuilder: 'static + Copy + Fn() -> subscriptions::ContractObj<DebugApi>,
+{
+    let transfers = [
+        TxInputESDT {
+            token_identifier: PAYMENT_TOKEN.to_vec(),
+            nonce: 0,
+            value: rust_biguint!(amount),
+        },
+        TxInputESDT {
+            token_identifier: collection.to_vec(),
+            nonce: HOLDER_NONCE,
+            value: rust_biguint!(1u64),
+        },
+    ];
+
+    setup
+        .blockchain_wrapper
+        .execute_esdt_multi_transfer(user_address, &setup.subscriptions_wrapper, &transfers, |sc| {
+            sc.pay_as_holder(managed_buffer!(PLAN_ID), OptionalValue::None);
+        })
+}
+
+fn pay_esdt<SubscriptionsBuilder>(setup: &mut SubscriptionsSetup<SubscriptionsBuilder>, user_address: &Address, amount: u64) -> TxResult
+where
+    SubscriptionsBuilder: 'static + Copy + Fn() -> subscriptions::ContractObj<DebugApi>,
+{
+    setup
+        .blockchain_wrapper
+        .execute_esdt_transfer(user_address, &setup.subscriptions_wrapper, PAYMENT_TOKEN, 0, &rust_biguint!(amount), |sc| {
+            sc.pay_with_esdt(managed_buffer!(PLAN_ID), MultiValueEncoded::new());
+        })
+}
+
+fn pay_as_holder_without_proof<SubscriptionsBuilder>(
+    setup: &mut SubscriptionsSetup<SubscriptionsBuilder>,
+    user_address: &Address,
+) -> TxResult
+where
+    SubscriptionsBuilder: 'static + Copy + Fn() -> subscriptions::ContractObj<DebugApi>,
+{
+    setup
+        .blockchain_wrapper
+        .execute_esdt_transfer(user_address, &setup.subscriptions_wrapper, PAYMENT_TOKEN, 0, &rust_biguint!(DISCOUNTED_PRICE), |sc| {
+            sc.pay_as_holder(managed_buffer!(PLAN_ID), OptionalValue::None);
+        })
+}
+
+#[test]
+fn holder_discount_test() {
+    let mut setup = setup_holders(subscriptions::contract_obj, false, DISCOUNT_BPS);
+    let holder_address = create_holder(&mut setup, COLLECTION);
+
+    pay_as_holder(&mut setup, &holder_address, PLAN_PRICE, COLLECTION).assert_user_error("Invalid payment amount");
+    pay_as_holder(&mut setup, &holder_address, DISCOUNTED_PRICE, COLLECTION).assert_ok();
+
+    setup
+        .blockchain_wrapper
+        .check_esdt_balance(&holder_address, PAYMENT_TOKEN, &rust_biguint!(PLAN_PRICE * 2 - DISCOUNTED_PRICE));
+    setup
+        .blockchain_wrapper
+        .check_esdt_balance(&setup.payment_address, PAYMENT_TOKEN, &rust_biguint!(DISCOUNTED_PRICE));
+    setup
+        .blockchain_wrapper
+        .check_nft_balance::<u8>(&holder_address, COLLECTION, HOLDER_NONCE, &rust_biguint!(1u64), None);
+
+    // the regular payment keeps the full price, even for holders
+    pay_esdt(&mut setup, &holder_address, DISCOUNTED_PRICE).assert_user_error("Invalid payment amount");
+    pay_esdt(&mut setup, &holder_address, PLAN_PRICE).assert_ok();
+}
+
+#[test]
+fn holder_proof_test() {
+    let mut setup = setup_holders(subscriptions::contract_obj, false, DISCOUNT_BPS);
+    let other_holder_address = create_holder(&mut setup, OTHER_COLLECTION);
+
+    pay_as_holder(&mut setup, &other_holder_address, DISCOUNTED_PRICE, OTHER_COLLECTION)
+        .assert_user_error("Invalid collection token");
+    pay_as_holder_without_proof(&mut setup, &other_holder_address)
+        .assert_user_error("Expected a payment and a token of the collection");
+}
+
+#[test]
+fn holder_gated_plan_test() {
+    let mut setup = setup_holders(subscriptions::contract_obj, true, 0);
+    let user_address = create_holder(&mut setup, OTHER_COLLECTION);
+    let holder_address = create_holder(&mut setup, COLLECTION);
+
+    pay_esdt(&mut setup, &user_address, PLAN_PRICE).assert_user_error("This plan is only available to collection holders");
+    pay_as_holder(&mut setup, &holder_address, PLAN_PRICE, COLLECTION).assert_ok();
+
+    setup.owner_tx(|sc| sc.remove_subscription_plan_holder_benefits(managed_buffer!(PLAN_ID))).assert_ok();
+    pay_esdt(&mut setup, &user_address, PLAN_PRICE).assert_ok();
+}
@@ -0,0 +1,122 @@
+mod setup;
+
+use elrond_wasm::types::{EgldOrEsdtTokenIdentifier, MultiValueEncoded};
+use elrond_wasm_debug::{managed_address, managed_biguint, managed_buffer};
+use setup::*;
+use subscriptions::bundles::BundlesModule;
+use subscriptions::loyalty::LoyaltyModule;
+use subscriptions::private_plans::PrivatePlansModule;
+use subscriptions::renewals::RenewalsModule;
+use subscriptions::storage::StorageModule;
+use subscriptions::structs::RenewalMode;
+use subscriptions::Subscriptions;
+
+const REWARD_PLAN_ID: &[u8] = b"reward";
+const POINTS_PER_DAY: u64 = 10;
+
+#[test]
+fn redeem_points_test() {
+    let mut setup = setup_subscriptions(subscriptions::contract_obj);
+    let user_address = setup.create_user(PLAN_PRICE);
+    let other_address = setup.create_user(0);
+
+    setup
+        .owner_tx(|sc| {
+            sc.set_loyalty_point_rate(&EgldOrEsdtTokenIdentifier::egld(), managed_biguint!(1u64));
+            sc.add_plan(managed_buffer!(REWARD_PLAN_ID), PLAN_VALIDITY);
+            sc.set_subscription_plan_points_per_day(managed_buffer!(REWARD_PLAN_ID), managed_biguint!(POINTS_PER_DAY));
+            sc.set_subscription_plan_private(managed_buffer!(REWARD_PLAN_ID), true);
+            sc.add_subscription_plan_prerequisite(managed_buffer!(REWARD_PLAN_ID), managed_buffer!(PLAN_ID));
+        })
+        .assert_ok();
+
+    setup.pay_egld(&user_address, PLAN_PRICE).assert_ok();
+    setup.query(|sc| assert_eq!(sc.loyalty_points(&managed_address!(&user_address)).get(), managed_biguint!(PLAN_PRICE)));
+
+    setup
+        .user_tx(&user_address, |sc| sc.redeem_points(managed_buffer!(REWARD_PLAN_ID), managed_biguint!(25u64), MultiValueEncoded::new()))
+        .assert_user_error("This plan is private");
+
+    setup
+        .owner_tx(|sc| sc.set_subscription_plan_private(managed_buffer!(REWARD_PLAN_ID), false))
+        .assert_ok();
+    setup
+        .user_tx(&other_address, |sc| sc.redeem_points(managed_buffer!(REWARD_PLAN_ID), managed_biguint!(25u64), MultiValueEncoded::new()))
+        .assert_user_error("A prerequisite plan is not active");
+
+    setup
+        .owner_tx(|sc| sc.set_status(false))
+        .assert_ok();
+    setup
+        .user_tx(&user_address, |sc| sc.redeem_points(managed_buffer!(REWARD_PLAN_ID), managed_biguint!(25u64), MultiValueEncoded::new()))
+        .assert_user_error("Contract is not enabled");
+
+    setup
+        .owner_tx(|sc| sc.set_status(true))
+        .assert_ok();
+    setup
+        .user_tx(&user_address, |sc| sc.redeem_points(managed_buffer!(REWARD_PLAN_ID), managed_biguint!(25u64), MultiValueEncoded::new()))
+        .assert_ok();
+
+    // only the points for whole days are used
+    setup.query(|sc| {
+        let user_plan = sc.user_plans(&managed_address!(&user_address), &managed_buffer!(REWARD_PLAN_ID)).get();
+
+        assert_eq!(user_plan.expires_at, START_TIMESTAMP + 2 * DAY);
+        assert_eq!(sc.loyalty_points(&managed_address!(&user_address)).get(), managed_biguint!(PLAN_PRICE - 2 * POINTS_PER_DAY));
+    });
+}
+
+#[test]
+fn redeem_points_renewal_settings_test() {
+    let mut setup = setup_subscriptions(subscriptions::contract_obj);
+    let user_address = setup.create_user(PLAN_PRICE);
+
+    setup
+        .owner_tx(|sc| {
+            sc.set_loyalty_point_rate(&EgldOrEsdtTokenIdentifier::egld(), managed_biguint!(1u64));
+            sc.add_plan(managed_buffer!(REWARD_PLAN_ID), PLAN_VALIDITY);
+            sc.set_subscription_plan_renewal_mode(managed_buffer!(REWARD_PLAN_ID), RenewalMode::Lifetime);
+        })
+        .assert_ok();
+    setup
+        .owner_tx(|sc| sc.set_subscription_plan_points_per_day(managed_buffer!(REWARD_PLAN_ID), managed_biguint!(POINTS_PER_DAY)))
+        .assert_user_error("Lifetime plans cannot be redeemed with points");
+
+    setup
+        .owner_tx(|sc| {
+            sc.set_subscription_plan_renewal_mode(managed_buffer!(REWARD_PLAN_ID), RenewalMode::Reset);
+            sc.set_subscription_plan_max_horizon(managed_buffer!(REWARD_PLAN_ID), 3 * DAY);
+            sc.set_subscription_plan_points_per_day(managed_buffer!(REWARD_PLAN_ID), managed_biguint!(POINTS_PER_DAY));
+        })
+        .assert_ok();
+    setup.pay_egld(&user_address, PLAN_PRICE).assert_ok();
+
+    setup
+        .user_tx(&user_address, |sc| sc.redeem_points(managed_buffer!(REWARD_PLAN_ID), managed_biguint!(2 * POINTS_PER_DAY), MultiValueEncoded::new()))
+        .assert_ok();
+
+    // the redeemed days extend the subscription of a reset plan, up to the plan maximum horizon
+    setup.set_timestamp(START_TIMESTAMP + DAY);
+    setup
+        .user_tx(&user_address, |sc| sc.redeem_points(managed_buffer!(REWARD_PLAN_ID), managed_biguint!(2 * POINTS_PER_DAY), MultiValueEncoded::new()))
+        .assert_ok();
+    setup
+        .user_tx(&user_address, |sc| sc.redeem_points(managed_buffer!(REWARD_PLAN_ID), managed_biguint!(POINTS_PER_DAY), MultiValueEncoded::new()))
+        .assert_user_error("The plan maximum prepaid period is exceeded");
+
+    setup.query(|sc| {
+        let user_plan = sc.user_plans(&managed_address!(&user_address), &managed_buffer!(REWARD_PLAN_ID)).get();
+
+        assert_eq!(user_plan.expires_at, START_TIMESTAMP + 4 * DAY);
+        assert_eq!(sc.loyalty_points(&managed_address!(&user_address)).get(), managed_biguint!(PLAN_PRICE - 4 * POINTS_PER_DAY));
+    });
+
+    // the plan cannot be redeemed once it is made a lifetime plan
+    setup
+        .owner_tx(|sc| sc.set_subscription_plan_renewal_mode(managed_buffer!(REWARD_PLAN_ID), RenewalMode::Lifetime))
+        .assert_ok();
+    setup
+        .user_tx(&user_address, |sc| sc.redeem_points(managed_buffer!(REWARD_PLAN_ID), managed_biguint!(POINTS_PER_DAY), MultiValueEncoded::new()))
+        .assert_user_error("Lifetime plans cannot be redeemed with points");
+}
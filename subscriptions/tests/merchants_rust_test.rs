@@ -0,0 +1,131 @@
+mod setup;
+
+use elrond_wasm::elrond_codec::multi_types::OptionalValue;
+use elrond_wasm::types::{EgldOrEsdtTokenIdentifier, MultiValueEncoded};
+use elrond_wasm_debug::{managed_address, managed_biguint, managed_buffer, managed_token_id};
+use setup::*;
+use subscriptions::grants::GrantsModule;
+use subscriptions::holders::HoldersModule;
+use subscriptions::merchants::MerchantsModule;
+use subscriptions::migrations::MigrationsModule;
+use subscriptions::storage::StorageModule;
+use subscriptions::structs::UserPlan;
+use subscriptions::Subscriptions;
+
+const MERCHANT_PLAN_ID: &[u8] = b"1/pro";
+
+#[test]
+fn plan_id_separator_test() {
+    let mut setup = setup_subscriptions(subscriptions::contract_obj);
+    let merchant_address = setup.create_user(0);
+    let merchant_payment_address = merchant_address.clone();
+
+    setup
+        .user_tx(&merchant_address, |sc| {
+            sc.register_merchant(managed_address!(&merchant_payment_address));
+        })
+        .assert_ok();
+
+    setup
+        .user_tx(&merchant_address, |sc| {
+            sc.add_merchant_plan(managed_buffer!(b"pro/yearly"), PLAN_VALIDITY);
+        })
+        .assert_user_error("Invalid plan id");
+    setup
+        .user_tx(&merchant_address, |sc| {
+            let merchant_plan_id = sc.add_merchant_plan(managed_buffer!(b"pro"), PLAN_VALIDITY);
+
+            assert_eq!(merchant_plan_id, managed_buffer!(MERCHANT_PLAN_ID));
+        })
+        .assert_ok();
+
+    setup
+        .owner_tx(|sc| sc.add_plan(managed_buffer!(MERCHANT_PLAN_ID), PLAN_VALIDITY))
+        .assert_user_error("Invalid plan id");
+    setup
+        .owner_tx(|sc| sc.add_plan(managed_buffer!(b"1/yearly"), PLAN_VALIDITY))
+        .assert_user_error("Invalid plan id");
+    setup
+        .owner_tx(|sc| sc.add_plan(managed_buffer!(b"a-plan-id-longer-than-one-chunk-of-bytes/yearly"), PLAN_VALIDITY))
+        .assert_user_error("Invalid plan id");
+    setup
+        .owner_tx(|sc| sc.add_plan(managed_buffer!(b"a-plan-id-longer-than-one-chunk-of-bytes"), PLAN_VALIDITY))
+        .assert_ok();
+
+    let user_address = setup.create_user(0);
+
+    setup.owner_tx(|sc| sc.start_migration()).assert_ok();
+    setup
+        .owner_tx(|sc| {
+            let mut user_plans = MultiValueEncoded::new();
+            let user_plan = UserPlan {
+                plan_id: managed_buffer!(b"2/pro"),
+                expires_at: START_TIMESTAMP + DAY,
+                first_subscribed: START_TIMESTAMP,
+                last_subscribed: START_TIMESTAMP,
+                paused_until: 0,
+            };
+            user_plans.push((managed_address!(&user_address), user_plan).into());
+
+            sc.import_user_plans(user_plans);
+        })
+        .assert_user_error("This plan does not exist");
+
+    setup.query(|sc| assert!(sc.user_plan_ids(&managed_address!(&user_address)).is_empty()));
+}
+
+#[test]
+fn owner_merchant_plan_settings_test() {
+    let mut setup = setup_subscriptions(subscriptions::contract_obj);
+    let merchant_address = setup.create_user(0);
+    let merchant_payment_address = merchant_address.clone();
+
+    setup
+        .user_tx(&merchant_address, |sc| {
+            sc.register_merchant(managed_address!(&merchant_payment_address));
+            sc.add_merchant_plan(managed_buffer!(b"pro"), PLAN_VALIDITY);
+        })
+        .assert_ok();
+
+    setup
+        .owner_tx(|sc| {
+            sc.set_subscription_plan_price(
+                managed_buffer!(MERCHANT_PLAN_ID),
+                &EgldOrEsdtTokenIdentifier::egld(),
+                managed_biguint!(1u64),
+            );
+        })
+        .assert_user_error("This plan is managed by its merchant");
+    setup
+        .owner_tx(|sc| sc.archive_subscription_plan(managed_buffer!(MERCHANT_PLAN_ID)))
+        .assert_user_error("This plan is managed by its merchant");
+    setup
+        .owner_tx(|sc| sc.remove_plan(managed_buffer!(MERCHANT_PLAN_ID)))
+        .assert_user_error("This plan is managed by its merchant");
+    setup
+        .owner_tx(|sc| {
+            sc.set_subscription_plan_holder_benefits(managed_buffer!(MERCHANT_PLAN_ID), managed_token_id!(b"HOLDER-123456"), true, 0);
+        })
+        .assert_user_error("This plan is managed by its merchant");
+    setup
+        .owner_tx(|sc| sc.grant_subscription(managed_address!(&merchant_payment_address), managed_buffer!(MERCHANT_PLAN_ID), PLAN_VALIDITY))
+        .assert_user_error("This plan is managed by its merchant");
+    setup
+        .owner_tx(|sc| {
+            sc.release_subscription_plan_subscribers(managed_buffer!(PLAN_ID), 1, OptionalValue::Some(managed_buffer!(MERCHANT_PLAN_ID)));
+        })
+        .assert_user_error("This plan is managed by its merchant");
+
+    // the merchant keeps managing its plan
+    setup
+        .user_tx(&merchant_address, |sc| {
+            sc.set_merchant_plan_price(managed_buffer!(b"pro"), &EgldOrEsdtTokenIdentifier::egld(), managed_biguint!(PLAN_PRICE));
+            sc.archive_merchant_plan(managed_buffer!(b"pro"));
+        })
+        .assert_ok();
+
+    // the owner plans are not affected
+    setup
+        .owner_tx(|sc| sc.archive_subscription_plan(managed_buffer!(PLAN_ID)))
+        .assert_ok();
+}
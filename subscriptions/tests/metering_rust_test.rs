@@ -0,0 +1,190 @@
+mod setup;
+
+use elrond_wasm::types::{EgldOrEsdtTokenIdentifier, MultiValueEncoded};
+use elrond_wasm_debug::{managed_address, managed_biguint, managed_buffer, rust_biguint, tx_mock::TxResult, DebugApi};
+use setup::*;
+use subscriptions::metering::MeteringModule;
+use subscriptions::storage::StorageModule;
+use subscriptions::views::ViewsModule;
+
+const ADDON_ID: &[u8] = b"api-calls";
+const BILLING_PERIOD: u64 = 10 * DAY;
+const FIRST_TIER_UNITS: u64 = 100;
+const FIRST_TIER_PRICE: u64 = 2;
+const SECOND_TIER_PRICE: u64 = 1;
+
+fn setup_metering<SubscriptionsBuilder>(
+    subscriptions_builder: SubscriptionsBuilder,
+) -> (SubscriptionsSetup<SubscriptionsBuilder>, elrond_wasm::types::Address)
+where
+    SubscriptionsBuilder: 'static + Copy + Fn() -> subscriptions::ContractObj<DebugApi>,
+{
+    let mut setup = setup_subscriptions(subscriptions_builder);
+    let reporter_address = setup.create_user(0);
+
+    setup
+        .owner_tx(|sc| {
+            let mut tiers = MultiValueEncoded::new();
+            tiers.push((FIRST_TIER_UNITS, managed_biguint!(FIRST_TIER_PRICE)).into());
+            tiers.push((0u64, managed_biguint!(SECOND_TIER_PRICE)).into());
+
+            sc.set_subscription_plan_addon(
+                managed_buffer!(PLAN_ID),
+                managed_buffer!(ADDON_ID),
+                EgldOrEsdtTokenIdentifier::egld(),
+                BILLING_PERIOD,
+                tiers,
+            );
+            sc.add_usage_reporter(managed_address!(&reporter_address));
+        })
+        .assert_ok();
+
+    (setup, reporter_address)
+}
+
+fn report_usage<SubscriptionsBuilder>(
+    setup: &mut SubscriptionsSetup<SubscriptionsBuilder>,
+    reporter_address: &elrond_wasm::types::Address,
+    user_address: &elrond_wasm::types::Address,
+    period: u64,
+    units: u64,
+) -> TxResult
+where
+    SubscriptionsBuilder: 'static + Copy + Fn() -> subscriptions::ContractObj<DebugApi>,
+{
+    setup.user_tx(reporter_address, |sc| {
+        sc.report_usage(managed_address!(user_address), managed_buffer!(PLAN_ID), managed_buffer!(ADDON_ID), period, units);
+    })
+}
+
+fn check_outstanding_charge<SubscriptionsBuilder>(
+    setup: &mut SubscriptionsSetup<SubscriptionsBuilder>,
+    user_address: &elrond_wasm::types::Address,
+    charge: u64,
+) where
+    SubscriptionsBuilder: 'static + Copy + Fn() -> subscriptions::ContractObj<DebugApi>,
+{
+    setup.query(|sc| {
+        assert_eq!(
+            sc.user_outstanding_usage_charge(&managed_address!(user_address), &EgldOrEsdtTokenIdentifier::egld()).get(),
+            managed_biguint!(charge)
+        );
+    });
+}
+
+#[test]
+fn tiered_usage_charge_test() {
+    let (mut setup, reporter_address) = setup_metering(subscriptions::contract_obj);
+    let user_address = setup.create_user(PLAN_PRICE + 300);
+    let period = START_TIMESTAMP / BILLING_PERIOD;
+    let charge = FIRST_TIER_UNITS * FIRST_TIER_PRICE + 50 * SECOND_TIER_PRICE;
+
+    report_usage(&mut setup, &reporter_address, &user_address, period, 10)
+        .assert_user_error("The user is not subscribed to this plan");
+
+    setup.pay_egld(&user_address, PLAN_PRICE).assert_ok();
+
+    // the units of a billing period are charged together, across the tiers
+    report_usage(&mut setup, &reporter_address, &user_address, period, 60).assert_ok();
+    check_outstanding_charge(&mut setup, &user_address, 60 * FIRST_TIER_PRICE);
+    report_usage(&mut setup, &reporter_address, &user_address, period, 90).assert_ok();
+    check_outstanding_charge(&mut setup, &user_address, charge);
+
+    report_usage(&mut setup, &reporter_address, &user_address, period + 1, 10)
+        .assert_user_error("Invalid billing period");
+    report_usage(&mut setup, &reporter_address, &user_address, period - 1, 10)
+        .assert_user_error("The user was not subscribed to this plan in this billing period");
+
+    setup
+        .blockchain_wrapper
+        .execute_tx(&user_address, &setup.subscriptions_wrapper, &rust_biguint!(200), |sc| sc.deposit_usage_funds())
+        .assert_ok();
+    setup
+        .user_tx(&user_address, |sc| sc.withdraw_usage_funds(EgldOrEsdtTokenIdentifier::egld(), managed_biguint!(1)))
+        .assert_user_error("The deposit should cover the outstanding usage charges");
+
+    // the open billing period is not settled
+    setup.user_tx(&user_address, |sc| assert_eq!(sc.settle_usage(managed_address!(&user_address), 10), 1)).assert_ok();
+
+    // the deposit does not cover the charge
+    setup.set_timestamp((period + 1) * BILLING_PERIOD);
+    setup.user_tx(&user_address, |sc| assert_eq!(sc.settle_usage(managed_address!(&user_address), 10), 1)).assert_ok();
+    check_outstanding_charge(&mut setup, &user_address, charge);
+
+    setup
+        .blockchain_wrapper
+        .execute_tx(&user_address, &setup.subscriptions_wrapper, &rust_biguint!(100), |sc| sc.deposit_usage_funds())
+        .assert_ok();
+    setup.user_tx(&user_address, |sc| assert_eq!(sc.settle_usage(managed_address!(&user_address), 0), 1)).assert_ok();
+    setup.user_tx(&user_address, |sc| assert_eq!(sc.settle_usage(managed_address!(&user_address), 10), 0)).assert_ok();
+
+    check_outstanding_charge(&mut setup, &user_address, 0);
+    setup
+        .blockchain_wrapper
+        .check_egld_balance(&setup.payment_address, &rust_biguint!(PLAN_PRICE + charge));
+    setup.query(|sc| {
+        assert_eq!(
+            sc.usage_deposits(&managed_address!(&user_address), &EgldOrEsdtTokenIdentifier::egld()).get(),
+            managed_biguint!(300 - charge)
+        );
+    });
+
+    setup
+        .user_tx(&user_address, |sc| sc.withdraw_usage_funds(EgldOrEsdtTokenIdentifier::egld(), managed_biguint!(300 - charge)))
+        .assert_ok();
+    setup.blockchain_wrapper.check_egld_balance(&user_address, &rust_biguint!(300 - charge));
+}
+
+#[test]
+fn settle_usage_paging_test() {
+    let (mut setup, reporter_address) = setup_metering(subscriptions::contract_obj);
+    let user_address = setup.create_user(PLAN_PRICE + 1_000);
+    let first_period = START_TIMESTAMP / BILLING_PERIOD;
+
+    setup.pay_egld(&user_address, PLAN_PRICE).assert_ok();
+
+    for index in 0..3 {
+        setup.set_timestamp((first_period + index) * BILLING_PERIOD + DAY);
+        report_usage(&mut setup, &reporter_address, &user_address, first_period + index, 10).assert_ok();
+    }
+
+    check_outstanding_charge(&mut setup, &user_address, 3 * 10 * FIRST_TIER_PRICE);
+
+    setup
+        .blockchain_wrapper
+        .execute_tx(&user_address, &setup.subscriptions_wrapper, &rust_biguint!(1_000), |sc| sc.deposit_usage_funds())
+        .assert_ok();
+
+    setup.set_timestamp((first_period + 3) * BILLING_PERIOD);
+    setup.user_tx(&user_address, |sc| assert_eq!(sc.settle_usage(managed_address!(&user_address), 2), 1)).assert_ok();
+    check_outstanding_charge(&mut setup, &user_address, 10 * FIRST_TIER_PRICE);
+    setup.user_tx(&user_address, |sc| assert_eq!(sc.settle_usage(managed_address!(&user_address), 2), 0)).assert_ok();
+    check_outstanding_charge(&mut setup, &user_address, 0);
+}
+
+#[test]
+fn removed_addon_usage_test() {
+    let (mut setup, reporter_address) = setup_metering(subscriptions::contract_obj);
+    let user_address = setup.create_user(PLAN_PRICE);
+    let period = START_TIMESTAMP / BILLING_PERIOD;
+
+    setup.pay_egld(&user_address, PLAN_PRICE).assert_ok();
+    report_usage(&mut setup, &reporter_address, &user_address, period, 10).assert_ok();
+    setup
+        .owner_tx(|sc| sc.remove_subscription_plan_addon(managed_buffer!(PLAN_ID), managed_buffer!(ADDON_ID)))
+        .assert_ok();
+
+    // the usage of the removed add-on is listed as long as it is part of the outstanding charge
+    check_outstanding_charge(&mut setup, &user_address, 10 * FIRST_TIER_PRICE);
+    setup.query(|sc| {
+        let charges: Vec<_> = sc.get_outstanding_usage_charges(&managed_address!(&user_address)).into_iter().collect();
+
+        assert_eq!(charges.len(), 1);
+        assert_eq!(charges[0].clone().into_tuple().5, managed_biguint!(10 * FIRST_TIER_PRICE));
+    });
+
+    // a settlement drops the usage without a deposit
+    setup.user_tx(&user_address, |sc| assert_eq!(sc.settle_usage(managed_address!(&user_address), 10), 0)).assert_ok();
+    check_outstanding_charge(&mut setup, &user_address, 0);
+    setup.query(|sc| assert!(sc.get_outstanding_usage_charges(&managed_address!(&user_address)).is_empty()));
+}
@@ -0,0 +1,187 @@
+mod setup;
+
+use elrond_wasm::types::{Address, EgldOrEsdtTokenIdentifier, MultiValueEncoded};
+use elrond_wasm_debug::{managed_address, managed_biguint, managed_buffer, DebugApi};
+use setup::*;
+use subscriptions::merchants::MerchantsModule;
+use subscriptions::migrations::MigrationsModule;
+use subscriptions::pauses::PausesModule;
+use subscriptions::storage::StorageModule;
+use subscriptions::structs::UserPlan;
+use subscriptions::views::ViewsModule;
+use subscriptions::Subscriptions;
+
+const MERCHANT_PLAN_ID: &[u8] = b"1/pro";
+
+type ExportedUserPlan = (Address, Vec<u8>, u64, u64, u64, u64);
+
+fn setup_plans<SubscriptionsBuilder>(subscriptions_builder: SubscriptionsBuilder) -> SubscriptionsSetup<SubscriptionsBuilder>
+where
+    SubscriptionsBuilder: 'static + Copy + Fn() -> subscriptions::ContractObj<DebugApi>,
+{
+    let mut setup = setup_subscriptions(subscriptions_builder);
+    let merchant_address = setup.create_user(0);
+    let merchant_payment_address = merchant_address.clone();
+
+    setup
+        .owner_tx(|sc| sc.set_subscription_plan_pause_settings(managed_buffer!(PLAN_ID), 5 * DAY, 0))
+        .assert_ok();
+    setup
+        .user_tx(&merchant_address, |sc| {
+            sc.register_merchant(managed_address!(&merchant_payment_address));
+            sc.add_merchant_plan(managed_buffer!(b"pro"), PLAN_VALIDITY);
+            sc.set_merchant_plan_price(managed_buffer!(b"pro"), &EgldOrEsdtTokenIdentifier::egld(), managed_biguint!(PLAN_PRICE));
+        })
+        .assert_ok();
+
+    setup
+}
+
+fn export_user_plans<SubscriptionsBuilder>(setup: &mut SubscriptionsSetup<SubscriptionsBuilder>) -> Vec<ExportedUserPlan>
+where
+    SubscriptionsBuilder: 'static + Copy + Fn() -> subscriptions::ContractObj<DebugApi>,
+{
+    let mut exported = Vec::new();
+
+    setup.query(|sc| {
+        for item in sc.export_user_plans(0, 10).into_iter() {
+            let (address, user_plan) = item.into_tuple();
+
+            exported.push((
+                address.to_address(),
+                user_plan.plan_id.to_boxed_bytes().into_vec(),
+                user_plan.expires_at,
+                user_plan.first_subscribed,
+                user_plan.last_subscribed,
+                user_plan.paused_until,
+            ));
+        }
+    });
+
+    exported
+}
+
+#[test]
+fn export_import_user_plans_test() {
+    let mut previous_setup = setup_plans(subscriptions::contract_obj);
+    let user_address = previous_setup.create_user(PLAN_PRICE * 2);
+    let paused_address = previous_setup.create_user(PLAN_PRICE);
+
+    previous_setup.pay_egld(&user_address, PLAN_PRICE).assert_ok();
+    previous_setup.pay_egld_for(&user_address, MERCHANT_PLAN_ID, PLAN_PRICE).assert_ok();
+    previous_setup.pay_egld(&paused_address, PLAN_PRICE).assert_ok();
+    previous_setup.set_timestamp(START_TIMESTAMP + DAY);
+    previous_setup
+        .user_tx(&paused_address, |sc| sc.pause_subscription(managed_buffer!(PLAN_ID)))
+        .assert_ok();
+
+    let exported = export_user_plans(&mut previous_setup);
+
+    assert_eq!(exported.len(), 3);
+    assert!(exported.contains(&(
+        paused_address.clone(),
+        PLAN_ID.to_vec(),
+        START_TIMESTAMP + PLAN_VALIDITY + 5 * DAY,
+        START_TIMESTAMP,
+        START_TIMESTAMP,
+        START_TIMESTAMP + 6 * DAY,
+    )));
+
+    let mut setup = setup_plans(subscriptions::contract_obj);
+
+    setup.set_timestamp(START_TIMESTAMP + DAY);
+    setup.owner_tx(|sc| sc.start_migration()).assert_ok();
+    setup
+        .owner_tx(|sc| {
+            let mut user_plans = MultiValueEncoded::new();
+
+            for (address, plan_id, expires_at, first_subscribed, last_subscribed, paused_until) in exported.iter() {
+                let user_plan = UserPlan {
+                    plan_id: managed_buffer!(plan_id),
+                    expires_at: *expires_at,
+                    first_subscribed: *first_subscribed,
+                    last_subscribed: *last_subscribed,
+                    paused_until: *paused_until,
+                };
+
+                user_plans.push((managed_address!(address), user_plan).into());
+            }
+
+            sc.import_user_plans(user_plans);
+        })
+        .assert_ok();
+
+    assert_eq!(export_user_plans(&mut setup), exported);
+
+    // the paused subscription is imported as paused
+    setup.query(|sc| {
+        assert!(sc.has_active_subscription(&managed_address!(&user_address), &managed_buffer!(MERCHANT_PLAN_ID)));
+        assert!(!sc.has_active_subscription(&managed_address!(&paused_address), &managed_buffer!(PLAN_ID)));
+        assert_eq!(sc.get_active_subscriber_count(&managed_buffer!(PLAN_ID)), 1);
+        assert_eq!(sc.get_active_subscriber_count(&managed_buffer!(MERCHANT_PLAN_ID)), 1);
+    });
+
+    setup.owner_tx(|sc| sc.finalize_migration()).assert_ok();
+    setup
+        .user_tx(&paused_address, |sc| sc.resume_subscription(managed_buffer!(PLAN_ID)))
+        .assert_ok();
+    setup.query(|sc| {
+        assert!(sc.has_active_subscription(&managed_address!(&paused_address), &managed_buffer!(PLAN_ID)));
+        assert_eq!(sc.get_active_subscriber_count(&managed_buffer!(PLAN_ID)), 2);
+    });
+}
+
+#[test]
+fn import_expired_user_plans_test() {
+    let mut setup = setup_subscriptions(subscriptions::contract_obj);
+    let expired_address = setup.create_user(0);
+    let active_address = setup.create_user(0);
+    let imported_at = START_TIMESTAMP + 10 * DAY;
+
+    setup.set_timestamp(imported_at);
+    setup
+        .user_tx(&expired_address, |sc| {
+            sc.prune_expired_subscriptions(100);
+        })
+        .assert_ok();
+    setup.owner_tx(|sc| sc.start_migration()).assert_ok();
+    setup
+        .owner_tx(|sc| {
+            let mut user_plans = MultiValueEncoded::new();
+
+            for (address, expires_at) in [(&expired_address, START_TIMESTAMP + DAY), (&active_address, imported_at + DAY)] {
+                let user_plan = UserPlan {
+                    plan_id: managed_buffer!(PLAN_ID),
+                    expires_at,
+                    first_subscribed: START_TIMESTAMP - PLAN_VALIDITY,
+                    last_subscribed: START_TIMESTAMP - PLAN_VALIDITY,
+                    paused_until: 0,
+                };
+
+                user_plans.push((managed_address!(address), user_plan).into());
+            }
+
+            sc.import_user_plans(user_plans);
+        })
+        .assert_ok();
+
+    // the subscription that expired before the pruned days is kept out of the subscriber indexes
+    setup.query(|sc| {
+        assert!(sc.user_plan_ids(&managed_address!(&expired_address)).contains(&managed_buffer!(PLAN_ID)));
+        assert_eq!(sc.get_subscription_plan_subscriber_count(&managed_buffer!(PLAN_ID)), 1);
+        assert_eq!(sc.get_active_subscriber_count(&managed_buffer!(PLAN_ID)), 1);
+        assert_eq!(sc.get_expired_count(&managed_buffer!(PLAN_ID)), 0);
+        assert!(sc.get_expiring_between(START_TIMESTAMP, imported_at, 0, 10).is_empty());
+    });
+
+    setup.set_timestamp(imported_at + 2 * DAY);
+    setup
+        .user_tx(&expired_address, |sc| {
+            assert_eq!(sc.prune_expired_subscriptions(100), 1);
+        })
+        .assert_ok();
+    setup.query(|sc| {
+        assert_eq!(sc.get_subscription_plan_subscriber_count(&managed_buffer!(PLAN_ID)), 0);
+        assert_eq!(sc.get_active_subscriber_count(&managed_buffer!(PLAN_ID)), 0);
+    });
+}
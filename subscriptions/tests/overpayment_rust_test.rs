@@ -0,0 +1,102 @@
+mod setup;
+
+use elrond_wasm::types::{EgldOrEsdtTokenIdentifier, MultiValueEncoded};
+use elrond_wasm_debug::{managed_address, managed_biguint, managed_buffer, rust_biguint};
+use setup::*;
+use subscriptions::overpayment::OverpaymentModule;
+use subscriptions::promotions::PromotionsModule;
+use subscriptions::storage::StorageModule;
+use subscriptions::structs::OverpaymentPolicy;
+use subscriptions::Subscriptions;
+
+fn pay_with_credit<SubscriptionsBuilder>(
+    setup: &mut SubscriptionsSetup<SubscriptionsBuilder>,
+    user_address: &elrond_wasm::types::Address,
+) -> elrond_wasm_debug::tx_mock::TxResult
+where
+    SubscriptionsBuilder: 'static + Copy + Fn() -> subscriptions::ContractObj<elrond_wasm_debug::DebugApi>,
+{
+    setup.user_tx(user_address, |sc| {
+        sc.pay_with_credit(managed_buffer!(PLAN_ID), EgldOrEsdtTokenIdentifier::egld(), MultiValueEncoded::new());
+    })
+}
+
+#[test]
+fn overpayment_rejected_test() {
+    let mut setup = setup_subscriptions(subscriptions::contract_obj);
+    let user_address = setup.create_user(PLAN_PRICE * 2);
+
+    setup.pay_egld(&user_address, PLAN_PRICE + 1).assert_user_error("Invalid payment amount");
+    setup.pay_egld(&user_address, PLAN_PRICE - 1).assert_user_error("Invalid payment amount");
+}
+
+#[test]
+fn overpayment_refund_test() {
+    let mut setup = setup_subscriptions(subscriptions::contract_obj);
+    let user_address = setup.create_user(PLAN_PRICE * 2);
+
+    setup
+        .owner_tx(|sc| sc.set_subscription_plan_overpayment_policy(managed_buffer!(PLAN_ID), OverpaymentPolicy::Refund))
+        .assert_ok();
+
+    setup.pay_egld(&user_address, PLAN_PRICE + 30).assert_ok();
+    setup.pay_egld(&user_address, PLAN_PRICE - 1).assert_user_error("Invalid payment amount");
+
+    setup.blockchain_wrapper.check_egld_balance(&user_address, &rust_biguint!(PLAN_PRICE));
+    setup.blockchain_wrapper.check_egld_balance(&setup.payment_address, &rust_biguint!(PLAN_PRICE));
+}
+
+#[test]
+fn overpayment_credit_test() {
+    let mut setup = setup_subscriptions(subscriptions::contract_obj);
+    let user_address = setup.create_user(PLAN_PRICE * 3);
+    let credited_address = user_address.clone();
+
+    setup
+        .owner_tx(|sc| sc.set_subscription_plan_overpayment_policy(managed_buffer!(PLAN_ID), OverpaymentPolicy::Credit))
+        .assert_ok();
+
+    setup.pay_egld(&user_address, PLAN_PRICE * 2 + 50).assert_ok();
+    setup.query(|sc| {
+        assert_eq!(
+            sc.user_credits(&managed_address!(&credited_address), &EgldOrEsdtTokenIdentifier::egld()).get(),
+            managed_biguint!(PLAN_PRICE + 50)
+        );
+    });
+
+    pay_with_credit(&mut setup, &user_address).assert_ok();
+    pay_with_credit(&mut setup, &user_address).assert_user_error("Insufficient credit");
+
+    setup.query(|sc| {
+        let user_plan = sc.user_plans(&managed_address!(&credited_address), &managed_buffer!(PLAN_ID)).get();
+
+        assert_eq!(user_plan.expires_at, START_TIMESTAMP + 2 * PLAN_VALIDITY);
+        assert_eq!(
+            sc.user_credits(&managed_address!(&credited_address), &EgldOrEsdtTokenIdentifier::egld()).get(),
+            managed_biguint!(50)
+        );
+    });
+
+    setup.blockchain_wrapper.check_egld_balance(&setup.payment_address, &rust_biguint!(PLAN_PRICE * 2));
+    setup.blockchain_wrapper.check_egld_balance(setup.subscriptions_wrapper.address_ref(), &rust_biguint!(50));
+}
+
+#[test]
+fn pay_with_credit_at_zero_price_test() {
+    let mut setup = setup_subscriptions(subscriptions::contract_obj);
+    let user_address = setup.create_user(0);
+
+    setup
+        .owner_tx(|sc| {
+            sc.add_subscription_plan_promotion(
+                managed_buffer!(PLAN_ID),
+                &EgldOrEsdtTokenIdentifier::egld(),
+                managed_biguint!(0),
+                START_TIMESTAMP,
+                START_TIMESTAMP + DAY,
+            );
+        })
+        .assert_ok();
+
+    pay_with_credit(&mut setup, &user_address).assert_user_error("The plan cannot be paid with credit at a zero price");
+}
@@ -0,0 +1,33 @@
+elrond_wasm::imports!();
+
+/**
+ * Minimal DEX pair used in tests: it swaps any token into `token_out` at a fixed rate
+ */
+#[elrond_wasm::contract]
+pub trait PairMock {
+    #[init]
+    fn init(&self, rate: BigUint) {
+        self.rate().set(rate);
+    }
+
+    #[payable("*")]
+    #[endpoint(swapTokensFixedInput)]
+    fn swap_tokens_fixed_input(
+        &self,
+        token_out: TokenIdentifier,
+        amount_out_min: BigUint
+    ) -> EsdtTokenPayment<Self::Api> {
+        let payment = self.call_value().single_esdt();
+        let amount_out = payment.amount * self.rate().get();
+
+        require!(amount_out >= amount_out_min, "Slippage exceeded");
+
+        let caller = self.blockchain().get_caller();
+        self.send().direct_esdt(&caller, &token_out, 0, &amount_out);
+
+        EsdtTokenPayment::new(token_out, 0, amount_out)
+    }
+
+    #[storage_mapper("rate")]
+    fn rate(&self) -> SingleValueMapper<BigUint>;
+}
@@ -0,0 +1,181 @@
+mod setup;
+
+use elrond_wasm::types::Address;
+use elrond_wasm_debug::{managed_address, managed_buffer, DebugApi};
+use setup::*;
+use subscriptions::pauses::PausesModule;
+use subscriptions::renewals::RenewalsModule;
+use subscriptions::storage::StorageModule;
+use subscriptions::structs::RenewalMode;
+use subscriptions::views::ViewsModule;
+
+const PAUSE_DURATION: u64 = 5 * DAY;
+const PAUSE_COOLDOWN: u64 = 10 * DAY;
+
+fn setup_pauses<SubscriptionsBuilder>(subscriptions_builder: SubscriptionsBuilder) -> (SubscriptionsSetup<SubscriptionsBuilder>, Address)
+where
+    SubscriptionsBuilder: 'static + Copy + Fn() -> subscriptions::ContractObj<DebugApi>,
+{
+    let mut setup = setup_subscriptions(subscriptions_builder);
+    let user_address = setup.create_user(PLAN_PRICE);
+
+    setup
+        .owner_tx(|sc| sc.set_subscription_plan_pause_settings(managed_buffer!(PLAN_ID), PAUSE_DURATION, PAUSE_COOLDOWN))
+        .assert_ok();
+    setup.pay_egld(&user_address, PLAN_PRICE).assert_ok();
+
+    (setup, user_address)
+}
+
+fn check_user_plan<SubscriptionsBuilder>(
+    setup: &mut SubscriptionsSetup<SubscriptionsBuilder>,
+    user_address: &Address,
+    expires_at: u64,
+    paused_until: u64,
+    is_active: bool,
+) where
+    SubscriptionsBuilder: 'static + Copy + Fn() -> subscriptions::ContractObj<DebugApi>,
+{
+    setup.query(|sc| {
+        let user_plan = sc.user_plans(&managed_address!(user_address), &managed_buffer!(PLAN_ID)).get();
+
+        assert_eq!(user_plan.expires_at, expires_at);
+        assert_eq!(user_plan.paused_until, paused_until);
+        assert_eq!(sc.has_active_subscription(&managed_address!(user_address), &managed_buffer!(PLAN_ID)), is_active);
+    });
+}
+
+#[test]
+fn pause_settings_test() {
+    let mut setup = setup_subscriptions(subscriptions::contract_obj);
+    let user_address = setup.create_user(PLAN_PRICE);
+
+    setup.pay_egld(&user_address, PLAN_PRICE).assert_ok();
+    setup
+        .user_tx(&user_address, |sc| sc.pause_subscription(managed_buffer!(PLAN_ID)))
+        .assert_user_error("This plan cannot be paused");
+
+    setup
+        .owner_tx(|sc| sc.set_subscription_plan_pause_settings(managed_buffer!(PLAN_ID), 0, PAUSE_COOLDOWN))
+        .assert_user_error("Invalid pause duration");
+    setup
+        .owner_tx(|sc| sc.set_subscription_plan_pause_settings(managed_buffer!(PLAN_ID), PAUSE_DURATION, PAUSE_COOLDOWN))
+        .assert_ok();
+    setup
+        .owner_tx(|sc| sc.remove_subscription_plan_pause_settings(managed_buffer!(PLAN_ID)))
+        .assert_ok();
+    setup
+        .owner_tx(|sc| sc.remove_subscription_plan_pause_settings(managed_buffer!(PLAN_ID)))
+        .assert_user_error("This plan cannot be paused");
+}
+
+#[test]
+fn pause_max_duration_test() {
+    let (mut setup, user_address) = setup_pauses(subscriptions::contract_obj);
+    let other_address = setup.create_user(0);
+
+    setup
+        .user_tx(&other_address, |sc| sc.pause_subscription(managed_buffer!(PLAN_ID)))
+        .assert_user_error("The user is not subscribed to this plan");
+    setup
+        .user_tx(&user_address, |sc| sc.resume_subscription(managed_buffer!(PLAN_ID)))
+        .assert_user_error("The user subscription is not paused");
+
+    // the subscription is extended with the whole pause while it is paused
+    setup
+        .user_tx(&user_address, |sc| sc.pause_subscription(managed_buffer!(PLAN_ID)))
+        .assert_ok();
+    check_user_plan(
+        &mut setup,
+        &user_address,
+        START_TIMESTAMP + PLAN_VALIDITY + PAUSE_DURATION,
+        START_TIMESTAMP + PAUSE_DURATION,
+        false,
+    );
+
+    // the subscription resumes by itself after the maximum pause duration
+    setup.set_timestamp(START_TIMESTAMP + PAUSE_DURATION);
+    check_user_plan(
+        &mut setup,
+        &user_address,
+        START_TIMESTAMP + PLAN_VALIDITY + PAUSE_DURATION,
+        START_TIMESTAMP + PAUSE_DURATION,
+        true,
+    );
+    setup
+        .user_tx(&user_address, |sc| sc.resume_subscription(managed_buffer!(PLAN_ID)))
+        .assert_user_error("The user subscription is not paused");
+}
+
+#[test]
+fn pause_resume_test() {
+    let (mut setup, user_address) = setup_pauses(subscriptions::contract_obj);
+
+    setup
+        .user_tx(&user_address, |sc| sc.pause_subscription(managed_buffer!(PLAN_ID)))
+        .assert_ok();
+
+    // an early resume gives back only the used pause time
+    setup.set_timestamp(START_TIMESTAMP + 2 * DAY);
+    setup
+        .user_tx(&user_address, |sc| sc.resume_subscription(managed_buffer!(PLAN_ID)))
+        .assert_ok();
+    check_user_plan(
+        &mut setup,
+        &user_address,
+        START_TIMESTAMP + PLAN_VALIDITY + 2 * DAY,
+        START_TIMESTAMP + 2 * DAY,
+        true,
+    );
+}
+
+#[test]
+fn pause_cooldown_test() {
+    let (mut setup, user_address) = setup_pauses(subscriptions::contract_obj);
+
+    setup
+        .user_tx(&user_address, |sc| sc.pause_subscription(managed_buffer!(PLAN_ID)))
+        .assert_ok();
+    setup.set_timestamp(START_TIMESTAMP + DAY);
+    setup
+        .user_tx(&user_address, |sc| sc.resume_subscription(managed_buffer!(PLAN_ID)))
+        .assert_ok();
+
+    // the cooldown starts when the pause ends
+    setup.set_timestamp(START_TIMESTAMP + DAY + PAUSE_COOLDOWN - 1);
+    setup
+        .user_tx(&user_address, |sc| sc.pause_subscription(managed_buffer!(PLAN_ID)))
+        .assert_user_error("The subscription was paused too recently");
+
+    setup.set_timestamp(START_TIMESTAMP + DAY + PAUSE_COOLDOWN);
+    setup
+        .user_tx(&user_address, |sc| sc.pause_subscription(managed_buffer!(PLAN_ID)))
+        .assert_ok();
+    check_user_plan(
+        &mut setup,
+        &user_address,
+        START_TIMESTAMP + PLAN_VALIDITY + DAY + PAUSE_DURATION,
+        START_TIMESTAMP + DAY + PAUSE_COOLDOWN + PAUSE_DURATION,
+        false,
+    );
+}
+
+#[test]
+fn pause_inactive_subscription_test() {
+    let (mut setup, user_address) = setup_pauses(subscriptions::contract_obj);
+
+    setup.set_timestamp(START_TIMESTAMP + PLAN_VALIDITY);
+    setup
+        .user_tx(&user_address, |sc| sc.pause_subscription(managed_buffer!(PLAN_ID)))
+        .assert_user_error("The user subscription is not active");
+
+    let lifetime_address = setup.create_user(PLAN_PRICE);
+
+    setup
+        .owner_tx(|sc| sc.set_subscription_plan_renewal_mode(managed_buffer!(PLAN_ID), RenewalMode::Lifetime))
+        .assert_ok();
+    setup.pay_egld(&lifetime_address, PLAN_PRICE).assert_ok();
+    setup
+        .user_tx(&lifetime_address, |sc| sc.pause_subscription(managed_buffer!(PLAN_ID)))
+        .assert_user_error("Lifetime subscriptions cannot be paused");
+}
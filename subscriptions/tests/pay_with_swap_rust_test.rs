@@ -0,0 +1,202 @@
+mod pair_mock;
+
+use elrond_wasm::types::{Address, EgldOrEsdtTokenIdentifier, MultiValueEncoded};
+use elrond_wasm_debug::{
+    managed_address, managed_biguint, managed_buffer, managed_token_id, rust_biguint,
+    testing_framework::*, DebugApi,
+};
+use pair_mock::PairMock;
+use subscriptions::storage::StorageModule;
+use subscriptions::swaps::SwapsModule;
+use subscriptions::Subscriptions;
+
+const SUBSCRIPTIONS_WASM_PATH: &str = "output/subscriptions.wasm";
+const PAIR_WASM_PATH: &str = "pair-mock.wasm";
+
+const PLAN_ID: &[u8] = b"monthly";
+const PLAN_VALIDITY: u64 = 30 * 24 * 60 * 60;
+const PLAN_PRICE: u64 = 150;
+
+const TOKEN_IN: &[u8] = b"RIDE-123456";
+const TOKEN_OUT: &[u8] = b"USDC-123456";
+const NOT_ROUTED_TOKEN: &[u8] = b"MEX-123456";
+
+struct SwapSetup<SubscriptionsBuilder>
+where
+    SubscriptionsBuilder: 'static + Copy + Fn() -> subscriptions::ContractObj<DebugApi>,
+{
+    blockchain_wrapper: BlockchainStateWrapper,
+    user_address: Address,
+    payment_address: Address,
+    subscriptions_wrapper: ContractObjWrapper<subscriptions::ContractObj<DebugApi>, SubscriptionsBuilder>,
+}
+
+fn setup_swap<SubscriptionsBuilder, PairBuilder>(
+    subscriptions_builder: SubscriptionsBuilder,
+    pair_builder: PairBuilder,
+    pair_rate: u64,
+) -> SwapSetup<SubscriptionsBuilder>
+where
+    SubscriptionsBuilder: 'static + Copy + Fn() -> subscriptions::ContractObj<DebugApi>,
+    PairBuilder: 'static + Copy + Fn() -> pair_mock::ContractObj<DebugApi>,
+{
+    let rust_zero = rust_biguint!(0u64);
+    let mut blockchain_wrapper = BlockchainStateWrapper::new();
+
+    let owner_address = blockchain_wrapper.create_user_account(&rust_zero);
+    let user_address = blockchain_wrapper.create_user_account(&rust_zero);
+    let payment_address = blockchain_wrapper.create_user_account(&rust_zero);
+
+    let subscriptions_wrapper = blockchain_wrapper.create_sc_account(
+        &rust_zero,
+        Some(&owner_address),
+        subscriptions_builder,
+        SUBSCRIPTIONS_WASM_PATH,
+    );
+    let pair_wrapper = blockchain_wrapper.create_sc_account(
+        &rust_zero,
+        Some(&owner_address),
+        pair_builder,
+        PAIR_WASM_PATH,
+    );
+
+    blockchain_wrapper.set_esdt_balance(&user_address, TOKEN_IN, &rust_biguint!(1_000));
+    blockchain_wrapper.set_esdt_balance(&user_address, NOT_ROUTED_TOKEN, &rust_biguint!(1_000));
+    blockchain_wrapper.set_esdt_balance(pair_wrapper.address_ref(), TOKEN_OUT, &rust_biguint!(1_000_000));
+
+    blockchain_wrapper
+        .execute_tx(&owner_address, &pair_wrapper, &rust_zero, |sc| {
+            sc.init(managed_biguint!(pair_rate));
+        })
+        .assert_ok();
+
+    let pair_address = pair_wrapper.address_ref().clone();
+    let treasury_address = payment_address.clone();
+
+    blockchain_wrapper
+        .execute_tx(&owner_address, &subscriptions_wrapper, &rust_zero, |sc| {
+            sc.init();
+
+            sc.set_payment_address(managed_address!(&treasury_address));
+            sc.set_status(true);
+
+            sc.add_plan(managed_buffer!(PLAN_ID), PLAN_VALIDITY);
+            sc.set_subscription_plan_price(
+                managed_buffer!(PLAN_ID),
+                &EgldOrEsdtTokenIdentifier::esdt(managed_token_id!(TOKEN_OUT)),
+                managed_biguint!(PLAN_PRICE),
+            );
+
+            sc.set_swap_route(
+                managed_token_id!(TOKEN_IN),
+                managed_address!(&pair_address),
+                managed_token_id!(TOKEN_OUT),
+            );
+        })
+        .assert_ok();
+
+    SwapSetup {
+        blockchain_wrapper,
+        user_address,
+        payment_address,
+        subscriptions_wrapper,
+    }
+}
+
+#[test]
+fn pay_with_swap_test() {
+    let mut setup = setup_swap(subscriptions::contract_obj, pair_mock::contract_obj, 2);
+
+    setup.blockchain_wrapper.set_block_timestamp(1_000);
+    setup
+        .blockchain_wrapper
+        .execute_esdt_transfer(
+            &setup.user_address,
+            &setup.subscriptions_wrapper,
+            TOKEN_IN,
+            0,
+            &rust_biguint!(100),
+            |sc| {
+                sc.pay_with_swap(managed_buffer!(PLAN_ID), managed_biguint!(180), MultiValueEncoded::new());
+            },
+        )
+        .assert_ok();
+
+    setup.blockchain_wrapper.check_esdt_balance(&setup.user_address, TOKEN_IN, &rust_biguint!(900));
+    setup.blockchain_wrapper.check_esdt_balance(&setup.user_address, TOKEN_OUT, &rust_biguint!(50));
+    setup.blockchain_wrapper.check_esdt_balance(&setup.payment_address, TOKEN_OUT, &rust_biguint!(PLAN_PRICE));
+    setup.blockchain_wrapper.check_esdt_balance(setup.subscriptions_wrapper.address_ref(), TOKEN_OUT, &rust_biguint!(0));
+
+    let user_address = setup.user_address.clone();
+    setup
+        .blockchain_wrapper
+        .execute_query(&setup.subscriptions_wrapper, |sc| {
+            let user_plan = sc.user_plans(&managed_address!(&user_address), &managed_buffer!(PLAN_ID)).get();
+
+            assert_eq!(user_plan.expires_at, 1_000 + PLAN_VALIDITY);
+            assert_eq!(
+                sc.payment_token_amounts(&EgldOrEsdtTokenIdentifier::esdt(managed_token_id!(TOKEN_OUT))).get(),
+                managed_biguint!(PLAN_PRICE)
+            );
+        })
+        .assert_ok();
+}
+
+#[test]
+fn pay_with_swap_slippage_test() {
+    let mut setup = setup_swap(subscriptions::contract_obj, pair_mock::contract_obj, 2);
+
+    setup
+        .blockchain_wrapper
+        .execute_esdt_transfer(
+            &setup.user_address,
+            &setup.subscriptions_wrapper,
+            TOKEN_IN,
+            0,
+            &rust_biguint!(100),
+            |sc| {
+                sc.pay_with_swap(managed_buffer!(PLAN_ID), managed_biguint!(201), MultiValueEncoded::new());
+            },
+        )
+        .assert_user_error("Slippage exceeded");
+
+    setup.blockchain_wrapper.check_esdt_balance(&setup.user_address, TOKEN_IN, &rust_biguint!(1_000));
+}
+
+#[test]
+fn pay_with_swap_output_lower_than_price_test() {
+    let mut setup = setup_swap(subscriptions::contract_obj, pair_mock::contract_obj, 1);
+
+    setup
+        .blockchain_wrapper
+        .execute_esdt_transfer(
+            &setup.user_address,
+            &setup.subscriptions_wrapper,
+            TOKEN_IN,
+            0,
+            &rust_biguint!(100),
+            |sc| {
+                sc.pay_with_swap(managed_buffer!(PLAN_ID), managed_biguint!(100), MultiValueEncoded::new());
+            },
+        )
+        .assert_user_error("The swap output is lower than the plan price");
+}
+
+#[test]
+fn pay_with_swap_without_route_test() {
+    let mut setup = setup_swap(subscriptions::contract_obj, pair_mock::contract_obj, 2);
+
+    setup
+        .blockchain_wrapper
+        .execute_esdt_transfer(
+            &setup.user_address,
+            &setup.subscriptions_wrapper,
+            NOT_ROUTED_TOKEN,
+            0,
+            &rust_biguint!(100),
+            |sc| {
+                sc.pay_with_swap(managed_buffer!(PLAN_ID), managed_biguint!(1), MultiValueEncoded::new());
+            },
+        )
+        .assert_user_error("No swap route configured for this token");
+}
@@ -0,0 +1,177 @@
+mod setup;
+
+use elrond_wasm::elrond_codec::multi_types::OptionalValue;
+use elrond_wasm::types::{Address, EgldOrEsdtTokenIdentifier};
+use elrond_wasm_debug::{managed_address, managed_buffer, rust_biguint};
+use setup::*;
+use subscriptions::pauses::PausesModule;
+use subscriptions::storage::StorageModule;
+use subscriptions::views::ViewsModule;
+use subscriptions::Subscriptions;
+
+#[test]
+fn plan_id_does_not_overwrite_user_plans_test() {
+    let mut setup = setup_subscriptions(subscriptions::contract_obj);
+
+    // the plan id is the rest of the user plan key once the plan key prefix and the plan id length are removed
+    let mut colliding_plan_id = Vec::new();
+    colliding_plan_id.extend_from_slice(&[7u8; 28]);
+    colliding_plan_id.extend_from_slice(&(PLAN_ID.len() as u32).to_be_bytes());
+    colliding_plan_id.extend_from_slice(PLAN_ID);
+
+    let mut address_bytes = [7u8; 32];
+    address_bytes[..4].copy_from_slice(&(colliding_plan_id.len() as u32).to_be_bytes());
+
+    let user_address = Address::from(address_bytes);
+    setup
+        .blockchain_wrapper
+        .create_user_account_fixed_address(&user_address, &rust_biguint!(PLAN_PRICE));
+
+    setup.pay_egld(&user_address, PLAN_PRICE).assert_ok();
+    setup
+        .owner_tx(|sc| sc.add_plan(managed_buffer!(&colliding_plan_id), PLAN_VALIDITY))
+        .assert_ok();
+
+    setup.query(|sc| {
+        let user_plan = sc.user_plans(&managed_address!(&user_address), &managed_buffer!(PLAN_ID)).get();
+
+        assert_eq!(user_plan.plan_id, managed_buffer!(PLAN_ID));
+        assert_eq!(user_plan.expires_at, START_TIMESTAMP + PLAN_VALIDITY);
+    });
+}
+
+#[test]
+fn pruning_keeps_user_plans_test() {
+    let mut setup = setup_subscriptions(subscriptions::contract_obj);
+    let user_address = setup.create_user(PLAN_PRICE);
+
+    setup.pay_egld(&user_address, PLAN_PRICE).assert_ok();
+    setup
+        .owner_tx(|sc| sc.deprecate_subscription_plan(managed_buffer!(PLAN_ID)))
+        .assert_ok();
+
+    setup.set_timestamp(START_TIMESTAMP + PLAN_VALIDITY + 2 * DAY);
+    setup
+        .user_tx(&user_address, |sc| {
+            sc.prune_expired_subscriptions(100);
+        })
+        .assert_ok();
+
+    setup.query(|sc| {
+        assert_eq!(sc.get_subscription_plan_subscriber_count(&managed_buffer!(PLAN_ID)), 0);
+        assert_eq!(sc.get_active_subscriber_count(&managed_buffer!(PLAN_ID)), 0);
+        assert_eq!(sc.user_plans(&managed_address!(&user_address), &managed_buffer!(PLAN_ID)).get().first_subscribed, START_TIMESTAMP);
+    });
+
+    // the pruned subscriber can still renew the deprecated plan and keeps its first subscription date
+    let renewed_at = START_TIMESTAMP + PLAN_VALIDITY + 2 * DAY;
+
+    setup.blockchain_wrapper.set_egld_balance(&user_address, &rust_biguint!(PLAN_PRICE));
+    setup.pay_egld(&user_address, PLAN_PRICE).assert_ok();
+    setup.query(|sc| {
+        let user_plan = sc.user_plans(&managed_address!(&user_address), &managed_buffer!(PLAN_ID)).get();
+
+        assert_eq!(user_plan.first_subscribed, START_TIMESTAMP);
+        assert_eq!(user_plan.expires_at, renewed_at + PLAN_VALIDITY);
+        assert_eq!(sc.get_subscription_plan_subscriber_count(&managed_buffer!(PLAN_ID)), 1);
+        assert_eq!(sc.get_active_subscriber_count(&managed_buffer!(PLAN_ID)), 1);
+    });
+}
+
+#[test]
+fn active_subscriber_count_test() {
+    let mut setup = setup_subscriptions(subscriptions::contract_obj);
+    let user_address = setup.create_user(PLAN_PRICE);
+    let paused_address = setup.create_user(PLAN_PRICE);
+
+    setup
+        .owner_tx(|sc| sc.set_subscription_plan_pause_settings(managed_buffer!(PLAN_ID), 5 * DAY, 0))
+        .assert_ok();
+    setup.pay_egld(&user_address, PLAN_PRICE).assert_ok();
+    setup.pay_egld(&paused_address, PLAN_PRICE).assert_ok();
+    setup.query(|sc| assert_eq!(sc.get_active_subscriber_count(&managed_buffer!(PLAN_ID)), 2));
+
+    // a paused subscription is not counted until it is resumed
+    setup
+        .user_tx(&paused_address, |sc| sc.pause_subscription(managed_buffer!(PLAN_ID)))
+        .assert_ok();
+    setup.query(|sc| assert_eq!(sc.get_active_subscriber_count(&managed_buffer!(PLAN_ID)), 1));
+
+    setup.set_timestamp(START_TIMESTAMP + DAY);
+    setup
+        .user_tx(&paused_address, |sc| sc.resume_subscription(managed_buffer!(PLAN_ID)))
+        .assert_ok();
+    setup.query(|sc| assert_eq!(sc.get_active_subscriber_count(&managed_buffer!(PLAN_ID)), 2));
+
+    // a pause that ends by itself is counted again once its day is pruned
+    setup
+        .user_tx(&paused_address, |sc| sc.pause_subscription(managed_buffer!(PLAN_ID)))
+        .assert_ok();
+    setup.query(|sc| assert_eq!(sc.get_active_subscriber_count(&managed_buffer!(PLAN_ID)), 1));
+
+    setup.set_timestamp(START_TIMESTAMP + 7 * DAY);
+    setup
+        .user_tx(&user_address, |sc| {
+            assert_eq!(sc.prune_expired_subscriptions(100), 0);
+        })
+        .assert_ok();
+    setup.query(|sc| assert_eq!(sc.get_active_subscriber_count(&managed_buffer!(PLAN_ID)), 2));
+
+    // the expired subscriptions are no longer counted once pruned
+    setup.set_timestamp(START_TIMESTAMP + PLAN_VALIDITY + DAY);
+    setup
+        .user_tx(&user_address, |sc| {
+            assert_eq!(sc.prune_expired_subscriptions(100), 1);
+        })
+        .assert_ok();
+    setup.query(|sc| assert_eq!(sc.get_active_subscriber_count(&managed_buffer!(PLAN_ID)), 1));
+}
+
+#[test]
+fn remove_plan_test() {
+    let mut setup = setup_subscriptions(subscriptions::contract_obj);
+    let user_address = setup.create_user(PLAN_PRICE);
+
+    setup
+        .owner_tx(|sc| sc.remove_plan(managed_buffer!(b"yearly")))
+        .assert_user_error("This plan does not exist");
+
+    setup
+        .owner_tx(|sc| sc.set_subscription_plan_pause_settings(managed_buffer!(PLAN_ID), 5 * DAY, 0))
+        .assert_ok();
+    setup.pay_egld(&user_address, PLAN_PRICE).assert_ok();
+    setup
+        .owner_tx(|sc| sc.remove_plan(managed_buffer!(PLAN_ID)))
+        .assert_user_error("This plan still has subscribers");
+
+    // the subscribers are released once their subscriptions expired
+    setup
+        .owner_tx(|sc| sc.archive_subscription_plan(managed_buffer!(PLAN_ID)))
+        .assert_ok();
+    setup.set_timestamp(START_TIMESTAMP + PLAN_VALIDITY + DAY);
+    setup
+        .owner_tx(|sc| {
+            assert_eq!(sc.release_subscription_plan_subscribers(managed_buffer!(PLAN_ID), 10, OptionalValue::None), 0);
+        })
+        .assert_ok();
+    setup
+        .owner_tx(|sc| sc.remove_plan(managed_buffer!(PLAN_ID)))
+        .assert_ok();
+
+    setup.query(|sc| {
+        assert!(!sc.plan_ids().contains(&managed_buffer!(PLAN_ID)));
+        assert!(sc.plans(&managed_buffer!(PLAN_ID)).is_empty());
+        assert!(sc.plan_tokens(&managed_buffer!(PLAN_ID)).is_empty());
+        assert!(sc.plan_prices(&managed_buffer!(PLAN_ID), &EgldOrEsdtTokenIdentifier::egld()).is_empty());
+        assert!(sc.plan_pause_settings(&managed_buffer!(PLAN_ID)).is_empty());
+    });
+
+    // a plan added again with the same id does not inherit the removed settings
+    setup
+        .owner_tx(|sc| sc.add_plan(managed_buffer!(PLAN_ID), PLAN_VALIDITY))
+        .assert_ok();
+    setup.blockchain_wrapper.set_egld_balance(&user_address, &rust_biguint!(PLAN_PRICE));
+    setup
+        .pay_egld(&user_address, PLAN_PRICE)
+        .assert_user_error("This token is not enabled");
+}
@@ -0,0 +1,76 @@
+mod setup;
+
+use elrond_wasm::types::{EgldOrEsdtTokenIdentifier, MultiValueEncoded};
+use elrond_wasm_debug::{managed_biguint, managed_buffer, managed_token_id, rust_biguint};
+use setup::*;
+use subscriptions::pricing::PricingModule;
+use subscriptions::storage::StorageModule;
+use subscriptions::Subscriptions;
+
+const SFT_PLAN_ID: &[u8] = b"sft";
+const SFT_TOKEN: &[u8] = b"SFT-123456";
+const SFT_PRICE: u64 = 2;
+
+fn setup_nonce_plan<SubscriptionsBuilder>(setup: &mut SubscriptionsSetup<SubscriptionsBuilder>)
+where
+    SubscriptionsBuilder: 'static + Copy + Fn() -> subscriptions::ContractObj<elrond_wasm_debug::DebugApi>,
+{
+    setup
+        .owner_tx(|sc| {
+            sc.add_plan(managed_buffer!(SFT_PLAN_ID), PLAN_VALIDITY);
+            sc.set_subscription_plan_nonce_price(
+                managed_buffer!(SFT_PLAN_ID),
+                &EgldOrEsdtTokenIdentifier::esdt(managed_token_id!(SFT_TOKEN)),
+                1,
+                managed_biguint!(SFT_PRICE),
+            );
+        })
+        .assert_ok();
+}
+
+#[test]
+fn nonce_price_test() {
+    let mut setup = setup_subscriptions(subscriptions::contract_obj);
+    let user_address = setup.create_user(0);
+
+    setup_nonce_plan(&mut setup);
+    setup.blockchain_wrapper.set_nft_balance(&user_address, SFT_TOKEN, 1, &rust_biguint!(10), &0u8);
+    setup.blockchain_wrapper.set_esdt_balance(&user_address, SFT_TOKEN, &rust_biguint!(10));
+
+    setup
+        .blockchain_wrapper
+        .execute_esdt_transfer(&user_address, &setup.subscriptions_wrapper, SFT_TOKEN, 1, &rust_biguint!(SFT_PRICE), |sc| {
+            sc.pay_with_esdt(managed_buffer!(SFT_PLAN_ID), MultiValueEncoded::new());
+        })
+        .assert_ok();
+
+    setup
+        .blockchain_wrapper
+        .execute_esdt_transfer(&user_address, &setup.subscriptions_wrapper, SFT_TOKEN, 0, &rust_biguint!(SFT_PRICE), |sc| {
+            sc.pay_with_esdt(managed_buffer!(SFT_PLAN_ID), MultiValueEncoded::new());
+        })
+        .assert_user_error("This token is not enabled");
+
+    setup.query(|sc| {
+        assert!(!sc.plan_tokens(&managed_buffer!(SFT_PLAN_ID)).contains(&EgldOrEsdtTokenIdentifier::esdt(managed_token_id!(SFT_TOKEN))));
+        assert!(sc.is_plan_token(&managed_buffer!(SFT_PLAN_ID), &EgldOrEsdtTokenIdentifier::esdt(managed_token_id!(SFT_TOKEN))));
+    });
+}
+
+#[test]
+fn pay_with_credit_on_nonce_priced_plan_test() {
+    let mut setup = setup_subscriptions(subscriptions::contract_obj);
+    let user_address = setup.create_user(0);
+
+    setup_nonce_plan(&mut setup);
+
+    setup
+        .user_tx(&user_address, |sc| {
+            sc.pay_with_credit(
+                managed_buffer!(SFT_PLAN_ID),
+                EgldOrEsdtTokenIdentifier::esdt(managed_token_id!(SFT_TOKEN)),
+                MultiValueEncoded::new(),
+            );
+        })
+        .assert_user_error("No price configured for this plan/token combination");
+}
@@ -0,0 +1,146 @@
+mod setup;
+
+use elrond_wasm::types::{Address, ManagedBuffer, MultiValueEncoded};
+use elrond_wasm_debug::{managed_address, managed_buffer, rust_biguint, DebugApi};
+use setup::*;
+use subscriptions::merkle::{Hash, MerkleModule, HASH_LENGTH};
+use subscriptions::private_plans::PrivatePlansModule;
+use subscriptions::structs::HashAlgorithm;
+use subscriptions::Subscriptions;
+
+/**
+ * The root and the proofs of the three leaves tree [[a, b], c], with the pairs hashed in sorted order
+ */
+struct AllowlistTree {
+    root: [u8; HASH_LENGTH],
+    proofs: Vec<Vec<[u8; HASH_LENGTH]>>,
+}
+
+fn hash_pair(sc: &subscriptions::ContractObj<DebugApi>, left: &Hash<DebugApi>, right: &Hash<DebugApi>, algorithm: HashAlgorithm) -> Hash<DebugApi> {
+    let mut pair = ManagedBuffer::new();
+
+    if left.to_byte_array() <= right.to_byte_array() {
+        pair.append(left.as_managed_buffer());
+        pair.append(right.as_managed_buffer());
+    } else {
+        pair.append(right.as_managed_buffer());
+        pair.append(left.as_managed_buffer());
+    }
+
+    sc.hash_with(&pair, algorithm)
+}
+
+fn build_tree<SubscriptionsBuilder>(
+    setup: &mut SubscriptionsSetup<SubscriptionsBuilder>,
+    addresses: &[Address; 3],
+    algorithm: HashAlgorithm,
+) -> AllowlistTree
+where
+    SubscriptionsBuilder: 'static + Copy + Fn() -> subscriptions::ContractObj<DebugApi>,
+{
+    let mut tree = AllowlistTree {
+        root: [0u8; HASH_LENGTH],
+        proofs: Vec::new(),
+    };
+
+    setup.query(|sc| {
+        let leaves: Vec<Hash<DebugApi>> = addresses
+            .iter()
+            .map(|address| sc.address_leaf_with(&managed_address!(address), algorithm))
+            .collect();
+        let node = hash_pair(&sc, &leaves[0], &leaves[1], algorithm);
+
+        tree.root = hash_pair(&sc, &node, &leaves[2], algorithm).to_byte_array();
+        tree.proofs = vec![
+            vec![leaves[1].to_byte_array(), leaves[2].to_byte_array()],
+            vec![leaves[0].to_byte_array(), leaves[2].to_byte_array()],
+            vec![node.to_byte_array()],
+        ];
+    });
+
+    tree
+}
+
+fn pay_with_proof<SubscriptionsBuilder>(
+    setup: &mut SubscriptionsSetup<SubscriptionsBuilder>,
+    user_address: &Address,
+    proof: &[[u8; HASH_LENGTH]],
+) -> elrond_wasm_debug::tx_mock::TxResult
+where
+    SubscriptionsBuilder: 'static + Copy + Fn() -> subscriptions::ContractObj<DebugApi>,
+{
+    setup
+        .blockchain_wrapper
+        .execute_tx(user_address, &setup.subscriptions_wrapper, &rust_biguint!(PLAN_PRICE), |sc| {
+            let mut managed_proof = MultiValueEncoded::new();
+
+            for node in proof {
+                managed_proof.push(Hash::<DebugApi>::from(node));
+            }
+
+            sc.pay_with_egld(managed_buffer!(PLAN_ID), managed_proof);
+        })
+}
+
+fn check_allowlist_proofs(algorithm: HashAlgorithm) {
+    let mut setup = setup_subscriptions(subscriptions::contract_obj);
+    let addresses = [
+        setup.create_user(PLAN_PRICE),
+        setup.create_user(PLAN_PRICE),
+        setup.create_user(PLAN_PRICE),
+    ];
+    let outsider_address = setup.create_user(PLAN_PRICE * 2);
+    let tree = build_tree(&mut setup, &addresses, algorithm);
+
+    setup
+        .owner_tx(|sc| {
+            sc.set_subscription_plan_private(managed_buffer!(PLAN_ID), true);
+            sc.set_subscription_plan_allowlist_root(managed_buffer!(PLAN_ID), Hash::<DebugApi>::from(&tree.root), algorithm);
+        })
+        .assert_ok();
+
+    setup.pay_egld(&addresses[0], PLAN_PRICE).assert_user_error("This plan is private");
+    pay_with_proof(&mut setup, &addresses[0], &tree.proofs[1]).assert_user_error("Invalid allowlist proof");
+
+    for (address, proof) in addresses.iter().zip(tree.proofs.iter()) {
+        pay_with_proof(&mut setup, address, proof).assert_ok();
+    }
+
+    // a valid proof only works for its own leaf
+    pay_with_proof(&mut setup, &outsider_address, &tree.proofs[0]).assert_user_error("Invalid allowlist proof");
+
+    setup
+        .owner_tx(|sc| {
+            let mut allowed_addresses = MultiValueEncoded::new();
+            allowed_addresses.push(managed_address!(&outsider_address));
+
+            sc.add_subscription_plan_allowed_addresses(managed_buffer!(PLAN_ID), allowed_addresses);
+        })
+        .assert_ok();
+    setup.pay_egld(&outsider_address, PLAN_PRICE).assert_ok();
+
+    // the leaf of a single address tree is its root, the address pays with an empty proof
+    let single_address = setup.create_user(PLAN_PRICE);
+    let other_address = setup.create_user(PLAN_PRICE);
+
+    setup
+        .owner_tx(|sc| {
+            let root = sc.address_leaf_with(&managed_address!(&single_address), algorithm);
+
+            sc.set_subscription_plan_allowlist_root(managed_buffer!(PLAN_ID), root, algorithm);
+        })
+        .assert_ok();
+    setup.pay_egld(&other_address, PLAN_PRICE).assert_user_error("This plan is private");
+    pay_with_proof(&mut setup, &single_address, &tree.proofs[2]).assert_user_error("Invalid allowlist proof");
+    setup.pay_egld(&single_address, PLAN_PRICE).assert_ok();
+}
+
+#[test]
+fn keccak256_allowlist_proof_test() {
+    check_allowlist_proofs(HashAlgorithm::Keccak256);
+}
+
+#[test]
+fn sha256_allowlist_proof_test() {
+    check_allowlist_proofs(HashAlgorithm::Sha256);
+}
@@ -0,0 +1,112 @@
+mod setup;
+
+use elrond_wasm::types::EgldOrEsdtTokenIdentifier;
+use elrond_wasm_debug::{managed_biguint, managed_buffer, managed_token_id, rust_biguint, tx_mock::TxResult, DebugApi};
+use setup::*;
+use subscriptions::promotions::PromotionsModule;
+use subscriptions::views::ViewsModule;
+
+const PROMOTION_PRICE: u64 = 60;
+const PROMOTION_START: u64 = START_TIMESTAMP + DAY;
+const PROMOTION_END: u64 = START_TIMESTAMP + 3 * DAY;
+
+fn add_promotion<SubscriptionsBuilder>(
+    setup: &mut SubscriptionsSetup<SubscriptionsBuilder>,
+    price: u64,
+    starts_at: u64,
+    ends_at: u64,
+) -> TxResult
+where
+    SubscriptionsBuilder: 'static + Copy + Fn() -> subscriptions::ContractObj<DebugApi>,
+{
+    setup.owner_tx(|sc| {
+        sc.add_subscription_plan_promotion(managed_buffer!(PLAN_ID), &EgldOrEsdtTokenIdentifier::egld(), managed_biguint!(price), starts_at, ends_at);
+    })
+}
+
+fn check_effective_price<SubscriptionsBuilder>(setup: &mut SubscriptionsSetup<SubscriptionsBuilder>, timestamp: u64, price: u64)
+where
+    SubscriptionsBuilder: 'static + Copy + Fn() -> subscriptions::ContractObj<DebugApi>,
+{
+    setup.set_timestamp(timestamp);
+    setup.query(|sc| {
+        assert_eq!(sc.get_effective_price_view(&managed_buffer!(PLAN_ID), EgldOrEsdtTokenIdentifier::egld(), 0), managed_biguint!(price));
+    });
+}
+
+#[test]
+fn promotion_window_test() {
+    let mut setup = setup_subscriptions(subscriptions::contract_obj);
+    let user_address = setup.create_user(PLAN_PRICE + PROMOTION_PRICE);
+
+    add_promotion(&mut setup, PROMOTION_PRICE, PROMOTION_START, PROMOTION_END).assert_ok();
+
+    // the promotion starts at its start timestamp and ends right before its end timestamp
+    check_effective_price(&mut setup, PROMOTION_START - 1, PLAN_PRICE);
+    check_effective_price(&mut setup, PROMOTION_START, PROMOTION_PRICE);
+    check_effective_price(&mut setup, PROMOTION_END - 1, PROMOTION_PRICE);
+    check_effective_price(&mut setup, PROMOTION_END, PLAN_PRICE);
+
+    setup.set_timestamp(PROMOTION_START);
+    setup.pay_egld(&user_address, PLAN_PRICE).assert_user_error("Invalid payment amount");
+    setup.pay_egld(&user_address, PROMOTION_PRICE).assert_ok();
+
+    setup.set_timestamp(PROMOTION_END);
+    setup.pay_egld(&user_address, PROMOTION_PRICE).assert_user_error("Invalid payment amount");
+    setup.pay_egld(&user_address, PLAN_PRICE).assert_ok();
+    setup
+        .blockchain_wrapper
+        .check_egld_balance(&setup.payment_address, &rust_biguint!(PLAN_PRICE + PROMOTION_PRICE));
+}
+
+#[test]
+fn promotion_validation_test() {
+    let mut setup = setup_subscriptions(subscriptions::contract_obj);
+
+    add_promotion(&mut setup, PROMOTION_PRICE, PROMOTION_END, PROMOTION_START).assert_user_error("Invalid promotion window");
+    add_promotion(&mut setup, PROMOTION_PRICE, PROMOTION_START, PROMOTION_START).assert_user_error("Invalid promotion window");
+    add_promotion(&mut setup, PROMOTION_PRICE, START_TIMESTAMP - DAY, START_TIMESTAMP).assert_user_error("Invalid promotion window");
+    setup
+        .owner_tx(|sc| {
+            sc.add_subscription_plan_promotion(
+                managed_buffer!(PLAN_ID),
+                &EgldOrEsdtTokenIdentifier::esdt(managed_token_id!(b"USDC-123456")),
+                managed_biguint!(PROMOTION_PRICE),
+                PROMOTION_START,
+                PROMOTION_END,
+            );
+        })
+        .assert_user_error("No price configured for this plan/token combination");
+
+    add_promotion(&mut setup, PROMOTION_PRICE, PROMOTION_START, PROMOTION_END).assert_ok();
+    add_promotion(&mut setup, 50, PROMOTION_END - 1, PROMOTION_END + DAY).assert_user_error("The promotion overlaps another promotion");
+    add_promotion(&mut setup, 50, PROMOTION_START - DAY, PROMOTION_START + 1).assert_user_error("The promotion overlaps another promotion");
+
+    // the adjacent windows do not overlap
+    add_promotion(&mut setup, 50, PROMOTION_END, PROMOTION_END + DAY).assert_ok();
+    add_promotion(&mut setup, 40, START_TIMESTAMP, PROMOTION_START).assert_ok();
+
+    check_effective_price(&mut setup, START_TIMESTAMP, 40);
+    check_effective_price(&mut setup, PROMOTION_START, PROMOTION_PRICE);
+    check_effective_price(&mut setup, PROMOTION_END, 50);
+    check_effective_price(&mut setup, PROMOTION_END + DAY, PLAN_PRICE);
+
+    // the ended promotions are removed, so their windows can be reused
+    add_promotion(&mut setup, 30, START_TIMESTAMP, PROMOTION_END + 2 * DAY).assert_ok();
+    check_effective_price(&mut setup, PROMOTION_END + 2 * DAY - 1, 30);
+}
+
+#[test]
+fn remove_promotion_test() {
+    let mut setup = setup_subscriptions(subscriptions::contract_obj);
+
+    add_promotion(&mut setup, PROMOTION_PRICE, PROMOTION_START, PROMOTION_END).assert_ok();
+    setup
+        .owner_tx(|sc| sc.remove_subscription_plan_promotion(managed_buffer!(PLAN_ID), &EgldOrEsdtTokenIdentifier::egld(), PROMOTION_END))
+        .assert_user_error("This promotion does not exist");
+    setup
+        .owner_tx(|sc| sc.remove_subscription_plan_promotion(managed_buffer!(PLAN_ID), &EgldOrEsdtTokenIdentifier::egld(), PROMOTION_START))
+        .assert_ok();
+
+    check_effective_price(&mut setup, PROMOTION_START, PLAN_PRICE);
+}
@@ -0,0 +1,116 @@
+mod setup;
+
+use elrond_wasm::types::Address;
+use elrond_wasm_debug::{managed_address, managed_buffer, DebugApi};
+use setup::*;
+use subscriptions::activation::LIFETIME_EXPIRY;
+use subscriptions::grants::GrantsModule;
+use subscriptions::pauses::PausesModule;
+use subscriptions::renewals::RenewalsModule;
+use subscriptions::storage::StorageModule;
+use subscriptions::structs::RenewalMode;
+
+fn user_plan_expiry<SubscriptionsBuilder>(setup: &mut SubscriptionsSetup<SubscriptionsBuilder>, user_address: &Address) -> u64
+where
+    SubscriptionsBuilder: 'static + Copy + Fn() -> subscriptions::ContractObj<DebugApi>,
+{
+    let mut expires_at = 0;
+
+    setup.query(|sc| expires_at = sc.user_plans(&managed_address!(user_address), &managed_buffer!(PLAN_ID)).get().expires_at);
+
+    expires_at
+}
+
+#[test]
+fn max_horizon_test() {
+    let mut setup = setup_subscriptions(subscriptions::contract_obj);
+    let user_address = setup.create_user(PLAN_PRICE * 3);
+
+    setup
+        .owner_tx(|sc| sc.set_subscription_plan_max_horizon(managed_buffer!(PLAN_ID), 2 * PLAN_VALIDITY))
+        .assert_ok();
+
+    setup.pay_egld(&user_address, PLAN_PRICE).assert_ok();
+    setup.pay_egld(&user_address, PLAN_PRICE).assert_ok();
+    setup
+        .pay_egld(&user_address, PLAN_PRICE)
+        .assert_user_error("The plan maximum prepaid period is exceeded");
+    assert_eq!(user_plan_expiry(&mut setup, &user_address), START_TIMESTAMP + 2 * PLAN_VALIDITY);
+
+    // the used time frees the horizon again
+    setup.set_timestamp(START_TIMESTAMP + PLAN_VALIDITY);
+    setup.pay_egld(&user_address, PLAN_PRICE).assert_ok();
+    assert_eq!(user_plan_expiry(&mut setup, &user_address), START_TIMESTAMP + 3 * PLAN_VALIDITY);
+
+    // the granted time is not limited by the prepaid horizon
+    setup
+        .owner_tx(|sc| sc.grant_subscription(managed_address!(&user_address), managed_buffer!(PLAN_ID), PLAN_VALIDITY))
+        .assert_ok();
+    assert_eq!(user_plan_expiry(&mut setup, &user_address), START_TIMESTAMP + 4 * PLAN_VALIDITY);
+}
+
+#[test]
+fn reset_renewal_test() {
+    let mut setup = setup_subscriptions(subscriptions::contract_obj);
+    let user_address = setup.create_user(PLAN_PRICE * 3);
+
+    setup
+        .owner_tx(|sc| {
+            sc.set_subscription_plan_renewal_mode(managed_buffer!(PLAN_ID), RenewalMode::Reset);
+            sc.set_subscription_plan_pause_settings(managed_buffer!(PLAN_ID), 5 * DAY, 0);
+        })
+        .assert_ok();
+
+    setup.pay_egld(&user_address, PLAN_PRICE).assert_ok();
+
+    // a payment restarts the period from the payment date
+    setup.set_timestamp(START_TIMESTAMP + 10 * DAY);
+    setup.pay_egld(&user_address, PLAN_PRICE).assert_ok();
+    assert_eq!(user_plan_expiry(&mut setup, &user_address), START_TIMESTAMP + 10 * DAY + PLAN_VALIDITY);
+
+    // a grant extends the paid period instead of resetting it
+    setup
+        .owner_tx(|sc| sc.grant_subscription(managed_address!(&user_address), managed_buffer!(PLAN_ID), 7 * DAY))
+        .assert_ok();
+    assert_eq!(user_plan_expiry(&mut setup, &user_address), START_TIMESTAMP + 17 * DAY + PLAN_VALIDITY);
+
+    // a reset ends the running pause
+    setup
+        .user_tx(&user_address, |sc| sc.pause_subscription(managed_buffer!(PLAN_ID)))
+        .assert_ok();
+    setup.set_timestamp(START_TIMESTAMP + 11 * DAY);
+    setup.pay_egld(&user_address, PLAN_PRICE).assert_ok();
+    setup.query(|sc| {
+        let user_plan = sc.user_plans(&managed_address!(&user_address), &managed_buffer!(PLAN_ID)).get();
+
+        assert_eq!(user_plan.expires_at, START_TIMESTAMP + 11 * DAY + PLAN_VALIDITY);
+        assert_eq!(user_plan.paused_until, START_TIMESTAMP + 11 * DAY);
+    });
+}
+
+#[test]
+fn lifetime_renewal_test() {
+    let mut setup = setup_subscriptions(subscriptions::contract_obj);
+    let user_address = setup.create_user(PLAN_PRICE * 2);
+    let granted_address = setup.create_user(0);
+
+    setup
+        .owner_tx(|sc| sc.set_subscription_plan_renewal_mode(managed_buffer!(PLAN_ID), RenewalMode::Lifetime))
+        .assert_ok();
+
+    setup.pay_egld(&user_address, PLAN_PRICE).assert_ok();
+    assert_eq!(user_plan_expiry(&mut setup, &user_address), LIFETIME_EXPIRY);
+    setup
+        .pay_egld(&user_address, PLAN_PRICE)
+        .assert_user_error("The user already has a lifetime subscription");
+
+    // a grant never cuts a lifetime subscription and only grants its duration to the other users
+    setup
+        .owner_tx(|sc| {
+            sc.grant_subscription(managed_address!(&user_address), managed_buffer!(PLAN_ID), DAY);
+            sc.grant_subscription(managed_address!(&granted_address), managed_buffer!(PLAN_ID), DAY);
+        })
+        .assert_ok();
+    assert_eq!(user_plan_expiry(&mut setup, &user_address), LIFETIME_EXPIRY);
+    assert_eq!(user_plan_expiry(&mut setup, &granted_address), START_TIMESTAMP + DAY);
+}
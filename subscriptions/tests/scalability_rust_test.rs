@@ -0,0 +1,165 @@
+mod setup;
+
+use std::collections::HashMap;
+
+use elrond_wasm::types::{Address, EgldOrEsdtTokenIdentifier};
+use elrond_wasm_debug::{managed_address, managed_buffer, DebugApi};
+use setup::*;
+use subscriptions::storage::StorageModule;
+use subscriptions::views::ViewsModule;
+use subscriptions::Subscriptions;
+
+const USER_COUNT: usize = 100;
+
+fn create_subscriber<SubscriptionsBuilder>(setup: &mut SubscriptionsSetup<SubscriptionsBuilder>) -> Address
+where
+    SubscriptionsBuilder: 'static + Copy + Fn() -> subscriptions::ContractObj<DebugApi>,
+{
+    let user_address = setup.create_user(PLAN_PRICE * 10);
+
+    setup.pay_egld(&user_address, PLAN_PRICE).assert_ok();
+
+    user_address
+}
+
+fn prune<SubscriptionsBuilder>(setup: &mut SubscriptionsSetup<SubscriptionsBuilder>, max_steps: usize) -> usize
+where
+    SubscriptionsBuilder: 'static + Copy + Fn() -> subscriptions::ContractObj<DebugApi>,
+{
+    let mut pruned = 0;
+    let caller = setup.create_user(0);
+
+    setup
+        .user_tx(&caller, |sc| pruned = sc.prune_expired_subscriptions(max_steps))
+        .assert_ok();
+
+    pruned
+}
+
+fn contract_storage<SubscriptionsBuilder>(setup: &mut SubscriptionsSetup<SubscriptionsBuilder>) -> HashMap<Vec<u8>, Vec<u8>>
+where
+    SubscriptionsBuilder: 'static + Copy + Fn() -> subscriptions::ContractObj<DebugApi>,
+{
+    let contract_address = setup.subscriptions_wrapper.address_ref().clone();
+
+    setup.blockchain_wrapper.get_mut_state().accounts[&contract_address].storage.clone()
+}
+
+/**
+ * It returns the number of storage entries added, changed or removed by a transaction, the main gas cost of the endpoints
+ */
+fn storage_writes<SubscriptionsBuilder, F>(setup: &mut SubscriptionsSetup<SubscriptionsBuilder>, transaction: F) -> usize
+where
+    SubscriptionsBuilder: 'static + Copy + Fn() -> subscriptions::ContractObj<DebugApi>,
+    F: FnOnce(&mut SubscriptionsSetup<SubscriptionsBuilder>),
+{
+    let before = contract_storage(setup);
+    transaction(setup);
+    let after = contract_storage(setup);
+
+    let changed = after.iter().filter(|(key, value)| before.get(*key) != Some(*value)).count();
+    let removed = before.keys().filter(|key| !after.contains_key(*key)).count();
+
+    changed + removed
+}
+
+/**
+ * It returns the storage writes of the new subscriptions, the renewals and the pruning for a number of existing users
+ */
+fn benchmark(user_count: usize) -> (usize, usize, usize) {
+    let mut setup = setup_subscriptions(subscriptions::contract_obj);
+    let mut user_addresses = Vec::new();
+
+    for _ in 0..user_count {
+        user_addresses.push(create_subscriber(&mut setup));
+    }
+
+    let new_user_address = setup.create_user(PLAN_PRICE);
+    let renewing_address = user_addresses[user_count / 2].clone();
+    let subscribe_writes = storage_writes(&mut setup, |setup| setup.pay_egld(&new_user_address, PLAN_PRICE).assert_ok());
+    let renew_writes = storage_writes(&mut setup, |setup| setup.pay_egld(&renewing_address, PLAN_PRICE).assert_ok());
+
+    setup.set_timestamp(START_TIMESTAMP + 3 * PLAN_VALIDITY);
+
+    let prune_writes = loop {
+        let mut pruned = 0;
+        let writes = storage_writes(&mut setup, |setup| pruned = prune(setup, 1));
+
+        if pruned == 1 {
+            break writes;
+        }
+    };
+
+    (subscribe_writes, renew_writes, prune_writes)
+}
+
+/**
+ * The debug framework has no gas metering, so the storage writes stand for the gas cost
+ */
+#[test]
+fn storage_writes_are_flat_test() {
+    let (subscribe_writes, renew_writes, prune_writes) = benchmark(USER_COUNT);
+    let (subscribe_writes_10x, renew_writes_10x, prune_writes_10x) = benchmark(USER_COUNT * 10);
+
+    assert_eq!(subscribe_writes, subscribe_writes_10x, "subscribe cost grows with the users");
+    assert_eq!(renew_writes, renew_writes_10x, "renew cost grows with the users");
+    assert_eq!(prune_writes, prune_writes_10x, "prune cost grows with the users");
+}
+
+#[test]
+fn counters_and_pages_test() {
+    let mut setup = setup_subscriptions(subscriptions::contract_obj);
+    let mut user_addresses = Vec::new();
+
+    for _ in 0..25 {
+        user_addresses.push(create_subscriber(&mut setup));
+    }
+
+    setup.set_timestamp(START_TIMESTAMP + PLAN_VALIDITY / 2);
+    setup.pay_egld(&user_addresses[0], PLAN_PRICE).assert_ok();
+
+    setup.query(|sc| {
+        assert_eq!(sc.get_user_count(), 25);
+        assert_eq!(sc.get_subscription_plan_subscriber_count(&managed_buffer!(PLAN_ID)), 25);
+        assert_eq!(sc.get_active_subscriber_count(&managed_buffer!(PLAN_ID)), 25);
+        assert_eq!(sc.payment_token_counts(&EgldOrEsdtTokenIdentifier::egld()).get(), 26);
+
+        assert_eq!(sc.get_users(0, 10).len(), 10);
+        assert_eq!(sc.get_users(20, 10).len(), 5);
+        assert_eq!(sc.get_users(30, 10).len(), 0);
+        assert_eq!(sc.get_subscription_plan_subscribers(&managed_buffer!(PLAN_ID), 10, 10).len(), 10);
+    });
+
+    setup.set_timestamp(START_TIMESTAMP + PLAN_VALIDITY + 2 * DAY);
+
+    setup.query(|sc| {
+        // the expired subscriptions are counted as active until they are pruned
+        assert_eq!(sc.get_expired_count(&managed_buffer!(PLAN_ID)), 24);
+        assert_eq!(sc.get_active_subscriber_count(&managed_buffer!(PLAN_ID)), 25);
+    });
+
+    let mut pruned = 0;
+
+    while pruned < 24 {
+        let pruned_now = prune(&mut setup, 10);
+
+        assert!(pruned_now <= 10);
+        pruned += pruned_now;
+    }
+
+    assert_eq!(pruned, 24);
+    assert_eq!(prune(&mut setup, 10), 0);
+
+    let first_user_address = user_addresses[0].clone();
+
+    setup.query(|sc| {
+        assert_eq!(sc.get_user_count(), 25);
+        assert_eq!(sc.get_subscription_plan_subscriber_count(&managed_buffer!(PLAN_ID)), 1);
+        assert_eq!(sc.get_expired_count(&managed_buffer!(PLAN_ID)), 0);
+        assert_eq!(sc.get_active_subscriber_count(&managed_buffer!(PLAN_ID)), 1);
+        assert!(sc
+            .get_subscription_plan_subscribers(&managed_buffer!(PLAN_ID), 0, 10)
+            .to_vec()
+            .contains(&managed_address!(&first_user_address)));
+    });
+}
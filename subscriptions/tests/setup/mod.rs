@@ -0,0 +1,117 @@
+#![allow(dead_code)]
+
+use elrond_wasm::types::{Address, EgldOrEsdtTokenIdentifier, MultiValueEncoded};
+use elrond_wasm_debug::{
+    managed_address, managed_biguint, managed_buffer, rust_biguint, testing_framework::*, tx_mock::TxResult, DebugApi,
+};
+use subscriptions::Subscriptions;
+
+pub const SUBSCRIPTIONS_WASM_PATH: &str = "output/subscriptions.wasm";
+
+pub const PLAN_ID: &[u8] = b"monthly";
+pub const PLAN_VALIDITY: u64 = 30 * 24 * 60 * 60;
+pub const PLAN_PRICE: u64 = 100;
+
+pub const START_TIMESTAMP: u64 = 1_700_000_000;
+pub const DAY: u64 = 24 * 60 * 60;
+
+/**
+ * A deployed and enabled subscriptions contract with a monthly plan paid with EGLD
+ */
+pub struct SubscriptionsSetup<SubscriptionsBuilder>
+where
+    SubscriptionsBuilder: 'static + Copy + Fn() -> subscriptions::ContractObj<DebugApi>,
+{
+    pub blockchain_wrapper: BlockchainStateWrapper,
+    pub owner_address: Address,
+    pub payment_address: Address,
+    pub subscriptions_wrapper: ContractObjWrapper<subscriptions::ContractObj<DebugApi>, SubscriptionsBuilder>,
+}
+
+pub fn setup_subscriptions<SubscriptionsBuilder>(
+    subscriptions_builder: SubscriptionsBuilder,
+) -> SubscriptionsSetup<SubscriptionsBuilder>
+where
+    SubscriptionsBuilder: 'static + Copy + Fn() -> subscriptions::ContractObj<DebugApi>,
+{
+    let rust_zero = rust_biguint!(0u64);
+    let mut blockchain_wrapper = BlockchainStateWrapper::new();
+
+    let owner_address = blockchain_wrapper.create_user_account(&rust_zero);
+    let payment_address = blockchain_wrapper.create_user_account(&rust_zero);
+
+    let subscriptions_wrapper = blockchain_wrapper.create_sc_account(
+        &rust_zero,
+        Some(&owner_address),
+        subscriptions_builder,
+        SUBSCRIPTIONS_WASM_PATH,
+    );
+
+    let treasury_address = payment_address.clone();
+
+    blockchain_wrapper.set_block_timestamp(START_TIMESTAMP);
+    blockchain_wrapper
+        .execute_tx(&owner_address, &subscriptions_wrapper, &rust_zero, |sc| {
+            sc.init();
+
+            sc.set_payment_address(managed_address!(&treasury_address));
+            sc.set_status(true);
+
+            sc.add_plan(managed_buffer!(PLAN_ID), PLAN_VALIDITY);
+            sc.set_subscription_plan_price(
+                managed_buffer!(PLAN_ID),
+                &EgldOrEsdtTokenIdentifier::egld(),
+                managed_biguint!(PLAN_PRICE),
+            );
+        })
+        .assert_ok();
+
+    SubscriptionsSetup {
+        blockchain_wrapper,
+        owner_address,
+        payment_address,
+        subscriptions_wrapper,
+    }
+}
+
+impl<SubscriptionsBuilder> SubscriptionsSetup<SubscriptionsBuilder>
+where
+    SubscriptionsBuilder: 'static + Copy + Fn() -> subscriptions::ContractObj<DebugApi>,
+{
+    pub fn create_user(&mut self, egld_balance: u64) -> Address {
+        self.blockchain_wrapper.create_user_account(&rust_biguint!(egld_balance))
+    }
+
+    pub fn owner_tx<F: FnOnce(subscriptions::ContractObj<DebugApi>)>(&mut self, tx: F) -> TxResult {
+        let owner_address = self.owner_address.clone();
+
+        self.blockchain_wrapper
+            .execute_tx(&owner_address, &self.subscriptions_wrapper, &rust_biguint!(0u64), tx)
+    }
+
+    pub fn user_tx<F: FnOnce(subscriptions::ContractObj<DebugApi>)>(&mut self, user_address: &Address, tx: F) -> TxResult {
+        self.blockchain_wrapper
+            .execute_tx(user_address, &self.subscriptions_wrapper, &rust_biguint!(0u64), tx)
+    }
+
+    pub fn pay_egld(&mut self, user_address: &Address, amount: u64) -> TxResult {
+        self.pay_egld_for(user_address, PLAN_ID, amount)
+    }
+
+    pub fn pay_egld_for(&mut self, user_address: &Address, plan_id: &[u8], amount: u64) -> TxResult {
+        self.blockchain_wrapper
+            .execute_tx(user_address, &self.subscriptions_wrapper, &rust_biguint!(amount), |sc| {
+                sc.pay_with_egld(managed_buffer!(plan_id), MultiValueEncoded::new());
+            })
+    }
+
+    pub fn query<F: FnOnce(subscriptions::ContractObj<DebugApi>)>(&mut self, query: F) {
+        self.blockchain_wrapper
+            .execute_query(&self.subscriptions_wrapper, query)
+            .assert_ok();
+    }
+
+    pub fn set_timestamp(&mut self, timestamp: u64) {
+        self.blockchain_wrapper.set_block_timestamp(timestamp);
+    }
+}
@@ -0,0 +1,146 @@
+mod setup;
+
+use elrond_wasm::types::{Address, MultiValueEncoded};
+use elrond_wasm_debug::{managed_address, managed_biguint, managed_buffer, managed_token_id, rust_biguint, tx_mock::TxResult, DebugApi};
+use setup::*;
+use subscriptions::holders::HoldersModule;
+use subscriptions::migrations::MigrationsModule;
+use subscriptions::private_plans::PrivatePlansModule;
+use subscriptions::staking::StakingModule;
+use subscriptions::storage::StorageModule;
+use subscriptions::views::ViewsModule;
+
+const STAKE_TOKEN: &[u8] = b"STAKE-123456";
+const STAKE_AMOUNT: u64 = 1_000;
+const UNBONDING_PERIOD: u64 = 7 * DAY;
+
+fn setup_staking<SubscriptionsBuilder>(
+    subscriptions_builder: SubscriptionsBuilder,
+) -> SubscriptionsSetup<SubscriptionsBuilder>
+where
+    SubscriptionsBuilder: 'static + Copy + Fn() -> subscriptions::ContractObj<DebugApi>,
+{
+    let mut setup = setup_subscriptions(subscriptions_builder);
+
+    setup
+        .owner_tx(|sc| {
+            sc.set_subscription_plan_stake_requirement(
+                managed_buffer!(PLAN_ID),
+                managed_token_id!(STAKE_TOKEN),
+                managed_biguint!(STAKE_AMOUNT),
+                UNBONDING_PERIOD,
+            );
+        })
+        .assert_ok();
+
+    setup
+}
+
+fn create_staker<SubscriptionsBuilder>(setup: &mut SubscriptionsSetup<SubscriptionsBuilder>) -> Address
+where
+    SubscriptionsBuilder: 'static + Copy + Fn() -> subscriptions::ContractObj<DebugApi>,
+{
+    let staker_address = setup.create_user(PLAN_PRICE * 2);
+    setup
+        .blockchain_wrapper
+        .set_esdt_balance(&staker_address, STAKE_TOKEN, &rust_biguint!(STAKE_AMOUNT));
+
+    staker_address
+}
+
+fn stake<SubscriptionsBuilder>(setup: &mut SubscriptionsSetup<SubscriptionsBuilder>, staker_address: &Address) -> TxResult
+where
+    SubscriptionsBuilder: 'static + Copy + Fn() -> subscriptions::ContractObj<DebugApi>,
+{
+    setup.blockchain_wrapper.execute_esdt_transfer(
+        staker_address,
+        &setup.subscriptions_wrapper,
+        STAKE_TOKEN,
+        0,
+        &rust_biguint!(STAKE_AMOUNT),
+        |sc| sc.stake(managed_buffer!(PLAN_ID), MultiValueEncoded::new()),
+    )
+}
+
+#[test]
+fn stake_unbonding_test() {
+    let mut setup = setup_staking(subscriptions::contract_obj);
+    let staker_address = create_staker(&mut setup);
+
+    // the time paid before staking is given back after the unbonding
+    setup.pay_egld(&staker_address, PLAN_PRICE).assert_ok();
+    stake(&mut setup, &staker_address).assert_ok();
+    setup.pay_egld(&staker_address, PLAN_PRICE).assert_user_error("The plan is active through a stake");
+
+    setup.set_timestamp(START_TIMESTAMP + PLAN_VALIDITY * 2);
+    setup.query(|sc| {
+        assert!(sc.has_active_subscription(&managed_address!(&staker_address), &managed_buffer!(PLAN_ID)));
+        assert_eq!(sc.total_staked(&managed_token_id!(STAKE_TOKEN)).get(), managed_biguint!(STAKE_AMOUNT));
+    });
+
+    let unstaked_at = START_TIMESTAMP + PLAN_VALIDITY * 2;
+
+    setup
+        .user_tx(&staker_address, |sc| sc.unstake(managed_buffer!(PLAN_ID)))
+        .assert_ok();
+    setup
+        .user_tx(&staker_address, |sc| sc.unstake(managed_buffer!(PLAN_ID)))
+        .assert_user_error("The stake is already unbonding");
+    setup.query(|sc| {
+        let user_plan = sc.user_plans(&managed_address!(&staker_address), &managed_buffer!(PLAN_ID)).get();
+
+        assert_eq!(user_plan.expires_at, unstaked_at + UNBONDING_PERIOD + PLAN_VALIDITY);
+    });
+
+    setup.set_timestamp(unstaked_at + UNBONDING_PERIOD - 1);
+    setup
+        .user_tx(&staker_address, |sc| sc.claim_unstaked(managed_buffer!(PLAN_ID)))
+        .assert_user_error("The stake is still locked");
+
+    setup.set_timestamp(unstaked_at + UNBONDING_PERIOD);
+    setup
+        .user_tx(&staker_address, |sc| sc.claim_unstaked(managed_buffer!(PLAN_ID)))
+        .assert_ok();
+    setup
+        .blockchain_wrapper
+        .check_esdt_balance(&staker_address, STAKE_TOKEN, &rust_biguint!(STAKE_AMOUNT));
+    setup.query(|sc| {
+        assert_eq!(sc.total_staked(&managed_token_id!(STAKE_TOKEN)).get(), managed_biguint!(0u64));
+        assert!(sc.has_active_subscription(&managed_address!(&staker_address), &managed_buffer!(PLAN_ID)));
+    });
+
+    setup.set_timestamp(unstaked_at + UNBONDING_PERIOD + PLAN_VALIDITY);
+    setup.query(|sc| assert!(!sc.has_active_subscription(&managed_address!(&staker_address), &managed_buffer!(PLAN_ID))));
+}
+
+#[test]
+fn stake_subscription_checks_test() {
+    let mut setup = setup_staking(subscriptions::contract_obj);
+    let staker_address = create_staker(&mut setup);
+
+    setup
+        .owner_tx(|sc| sc.set_subscription_plan_private(managed_buffer!(PLAN_ID), true))
+        .assert_ok();
+    stake(&mut setup, &staker_address).assert_user_error("This plan is private");
+
+    setup
+        .owner_tx(|sc| {
+            sc.set_subscription_plan_private(managed_buffer!(PLAN_ID), false);
+            sc.set_subscription_plan_holder_benefits(managed_buffer!(PLAN_ID), managed_token_id!(b"HOLDER-123456"), true, 0);
+        })
+        .assert_ok();
+    stake(&mut setup, &staker_address).assert_user_error("This plan is only available to collection holders");
+
+    setup
+        .owner_tx(|sc| {
+            sc.remove_subscription_plan_holder_benefits(managed_buffer!(PLAN_ID));
+            sc.start_migration();
+        })
+        .assert_ok();
+    stake(&mut setup, &staker_address).assert_user_error("Payments are disabled during the migration");
+
+    setup
+        .owner_tx(|sc| sc.finalize_migration())
+        .assert_ok();
+    stake(&mut setup, &staker_address).assert_ok();
+}
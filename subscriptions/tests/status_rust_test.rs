@@ -0,0 +1,24 @@
+mod setup;
+
+use elrond_wasm_debug::rust_biguint;
+use setup::*;
+use subscriptions::storage::StorageModule;
+use subscriptions::Subscriptions;
+
+#[test]
+fn set_status_test() {
+    let mut setup = setup_subscriptions(subscriptions::contract_obj);
+    let user_address = setup.create_user(PLAN_PRICE * 2);
+
+    setup.query(|sc| assert!(sc.enabled().get()));
+    setup.pay_egld(&user_address, PLAN_PRICE).assert_ok();
+
+    setup.owner_tx(|sc| sc.set_status(false)).assert_ok();
+    setup.query(|sc| assert!(!sc.enabled().get()));
+    setup.pay_egld(&user_address, PLAN_PRICE).assert_user_error("Contract is not enabled");
+
+    setup.owner_tx(|sc| sc.set_status(true)).assert_ok();
+    setup.pay_egld(&user_address, PLAN_PRICE).assert_ok();
+
+    setup.blockchain_wrapper.check_egld_balance(&setup.payment_address, &rust_biguint!(PLAN_PRICE * 2));
+}
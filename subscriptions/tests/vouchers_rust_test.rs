@@ -0,0 +1,178 @@
+mod setup;
+
+use ed25519_dalek::{Keypair, PublicKey, SecretKey, Signer};
+use elrond_wasm::types::{Address, ManagedByteArray};
+use elrond_wasm_debug::{managed_address, managed_buffer, tx_mock::TxResult, DebugApi};
+use setup::*;
+use subscriptions::storage::StorageModule;
+use subscriptions::vouchers::{VouchersModule, VOUCHER_DOMAIN};
+
+const CHAIN_ID: &[u8] = b"D";
+const VOUCHER_DURATION: u64 = 7 * DAY;
+const VOUCHER_NONCE: u64 = 1;
+const VOUCHER_EXPIRY: u64 = START_TIMESTAMP + DAY;
+
+fn keypair(seed: u8) -> Keypair {
+    let secret = SecretKey::from_bytes(&[seed; 32]).unwrap();
+    let public = PublicKey::from(&secret);
+
+    Keypair { secret, public }
+}
+
+fn setup_vouchers<SubscriptionsBuilder>(subscriptions_builder: SubscriptionsBuilder, signer: &Keypair) -> SubscriptionsSetup<SubscriptionsBuilder>
+where
+    SubscriptionsBuilder: 'static + Copy + Fn() -> subscriptions::ContractObj<DebugApi>,
+{
+    let mut setup = setup_subscriptions(subscriptions_builder);
+    let public_key = signer.public.to_bytes();
+
+    setup
+        .owner_tx(|sc| sc.set_voucher_signer(ManagedByteArray::new_from_bytes(&public_key), managed_buffer!(CHAIN_ID)))
+        .assert_ok();
+
+    setup
+}
+
+fn voucher_message(chain_id: &[u8], contract_address: &Address, caller: &Address, nonce: u64, expiry: u64) -> Vec<u8> {
+    let mut message = VOUCHER_DOMAIN.to_vec();
+
+    message.extend_from_slice(&(chain_id.len() as u32).to_be_bytes());
+    message.extend_from_slice(chain_id);
+    message.extend_from_slice(contract_address.as_bytes());
+    message.extend_from_slice(caller.as_bytes());
+    message.extend_from_slice(&(PLAN_ID.len() as u32).to_be_bytes());
+    message.extend_from_slice(PLAN_ID);
+    message.extend_from_slice(&VOUCHER_DURATION.to_be_bytes());
+    message.extend_from_slice(&nonce.to_be_bytes());
+    message.extend_from_slice(&expiry.to_be_bytes());
+
+    message
+}
+
+fn sign_voucher<SubscriptionsBuilder>(
+    setup: &SubscriptionsSetup<SubscriptionsBuilder>,
+    signer: &Keypair,
+    caller: &Address,
+    nonce: u64,
+    expiry: u64,
+) -> [u8; 64]
+where
+    SubscriptionsBuilder: 'static + Copy + Fn() -> subscriptions::ContractObj<DebugApi>,
+{
+    let message = voucher_message(CHAIN_ID, setup.subscriptions_wrapper.address_ref(), caller, nonce, expiry);
+
+    signer.sign(&message).to_bytes()
+}
+
+fn redeem_voucher<SubscriptionsBuilder>(
+    setup: &mut SubscriptionsSetup<SubscriptionsBuilder>,
+    caller: &Address,
+    nonce: u64,
+    expiry: u64,
+    signature: [u8; 64],
+) -> TxResult
+where
+    SubscriptionsBuilder: 'static + Copy + Fn() -> subscriptions::ContractObj<DebugApi>,
+{
+    setup.user_tx(caller, |sc| {
+        sc.redeem_voucher(
+            managed_buffer!(PLAN_ID),
+            VOUCHER_DURATION,
+            nonce,
+            expiry,
+            ManagedByteArray::new_from_bytes(&signature),
+        );
+    })
+}
+
+#[test]
+fn redeem_voucher_test() {
+    let signer = keypair(7);
+    let mut setup = setup_vouchers(subscriptions::contract_obj, &signer);
+    let user_address = setup.create_user(0);
+    let signature = sign_voucher(&setup, &signer, &user_address, VOUCHER_NONCE, VOUCHER_EXPIRY);
+
+    redeem_voucher(&mut setup, &user_address, VOUCHER_NONCE, VOUCHER_EXPIRY, signature).assert_ok();
+
+    setup.query(|sc| {
+        let user_plan = sc.user_plans(&managed_address!(&user_address), &managed_buffer!(PLAN_ID)).get();
+
+        assert_eq!(user_plan.expires_at, START_TIMESTAMP + VOUCHER_DURATION);
+        assert!(sc.redeemed_vouchers(VOUCHER_NONCE).get());
+    });
+}
+
+#[test]
+fn redeem_voucher_replay_test() {
+    let signer = keypair(7);
+    let mut setup = setup_vouchers(subscriptions::contract_obj, &signer);
+    let user_address = setup.create_user(0);
+    let other_user_address = setup.create_user(0);
+
+    let signature = sign_voucher(&setup, &signer, &user_address, VOUCHER_NONCE, VOUCHER_EXPIRY);
+    redeem_voucher(&mut setup, &user_address, VOUCHER_NONCE, VOUCHER_EXPIRY, signature).assert_ok();
+    redeem_voucher(&mut setup, &user_address, VOUCHER_NONCE, VOUCHER_EXPIRY, signature)
+        .assert_user_error("This voucher was already redeemed");
+
+    let other_signature = sign_voucher(&setup, &signer, &other_user_address, VOUCHER_NONCE, VOUCHER_EXPIRY);
+    redeem_voucher(&mut setup, &other_user_address, VOUCHER_NONCE, VOUCHER_EXPIRY, other_signature)
+        .assert_user_error("This voucher was already redeemed");
+}
+
+#[test]
+fn redeem_expired_voucher_test() {
+    let signer = keypair(7);
+    let mut setup = setup_vouchers(subscriptions::contract_obj, &signer);
+    let user_address = setup.create_user(0);
+    let signature = sign_voucher(&setup, &signer, &user_address, VOUCHER_NONCE, VOUCHER_EXPIRY);
+
+    setup.set_timestamp(VOUCHER_EXPIRY + 1);
+
+    redeem_voucher(&mut setup, &user_address, VOUCHER_NONCE, VOUCHER_EXPIRY, signature)
+        .assert_user_error("This voucher is expired");
+}
+
+#[test]
+fn redeem_voucher_bad_signature_test() {
+    let signer = keypair(7);
+    let mut setup = setup_vouchers(subscriptions::contract_obj, &signer);
+    let user_address = setup.create_user(0);
+
+    // signed by another key
+    let signature = sign_voucher(&setup, &keypair(8), &user_address, VOUCHER_NONCE, VOUCHER_EXPIRY);
+    redeem_voucher(&mut setup, &user_address, VOUCHER_NONCE, VOUCHER_EXPIRY, signature)
+        .assert_user_error("Invalid voucher signature");
+
+    // signed for other voucher fields
+    let signature = sign_voucher(&setup, &signer, &user_address, VOUCHER_NONCE, VOUCHER_EXPIRY + 1);
+    redeem_voucher(&mut setup, &user_address, VOUCHER_NONCE, VOUCHER_EXPIRY, signature)
+        .assert_user_error("Invalid voucher signature");
+
+    // signed for another chain
+    let message = voucher_message(b"1", setup.subscriptions_wrapper.address_ref(), &user_address, VOUCHER_NONCE, VOUCHER_EXPIRY);
+    let signature = signer.sign(&message).to_bytes();
+    redeem_voucher(&mut setup, &user_address, VOUCHER_NONCE, VOUCHER_EXPIRY, signature)
+        .assert_user_error("Invalid voucher signature");
+
+    // signed for another contract
+    let other_contract_address = setup.create_user(0);
+    let message = voucher_message(CHAIN_ID, &other_contract_address, &user_address, VOUCHER_NONCE, VOUCHER_EXPIRY);
+    let signature = signer.sign(&message).to_bytes();
+    redeem_voucher(&mut setup, &user_address, VOUCHER_NONCE, VOUCHER_EXPIRY, signature)
+        .assert_user_error("Invalid voucher signature");
+
+    setup.query(|sc| assert!(!sc.redeemed_vouchers(VOUCHER_NONCE).get()));
+}
+
+#[test]
+fn redeem_voucher_wrong_caller_test() {
+    let signer = keypair(7);
+    let mut setup = setup_vouchers(subscriptions::contract_obj, &signer);
+    let user_address = setup.create_user(0);
+    let other_user_address = setup.create_user(0);
+    let signature = sign_voucher(&setup, &signer, &user_address, VOUCHER_NONCE, VOUCHER_EXPIRY);
+
+    redeem_voucher(&mut setup, &other_user_address, VOUCHER_NONCE, VOUCHER_EXPIRY, signature)
+        .assert_user_error("Invalid voucher signature");
+    redeem_voucher(&mut setup, &user_address, VOUCHER_NONCE, VOUCHER_EXPIRY, signature).assert_ok();
+}
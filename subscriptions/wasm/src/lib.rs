@@ -7,26 +7,205 @@
 elrond_wasm_node::wasm_endpoints! {
     subscriptions
     (
+        addDelegate
+        addDeniedAddresses
+        addGrantManager
+        addMerchantSubscriptionPlan
+        addQuotaConsumer
         addSubscriptionPlan
+        addSubscriptionPlanAllowedAddresses
+        addSubscriptionPlanPrerequisite
+        addSubscriptionPlanPromotion
+        addUsageReporter
+        allowSubscriptionPlanTokenNonces
+        archiveMerchantSubscriptionPlan
+        archiveSubscriptionPlan
+        claimRevenue
+        claimUnstaked
+        cleanupUserPlans
+        clearSubscriptionPlanAllowlistRoot
+        consumeQuota
+        depositUsageFunds
+        deprecateMerchantSubscriptionPlan
+        deprecateSubscriptionPlan
+        disableMerchantSubscriptionPlan
         disableSubscriptionPlan
+        enableMerchantSubscriptionPlan
         enableSubscriptionPlan
+        exportPaymentTotals
+        exportUserPaymentTotals
+        exportUserPlans
+        finalizeMigration
+        getAccumulatedBalance
+        getAccumulatedTokenIds
+        getActiveSubscriberCount
+        getDelegateOwner
+        getDelegateRemovedAt
+        getDelegates
+        getDeniedAddresses
+        getEffectivePrice
+        getEntitlementPlanIds
+        getEscrowRevenue
+        getEscrowTokenIds
+        getExpiredCount
+        getExpiringBetween
+        getExpiryPruneDay
+        getGrantManagers
+        getLoyaltyPointRate
+        getLoyaltyPoints
+        getLoyaltyRenewalBonus
+        getMerchant
+        getMerchantId
+        getMerchantPlanIds
+        getMerchantSubscriptionPlanPrices
+        getMerchantSubscriptionPlans
+        getMerchantUserPlans
+        getOutstandingUsageCharge
+        getOutstandingUsageCharges
+        getPaymentCount
+        getPaymentDestination
+        getPlanActivations
         getPlanIds
         getPlanInfo
+        getPlanMerchant
+        getPlatformFee
+        getPlatformFees
+        getQuotaConsumers
+        getRedeemableDays
+        getRemainingQuota
+        getSubscriptionPlanAddon
+        getSubscriptionPlanAddonIds
+        getSubscriptionPlanAllowlist
+        getSubscriptionPlanAllowlistRoot
+        getSubscriptionPlanBundle
+        getSubscriptionPlanBundleComponents
+        getSubscriptionPlanDelegateSettings
+        getSubscriptionPlanEntitlement
+        getSubscriptionPlanEntitlementKeys
+        getSubscriptionPlanEntitlements
+        getSubscriptionPlanHolderBenefits
+        getSubscriptionPlanMaxHorizon
+        getSubscriptionPlanNoncePrices
+        getSubscriptionPlanNonceTokens
+        getSubscriptionPlanOverpaymentPolicy
+        getSubscriptionPlanPauseSettings
+        getSubscriptionPlanPointsPerDay
+        getSubscriptionPlanPrerequisites
         getSubscriptionPlanPrices
+        getSubscriptionPlanPromotions
+        getSubscriptionPlanRenewalMode
+        getSubscriptionPlanStakeRequirement
+        getSubscriptionPlanSubscriberCount
+        getSubscriptionPlanSubscribers
+        getSubscriptionPlanTokenPaymentAddress
         getSubscriptionPlans
+        getSwapRoute
+        getSweepThreshold
+        getTokenPaymentAddress
+        getTotalStaked
+        getUsageDeposit
+        getUsageReporters
+        getUserCount
+        getUserCredit
+        getUserDelegates
+        getUserGrantedTime
         getUserPlanIds
         getUserPlanInfo
         getUserPlans
+        getUserStake
         getUsers
+        getVoucherChainId
+        getVoucherSigner
         getpaymentTokenIds
+        grantSubscription
+        grantSubscriptions
+        hasActiveSubscription
+        importPaymentTotals
+        importUserPaymentTotals
+        importUserPlans
+        isAccumulationEnabled
+        isDenied
         isEnabled
+        isEscrowEnabled
+        isMigrationFinalized
+        isMigrationMode
+        isSubscriptionPlanPrivate
+        isVoucherRedeemed
+        pauseSubscription
+        payAsHolder
+        payWithCredit
         payWithEgld
         payWithEsdt
+        payWithSwap
+        pruneExpiredSubscriptions
+        redeemPoints
+        redeemVoucher
+        registerMerchant
+        releaseMerchantSubscriptionPlanSubscribers
+        releaseSubscriptionPlanSubscribers
+        removeDelegate
+        removeDeniedAddresses
+        removeGrantManager
+        removeMerchantSubscriptionPlan
+        removeMerchantSubscriptionPlanToken
+        removeQuotaConsumer
         removeSubscriptionPlan
+        removeSubscriptionPlanAddon
+        removeSubscriptionPlanAllowedAddresses
+        removeSubscriptionPlanBundleComponent
+        removeSubscriptionPlanDelegateSettings
+        removeSubscriptionPlanEntitlement
+        removeSubscriptionPlanHolderBenefits
+        removeSubscriptionPlanPauseSettings
+        removeSubscriptionPlanPrerequisite
+        removeSubscriptionPlanPromotion
+        removeSubscriptionPlanStakeRequirement
         removeSubscriptionPlanToken
+        removeSubscriptionPlanTokenNonce
+        removeSubscriptionPlanTokenPaymentAddress
+        removeSwapRoute
+        removeTokenPaymentAddress
+        removeUsageReporter
+        reportUsage
+        resumeSubscription
+        revokeSubscription
+        setAccumulationMode
+        setEscrowMode
+        setLoyaltyPointRate
+        setLoyaltyRenewalBonus
+        setMerchantPaymentAddress
+        setMerchantStatus
+        setMerchantSubscriptionPlanPrice
         setPaymentAddress
+        setPlatformFee
         setStatus
+        setSubscriptionPlanAddon
+        setSubscriptionPlanAllowlistRoot
+        setSubscriptionPlanBundleComponent
+        setSubscriptionPlanDelegateSettings
+        setSubscriptionPlanEntitlement
+        setSubscriptionPlanHolderBenefits
+        setSubscriptionPlanMaxHorizon
+        setSubscriptionPlanNoncePrice
+        setSubscriptionPlanOverpaymentPolicy
+        setSubscriptionPlanPauseSettings
+        setSubscriptionPlanPointsPerDay
         setSubscriptionPlanPrice
+        setSubscriptionPlanPrivate
+        setSubscriptionPlanRenewalMode
+        setSubscriptionPlanStakeRequirement
+        setSubscriptionPlanTokenPaymentAddress
+        setSubscriptionPlanTokenSettlement
+        setSwapRoute
+        setSweepThreshold
+        setTokenPaymentAddress
+        setVoucherSigner
+        settleUsage
+        stake
+        startMigration
+        sweep
+        unstake
+        withdrawUsageFunds
     )
 }
 